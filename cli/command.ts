@@ -2,7 +2,8 @@
 // @ts-ignore
 import { program } from 'commander';
 import { PublicKey } from '@solana/web3.js';
-import { cancelAuction, claimAuction, createAuction,  getAllAuction,  getAuctionInfo,  initialize,  placeBid,  setClusterConfig, getWalletKeypair} from "./script";
+import { buyNow, cancelAuction, claimAuction, commitBid, createAuction,  createCompressedAuction,  createCoreAuction,  getAllAuction,  getLegacyAuctions,  openBidBook,  closeBidBook,  getBidBookInfo,  getAuctionInfo,  initialize,  migrateGlobalAuthority,  placeBid,  forceCancel,  releaseQuarantinedNft,  setPaused,  freezeAuction,  proposeAdmin,  acceptAdmin,  setRole,  updateConfig,  setSoftCloseBounds,  getGlobalState,  getGlobalInfo,  setFeeSplit,  distributeFees,  getTreasuryInfo,  addQuoteMint,  removeQuoteMint,  addCollection,  updateCollection,  removeCollection,  setStrictCollections,  setIncrementSchedule,  getNextMinBid,  getCollectionState,  getCollectionInfo,  revealBid,  setClusterConfig, settleExpiredAuction, getWalletKeypair, withdrawSealedBid} from "./script";
+import * as anchor from '@project-serum/anchor';
 import * as log from 'loglevel';
 
 const TAG = '[AUCTION]';
@@ -25,13 +26,442 @@ programCommand('init')
     });
 
 
+programCommand('migrate')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc } = cmd.opts();
+        log.debug(TAG, '[migrate]', 'Solana Env Config:', env);
+        log.debug(TAG, '[migrate]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[migrate]', 'RPC URL:', rpc);
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await migrateGlobalAuthority();
+    });
+
+
+programCommand('propose_admin')
+    .option('-a, --admin <string>', 'new super admin address, the default pubkey withdraws the proposal')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc, admin } = cmd.opts();
+        log.debug(TAG, '[propose_admin]', 'Solana Env Config:', env);
+        log.debug(TAG, '[propose_admin]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[propose_admin]', 'RPC URL:', rpc);
+
+        if (admin === undefined) {
+            log.error(TAG, '[propose_admin]', "Error Admin Address Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await proposeAdmin(new PublicKey(admin));
+    });
+
+
+programCommand('accept_admin')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc } = cmd.opts();
+        log.debug(TAG, '[accept_admin]', 'Solana Env Config:', env);
+        log.debug(TAG, '[accept_admin]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[accept_admin]', 'RPC URL:', rpc);
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await acceptAdmin();
+    });
+
+
+programCommand('set_role')
+    .option('-role, --role <number>', 'operator role [0: fee manager, 1: pauser, 2: collection curator]')
+    .option('-o, --operator <string>', 'operator address, the default pubkey revokes the role')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc, role, operator } = cmd.opts();
+        log.debug(TAG, '[set_role]', 'Solana Env Config:', env);
+        log.debug(TAG, '[set_role]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[set_role]', 'RPC URL:', rpc);
+
+        if (role === undefined || isNaN(parseInt(role))) {
+            log.error(TAG, '[set_role]', "Error Role Input");
+            return;
+        }
+        if (operator === undefined) {
+            log.error(TAG, '[set_role]', "Error Operator Address Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await setRole(parseInt(role), new PublicKey(operator));
+    });
+
+
+programCommand('set_paused')
+    .option('-p, --paused <boolean>', 'pause [true] or resume [false] the program')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc, paused } = cmd.opts();
+        log.debug(TAG, '[set_paused]', 'Solana Env Config:', env);
+        log.debug(TAG, '[set_paused]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[set_paused]', 'RPC URL:', rpc);
+
+        if (paused !== 'true' && paused !== 'false') {
+            log.error(TAG, '[set_paused]', "Error Paused Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await setPaused(paused === 'true');
+    });
+
+
+programCommand('freeze_auction')
+    .option('-pda, --pda <string>', 'auction pda address')
+    .option('-f, --frozen <boolean>', 'freeze [true] or unfreeze [false] the auction')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc, pda, frozen } = cmd.opts();
+        log.debug(TAG, '[freeze_auction]', 'Solana Env Config:', env);
+        log.debug(TAG, '[freeze_auction]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[freeze_auction]', 'RPC URL:', rpc);
+
+        if (pda === undefined) {
+            log.error(TAG, '[freeze_auction]', "Error PDA Input");
+            return;
+        }
+        if (frozen !== 'true' && frozen !== 'false') {
+            log.error(TAG, '[freeze_auction]', "Error Frozen Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await freezeAuction(new PublicKey(pda), frozen === 'true');
+    });
+
+
+programCommand('update_config')
+    .option('-f, --fee_percent <number>', '[optional] fee taken from every sale [2 means 2%]')
+    .option('-mi, --min_increment <number>', '[optional] minimum bid increment in base units of the quote mint')
+    .option('-mp, --min_increment_percent <number>', '[optional] minimum bid increment [5 means 5%]')
+    .option('-md, --min_duration_after_bid <number>', '[optional] anti-sniping extension [600 means 600 seconds]')
+    .option('-day, --day <number>', '[optional] length of a day for auction durations [86400 means 86400 seconds]')
+    .option('-rf, --royalty_fallback <number>', '[optional] who takes unpaid royalties [0: seller, 1: treasury]')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc, fee_percent, min_increment, min_increment_percent, min_duration_after_bid, day, royalty_fallback } = cmd.opts();
+        log.debug(TAG, '[update_config]', 'Solana Env Config:', env);
+        log.debug(TAG, '[update_config]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[update_config]', 'RPC URL:', rpc);
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+
+        // Values left out keep their current setting
+        const config = await getGlobalState();
+        if (fee_percent !== undefined) config.feePercent = new anchor.BN(fee_percent);
+        if (min_increment !== undefined) config.minIncrement = new anchor.BN(min_increment);
+        if (min_increment_percent !== undefined) config.minIncrementPercent = new anchor.BN(min_increment_percent);
+        if (min_duration_after_bid !== undefined) config.minDurationAfterBidSecs = new anchor.BN(min_duration_after_bid);
+        if (day !== undefined) config.day = new anchor.BN(day);
+        if (royalty_fallback !== undefined) config.royaltyFallback = parseInt(royalty_fallback);
+        await updateConfig(config);
+    });
+
+
+programCommand('set_soft_close_bounds')
+    .option('-min, --min_secs <number>', 'shortest soft-close window and extension sellers may choose [60 means 60 seconds]')
+    .option('-max, --max_secs <number>', 'longest soft-close window and extension sellers may choose [3600 means 3600 seconds]')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc, min_secs, max_secs } = cmd.opts();
+        log.debug(TAG, '[set_soft_close_bounds]', 'Solana Env Config:', env);
+        log.debug(TAG, '[set_soft_close_bounds]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[set_soft_close_bounds]', 'RPC URL:', rpc);
+
+        if (min_secs === undefined || isNaN(parseInt(min_secs)) || max_secs === undefined || isNaN(parseInt(max_secs))) {
+            log.error(TAG, '[set_soft_close_bounds]', "Error Bounds Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await setSoftCloseBounds(parseInt(min_secs), parseInt(max_secs));
+    });
+
+
+programCommand('set_fee_split')
+    .option('-rc, --recipients <string>', 'comma separated wallets receiving the fees')
+    .option('-s, --shares <string>', 'comma separated shares of the recipients [2500 means 25%]')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc, recipients, shares } = cmd.opts();
+        log.debug(TAG, '[set_fee_split]', 'Solana Env Config:', env);
+        log.debug(TAG, '[set_fee_split]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[set_fee_split]', 'RPC URL:', rpc);
+
+        if (recipients === undefined || shares === undefined) {
+            log.error(TAG, '[set_fee_split]', "Error Split Input");
+            return;
+        }
+        const recipientList = recipients.split(',').map((recipient: string) => new PublicKey(recipient.trim()));
+        const shareList = shares.split(',').map((share: string) => parseInt(share));
+        if (recipientList.length !== shareList.length || shareList.some((share: number) => isNaN(share))) {
+            log.error(TAG, '[set_fee_split]', "Error Split Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await setFeeSplit(recipientList, shareList);
+    });
+
+
+programCommand('distribute_fees')
+    .option('-q, --quote_mint <string>', 'quote mint of the fees to pay out, or SOL')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc, quote_mint } = cmd.opts();
+        log.debug(TAG, '[distribute_fees]', 'Solana Env Config:', env);
+        log.debug(TAG, '[distribute_fees]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[distribute_fees]', 'RPC URL:', rpc);
+
+        if (quote_mint === undefined) {
+            log.error(TAG, '[distribute_fees]', "Error Quote Mint Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await distributeFees(parseQuoteMint(quote_mint));
+    });
+
+
+programCommand('get_treasury_info')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc } = cmd.opts();
+        log.debug(TAG, '[get_treasury_info]', 'Solana Env Config:', env);
+        log.debug(TAG, '[get_treasury_info]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[get_treasury_info]', 'RPC URL:', rpc);
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        log.info(TAG, '[get_treasury_info]', 'Treasury:', await getTreasuryInfo());
+    });
+
+
+programCommand('get_global_info')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc } = cmd.opts();
+        log.debug(TAG, '[get_global_info]', 'Solana Env Config:', env);
+        log.debug(TAG, '[get_global_info]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[get_global_info]', 'RPC URL:', rpc);
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        log.info(TAG, '[get_global_info]', 'Config:', await getGlobalInfo());
+    });
+
+
+programCommand('add_quote_mint')
+    .option('-q, --quote_mint <string>', 'mint to whitelist')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc, quote_mint } = cmd.opts();
+        log.debug(TAG, '[add_quote_mint]', 'Solana Env Config:', env);
+        log.debug(TAG, '[add_quote_mint]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[add_quote_mint]', 'RPC URL:', rpc);
+
+        if (quote_mint === undefined) {
+            log.error(TAG, '[add_quote_mint]', "Error Quote Mint Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await addQuoteMint(new PublicKey(quote_mint));
+    });
+
+
+programCommand('remove_quote_mint')
+    .option('-q, --quote_mint <string>', 'mint to remove from the whitelist')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc, quote_mint } = cmd.opts();
+        log.debug(TAG, '[remove_quote_mint]', 'Solana Env Config:', env);
+        log.debug(TAG, '[remove_quote_mint]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[remove_quote_mint]', 'RPC URL:', rpc);
+
+        if (quote_mint === undefined) {
+            log.error(TAG, '[remove_quote_mint]', "Error Quote Mint Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await removeQuoteMint(new PublicKey(quote_mint));
+    });
+
+
+programCommand('add_collection')
+    .option('-c, --collection <string>', 'collection address, or the first verified creator of NFTs without a collection')
+    .option('-b, --blocked <boolean>', '[optional] block listings of the collection', 'false')
+    .option('-f, --fee_percent <number>', '[optional] fee taken from its sales instead of the global fee [2 means 2%]')
+    .option('-mp, --min_price <number>', '[optional] minimum start price, floor price for Dutch auctions, in base units of the quote mint', '0')
+    .option('-mind, --min_duration <number>', '[optional] minimum duration [100 means 100 seconds]', '0')
+    .option('-maxd, --max_duration <number>', '[optional] maximum duration [100 means 100 seconds]', '0')
+    .option('-q, --quote_mints <string>', '[optional] comma separated quote mints listings may use, "SOL" for native SOL')
+    .option('-t, --tiers <string>', '[optional] bid increment schedule of its English auctions as from:increment:bps,... [0:1000000:500,1000000000:0:250]')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc, collection, blocked, fee_percent, min_price, min_duration, max_duration, quote_mints, tiers } = cmd.opts();
+        log.debug(TAG, '[add_collection]', 'Solana Env Config:', env);
+        log.debug(TAG, '[add_collection]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[add_collection]', 'RPC URL:', rpc);
+
+        if (collection === undefined) {
+            log.error(TAG, '[add_collection]', "Error Collection Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await addCollection(new PublicKey(collection), {
+            blocked: blocked === 'true',
+            feeOverride: fee_percent !== undefined ? new anchor.BN(fee_percent) : null,
+            minPrice: new anchor.BN(min_price),
+            minDuration: new anchor.BN(min_duration),
+            maxDuration: new anchor.BN(max_duration),
+            quoteMints: quote_mints !== undefined ? quote_mints.split(',').map((quoteMint: string) => parseQuoteMint(quoteMint.trim())) : [],
+            incrementTiers: parseIncrementTiers(tiers),
+        });
+    });
+
+
+programCommand('update_collection')
+    .option('-c, --collection <string>', 'registered collection address')
+    .option('-b, --blocked <boolean>', '[optional] block listings of the collection')
+    .option('-f, --fee_percent <number>', '[optional] fee taken from its sales, "none" for the global fee [2 means 2%]')
+    .option('-mp, --min_price <number>', '[optional] minimum start price, floor price for Dutch auctions, in base units of the quote mint')
+    .option('-mind, --min_duration <number>', '[optional] minimum duration [100 means 100 seconds]')
+    .option('-maxd, --max_duration <number>', '[optional] maximum duration [100 means 100 seconds]')
+    .option('-q, --quote_mints <string>', '[optional] comma separated quote mints listings may use, "SOL" for native SOL, "any" for no limit')
+    .option('-t, --tiers <string>', '[optional] bid increment schedule as from:increment:bps,..., "global" for the global increments')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc, collection, blocked, fee_percent, min_price, min_duration, max_duration, quote_mints, tiers } = cmd.opts();
+        log.debug(TAG, '[update_collection]', 'Solana Env Config:', env);
+        log.debug(TAG, '[update_collection]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[update_collection]', 'RPC URL:', rpc);
+
+        if (collection === undefined) {
+            log.error(TAG, '[update_collection]', "Error Collection Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+
+        // Values left out keep their current setting
+        const config = await getCollectionState(new PublicKey(collection));
+        if (!config) {
+            log.error(TAG, '[update_collection]', "Collection Not Registered");
+            return;
+        }
+        const policy = config.policy;
+        if (blocked !== undefined) policy.blocked = blocked === 'true';
+        if (fee_percent !== undefined) policy.feeOverride = fee_percent === 'none' ? null : new anchor.BN(fee_percent);
+        if (min_price !== undefined) policy.minPrice = new anchor.BN(min_price);
+        if (min_duration !== undefined) policy.minDuration = new anchor.BN(min_duration);
+        if (max_duration !== undefined) policy.maxDuration = new anchor.BN(max_duration);
+        if (quote_mints !== undefined) {
+            policy.quoteMints = quote_mints === 'any' ? [] : quote_mints.split(',').map((quoteMint: string) => parseQuoteMint(quoteMint.trim()));
+        }
+        if (tiers !== undefined) policy.incrementTiers = tiers === 'global' ? [] : parseIncrementTiers(tiers);
+        await updateCollection(new PublicKey(collection), policy);
+    });
+
+
+programCommand('remove_collection')
+    .option('-c, --collection <string>', 'collection address to remove from the registry')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc, collection } = cmd.opts();
+        log.debug(TAG, '[remove_collection]', 'Solana Env Config:', env);
+        log.debug(TAG, '[remove_collection]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[remove_collection]', 'RPC URL:', rpc);
+
+        if (collection === undefined) {
+            log.error(TAG, '[remove_collection]', "Error Collection Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await removeCollection(new PublicKey(collection));
+    });
+
+
+programCommand('set_strict_collections')
+    .option('-s, --strict <boolean>', 'only list registered collections [true] or any collection [false]')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc, strict } = cmd.opts();
+        log.debug(TAG, '[set_strict_collections]', 'Solana Env Config:', env);
+        log.debug(TAG, '[set_strict_collections]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[set_strict_collections]', 'RPC URL:', rpc);
+
+        if (strict !== 'true' && strict !== 'false') {
+            log.error(TAG, '[set_strict_collections]', "Error Strict Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await setStrictCollections(strict === 'true');
+    });
+
+
+programCommand('get_collection_info')
+    .option('-c, --collection <string>', 'collection address')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc, collection } = cmd.opts();
+        log.debug(TAG, '[get_collection_info]', 'Solana Env Config:', env);
+        log.debug(TAG, '[get_collection_info]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[get_collection_info]', 'RPC URL:', rpc);
+
+        if (collection === undefined) {
+            log.error(TAG, '[get_collection_info]', "Error Collection Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        log.info(TAG, '[get_collection_info]', 'Policy:', await getCollectionInfo(new PublicKey(collection)));
+    });
+
+
 programCommand('create_auction')
     .option('-mint, --mint <string>', 'NFT mint address')
-    .option('-s, --start_price <number>', 'start price of the auction [10.1 means 10.1 HTO]')
+    .option('-s, --start_price <number>', 'start price of the auction [10.1 means 10.1 quote tokens]')
     .option('-d, --duration <number>', 'duration of the auction [100 means 100 seconds]')
+    .option('-bn, --buy_now_price <number>', '[optional] instant purchase price [10.1 means 10.1 quote tokens]')
+    .option('-bc, --buy_now_cutoff <number>', 'buy-now closes once bids exceed this share of its price [5000 means 50%]', '5000')
+    .option('-rp, --reserve_price <number>', '[optional] hidden reserve revealed at claim [10.1 means 10.1 quote tokens]')
+    .option('-q, --quote_mint <string>', '[optional] mint bids are paid in, "SOL" for native SOL, defaults to HTO')
+    .option('-sw, --soft_close_window <number>', '[optional] bids this close to the end extend the auction [600 means 600 seconds]')
+    .option('-se, --soft_close_extension <number>', '[optional] how far past a late bid the auction then ends [600 means 600 seconds]', '600')
+    .option('-sm, --max_extension <number>', '[optional] cap on the total extension, 0 for none [3600 means 3600 seconds]', '0')
     // eslint-disable-next-line @typescript-eslint/no-unused-vars
     .action(async (directory, cmd) => {
-        const {env, keypair, rpc, mint, start_price, duration} = cmd.opts();
+        const {env, keypair, rpc, mint, start_price, duration, buy_now_price, buy_now_cutoff, reserve_price, quote_mint, soft_close_window, soft_close_extension, max_extension} = cmd.opts();
         log.debug(TAG, '[create_auction]', 'Solana Env Config:', env);
         log.debug(TAG, '[create_auction]', 'Keypair Path:', keypair);
         log.debug(TAG, '[create_auction]', 'RPC URL:', rpc);
@@ -51,7 +481,202 @@ programCommand('create_auction')
         
         const walletKeypair = getWalletKeypair(keypair);       
         await setClusterConfig(env, walletKeypair, rpc);
-        await createAuction(new PublicKey(mint), start_price, duration);
+        let buyNow = undefined;
+        if (buy_now_price !== undefined) {
+            buyNow = { price: buy_now_price, cutoffBps: parseInt(buy_now_cutoff) };
+        }
+        // Without a window the global default applies
+        let softClose = undefined;
+        if (soft_close_window !== undefined) {
+            softClose = {
+                windowSecs: parseInt(soft_close_window),
+                extensionSecs: parseInt(soft_close_extension),
+                maxExtensionSecs: parseInt(max_extension),
+            };
+        }
+        await createAuction(new PublicKey(mint), start_price, duration, undefined, undefined, buyNow, reserve_price, parseQuoteMint(quote_mint), softClose);
+    });
+
+
+programCommand('create_compressed_auction')
+    .option('-a, --asset <string>', 'cNFT asset id, read with its proof from a DAS RPC')
+    .option('-s, --start_price <number>', 'start price of the auction [10.1 means 10.1 quote tokens]')
+    .option('-d, --duration <number>', 'duration of the auction [100 means 100 seconds]')
+    .option('-bn, --buy_now_price <number>', '[optional] instant purchase price [10.1 means 10.1 quote tokens]')
+    .option('-bc, --buy_now_cutoff <number>', 'buy-now closes once bids exceed this share of its price [5000 means 50%]', '5000')
+    .option('-rp, --reserve_price <number>', '[optional] hidden reserve revealed at claim [10.1 means 10.1 quote tokens]')
+    .option('-q, --quote_mint <string>', '[optional] mint bids are paid in, "SOL" for native SOL, defaults to HTO')
+    .option('-sw, --soft_close_window <number>', '[optional] bids this close to the end extend the auction [600 means 600 seconds]')
+    .option('-se, --soft_close_extension <number>', '[optional] how far past a late bid the auction then ends [600 means 600 seconds]', '600')
+    .option('-sm, --max_extension <number>', '[optional] cap on the total extension, 0 for none [3600 means 3600 seconds]', '0')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const {env, keypair, rpc, asset, start_price, duration, buy_now_price, buy_now_cutoff, reserve_price, quote_mint, soft_close_window, soft_close_extension, max_extension} = cmd.opts();
+        log.debug(TAG, '[create_compressed_auction]', 'Solana Env Config:', env);
+        log.debug(TAG, '[create_compressed_auction]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[create_compressed_auction]', 'RPC URL:', rpc);
+
+        if (asset === undefined) {
+            log.error(TAG, '[create_compressed_auction]', "Error Asset Id Input");
+            return;
+        }
+        if (start_price === undefined || isNaN(parseInt(start_price))) {
+            log.error(TAG, '[create_compressed_auction]', "Error Start Price Input");
+            return;
+        }
+        if (duration === undefined || isNaN(parseInt(duration))) {
+            log.error(TAG, '[create_compressed_auction]', "Error Duration Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        let buyNow = undefined;
+        if (buy_now_price !== undefined) {
+            buyNow = { price: buy_now_price, cutoffBps: parseInt(buy_now_cutoff) };
+        }
+        // Without a window the global default applies
+        let softClose = undefined;
+        if (soft_close_window !== undefined) {
+            softClose = {
+                windowSecs: parseInt(soft_close_window),
+                extensionSecs: parseInt(soft_close_extension),
+                maxExtensionSecs: parseInt(max_extension),
+            };
+        }
+        await createCompressedAuction(new PublicKey(asset), start_price, duration, buyNow, reserve_price, parseQuoteMint(quote_mint), softClose);
+    });
+
+
+programCommand('create_core_auction')
+    .option('-a, --asset <string>', 'Core asset address, a member of a Core collection')
+    .option('-s, --start_price <number>', 'start price of the auction [10.1 means 10.1 quote tokens]')
+    .option('-d, --duration <number>', 'duration of the auction [100 means 100 seconds]')
+    .option('-bn, --buy_now_price <number>', '[optional] instant purchase price [10.1 means 10.1 quote tokens]')
+    .option('-bc, --buy_now_cutoff <number>', 'buy-now closes once bids exceed this share of its price [5000 means 50%]', '5000')
+    .option('-rp, --reserve_price <number>', '[optional] hidden reserve revealed at claim [10.1 means 10.1 quote tokens]')
+    .option('-q, --quote_mint <string>', '[optional] mint bids are paid in, "SOL" for native SOL, defaults to HTO')
+    .option('-sw, --soft_close_window <number>', '[optional] bids this close to the end extend the auction [600 means 600 seconds]')
+    .option('-se, --soft_close_extension <number>', '[optional] how far past a late bid the auction then ends [600 means 600 seconds]', '600')
+    .option('-sm, --max_extension <number>', '[optional] cap on the total extension, 0 for none [3600 means 3600 seconds]', '0')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const {env, keypair, rpc, asset, start_price, duration, buy_now_price, buy_now_cutoff, reserve_price, quote_mint, soft_close_window, soft_close_extension, max_extension} = cmd.opts();
+        log.debug(TAG, '[create_core_auction]', 'Solana Env Config:', env);
+        log.debug(TAG, '[create_core_auction]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[create_core_auction]', 'RPC URL:', rpc);
+
+        if (asset === undefined) {
+            log.error(TAG, '[create_core_auction]', "Error Asset Id Input");
+            return;
+        }
+        if (start_price === undefined || isNaN(parseInt(start_price))) {
+            log.error(TAG, '[create_core_auction]', "Error Start Price Input");
+            return;
+        }
+        if (duration === undefined || isNaN(parseInt(duration))) {
+            log.error(TAG, '[create_core_auction]', "Error Duration Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        let buyNow = undefined;
+        if (buy_now_price !== undefined) {
+            buyNow = { price: buy_now_price, cutoffBps: parseInt(buy_now_cutoff) };
+        }
+        // Without a window the global default applies
+        let softClose = undefined;
+        if (soft_close_window !== undefined) {
+            softClose = {
+                windowSecs: parseInt(soft_close_window),
+                extensionSecs: parseInt(soft_close_extension),
+                maxExtensionSecs: parseInt(max_extension),
+            };
+        }
+        await createCoreAuction(new PublicKey(asset), start_price, duration, buyNow, reserve_price, parseQuoteMint(quote_mint), softClose);
+    });
+
+
+programCommand('create_dutch_auction')
+    .option('-mint, --mint <string>', 'NFT mint address')
+    .option('-s, --start_price <number>', 'start price of the auction [10.1 means 10.1 quote tokens]')
+    .option('-f, --floor_price <number>', 'floor price the auction decays to [10.1 means 10.1 quote tokens]')
+    .option('-d, --duration <number>', 'duration of the auction [100 means 100 seconds]')
+    .option('-t, --decay_type <number>', 'price decay [0: linear, 1: stepped]', '0')
+    .option('-i, --decay_interval <number>', 'step length of a stepped decay [100 means 100 seconds]', '0')
+    .option('-q, --quote_mint <string>', '[optional] mint bids are paid in, "SOL" for native SOL, defaults to HTO')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const {env, keypair, rpc, mint, start_price, floor_price, duration, decay_type, decay_interval, quote_mint} = cmd.opts();
+        log.debug(TAG, '[create_dutch_auction]', 'Solana Env Config:', env);
+        log.debug(TAG, '[create_dutch_auction]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[create_dutch_auction]', 'RPC URL:', rpc);
+
+        if (mint === undefined) {
+            log.error(TAG, '[create_dutch_auction]', "Error Mint Address Input");
+            return;
+        }
+        if (start_price === undefined || isNaN(parseInt(start_price))) {
+            log.error(TAG, '[create_dutch_auction]', "Error Start Price Input");
+            return;
+        }
+        if (floor_price === undefined || isNaN(parseInt(floor_price))) {
+            log.error(TAG, '[create_dutch_auction]', "Error Floor Price Input");
+            return;
+        }
+        if (duration === undefined || isNaN(parseInt(duration))) {
+            log.error(TAG, '[create_dutch_auction]', "Error Duration Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await createAuction(new PublicKey(mint), start_price, duration, {
+            floorPrice: floor_price,
+            decayType: parseInt(decay_type),
+            decayInterval: parseInt(decay_interval),
+        }, undefined, undefined, undefined, parseQuoteMint(quote_mint));
+    });
+
+
+programCommand('create_sealed_auction')
+    .option('-mint, --mint <string>', 'NFT mint address')
+    .option('-s, --start_price <number>', 'minimum bid and deposit of the auction [10.1 means 10.1 quote tokens]')
+    .option('-d, --duration <number>', 'duration of the commit phase [100 means 100 seconds]')
+    .option('-rd, --reveal_duration <number>', 'duration of the reveal phase [100 means 100 seconds]')
+    .option('-t, --settlement_type <number>', 'winner pays [0: highest bid, 1: second-highest bid]', '0')
+    .option('-q, --quote_mint <string>', '[optional] mint bids are paid in, "SOL" for native SOL, defaults to HTO')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const {env, keypair, rpc, mint, start_price, duration, reveal_duration, settlement_type, quote_mint} = cmd.opts();
+        log.debug(TAG, '[create_sealed_auction]', 'Solana Env Config:', env);
+        log.debug(TAG, '[create_sealed_auction]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[create_sealed_auction]', 'RPC URL:', rpc);
+
+        if (mint === undefined) {
+            log.error(TAG, '[create_sealed_auction]', "Error Mint Address Input");
+            return;
+        }
+        if (start_price === undefined || isNaN(parseInt(start_price))) {
+            log.error(TAG, '[create_sealed_auction]', "Error Start Price Input");
+            return;
+        }
+        if (duration === undefined || isNaN(parseInt(duration))) {
+            log.error(TAG, '[create_sealed_auction]', "Error Duration Input");
+            return;
+        }
+        if (reveal_duration === undefined || isNaN(parseInt(reveal_duration))) {
+            log.error(TAG, '[create_sealed_auction]', "Error Reveal Duration Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await createAuction(new PublicKey(mint), start_price, duration, undefined, {
+            revealDuration: parseInt(reveal_duration),
+            settlementType: parseInt(settlement_type),
+        }, undefined, undefined, parseQuoteMint(quote_mint));
     });
 
 
@@ -75,11 +700,93 @@ programCommand('cancel_auction')
     });
 
 
+programCommand('set_increment_schedule')
+    .option('-pda, --pda <string>', 'auction pda address')
+    .option('-t, --tiers <string>', 'bid increment schedule as from:increment:bps,..., "global" for the global increments [0:1000000:500,1000000000:0:250]')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const {env, keypair, rpc, pda, tiers} = cmd.opts();
+        log.debug(TAG, '[set_increment_schedule]', 'Solana Env Config:', env);
+        log.debug(TAG, '[set_increment_schedule]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[set_increment_schedule]', 'RPC URL:', rpc);
+
+        if (pda === undefined) {
+            log.error(TAG, '[set_increment_schedule]', "Error PDA Input");
+            return;
+        }
+        if (tiers === undefined) {
+            log.error(TAG, '[set_increment_schedule]', "Error Tiers Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await setIncrementSchedule(new PublicKey(pda), tiers === 'global' ? [] : parseIncrementTiers(tiers));
+    });
+
+
+programCommand('force_cancel')
+    .option('-pda, --pda <string>', 'auction pda address')
+    .option('-r, --reason <number>', 'reason code [0: stolen, 1: counterfeit, 2: seller fraud, 3: other]')
+    .option('-qr, --quarantine <boolean>', 'keep the NFT quarantined [true] or return it to the seller [false]')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const {env, keypair, rpc, pda, reason, quarantine} = cmd.opts();
+        log.debug(TAG, '[force_cancel]', 'Solana Env Config:', env);
+        log.debug(TAG, '[force_cancel]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[force_cancel]', 'RPC URL:', rpc);
+
+        if (pda === undefined) {
+            log.error(TAG, '[force_cancel]', "Error PDA Input");
+            return;
+        }
+        if (reason === undefined || isNaN(parseInt(reason))) {
+            log.error(TAG, '[force_cancel]', "Error Reason Input");
+            return;
+        }
+        if (quarantine !== 'true' && quarantine !== 'false') {
+            log.error(TAG, '[force_cancel]', "Error Quarantine Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await forceCancel(new PublicKey(pda), parseInt(reason), quarantine === 'true');
+    });
+
+
+programCommand('release_quarantined_nft')
+    .option('-pda, --pda <string>', 'force cancelled auction pda address')
+    .option('-d, --destination <string>', 'wallet receiving the NFT')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const {env, keypair, rpc, pda, destination} = cmd.opts();
+        log.debug(TAG, '[release_quarantined_nft]', 'Solana Env Config:', env);
+        log.debug(TAG, '[release_quarantined_nft]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[release_quarantined_nft]', 'RPC URL:', rpc);
+
+        if (pda === undefined) {
+            log.error(TAG, '[release_quarantined_nft]', "Error PDA Input");
+            return;
+        }
+        if (destination === undefined) {
+            log.error(TAG, '[release_quarantined_nft]', "Error Destination Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await releaseQuarantinedNft(new PublicKey(pda), new PublicKey(destination));
+    });
+
+
 programCommand('claim_auction')
     .option('-pda, --pda <string>', 'auction pda address')
+    .option('-rp, --reserve_price <number>', '[seller only] hidden reserve to reveal [10.1 means 10.1 quote tokens]')
+    .option('-salt, --salt <string>', '[seller only] hex reserve salt printed by create_auction')
     // eslint-disable-next-line @typescript-eslint/no-unused-vars
     .action(async (directory, cmd) => {
-        const {env, keypair, rpc, pda} = cmd.opts();
+        const {env, keypair, rpc, pda, reserve_price, salt} = cmd.opts();
         log.debug(TAG, '[claim_auction]', 'Solana Env Config:', env);
         log.debug(TAG, '[claim_auction]', 'Keypair Path:', keypair);
         log.debug(TAG, '[claim_auction]', 'RPC URL:', rpc);
@@ -91,16 +798,37 @@ programCommand('claim_auction')
 
         const walletKeypair = getWalletKeypair(keypair);       
         await setClusterConfig(env, walletKeypair, rpc);
-        await claimAuction(new PublicKey(pda));
+        await claimAuction(new PublicKey(pda), reserve_price, salt);
     });
     
 
+programCommand('settle_expired_auction')
+    .option('-pda, --pda <string>', 'auction pda address')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const {env, keypair, rpc, pda} = cmd.opts();
+        log.debug(TAG, '[settle_expired_auction]', 'Solana Env Config:', env);
+        log.debug(TAG, '[settle_expired_auction]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[settle_expired_auction]', 'RPC URL:', rpc);
+
+        if (pda === undefined) {
+            log.error(TAG, '[settle_expired_auction]', "Error PDA Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await settleExpiredAuction(new PublicKey(pda));
+    });
+
+
 programCommand('place_bid')
     .option('-pda, --pda <string>', 'auction pda address')
-    .option('-b, --bid <number>', 'bid amount [10.1 means 10.1 HTO]')
+    .option('-b, --bid <number>', 'bid amount [10.1 means 10.1 quote tokens]')
+    .option('-m, --max_bid <number>', '[optional] proxy ceiling, escrowed in full while the bid only goes as high as needed to lead')
     // eslint-disable-next-line @typescript-eslint/no-unused-vars
     .action(async (directory, cmd) => {
-        const {env, keypair, rpc, pda, bid} = cmd.opts();
+        const {env, keypair, rpc, pda, bid, max_bid} = cmd.opts();
         log.debug(TAG, '[place_bid]', 'Solana Env Config:', env);
         log.debug(TAG, '[place_bid]', 'Keypair Path:', keypair);
         log.debug(TAG, '[place_bid]', 'RPC URL:', rpc);
@@ -116,10 +844,170 @@ programCommand('place_bid')
 
         const walletKeypair = getWalletKeypair(keypair);       
         await setClusterConfig(env, walletKeypair, rpc);
-        await placeBid(new PublicKey(pda), bid);
+        await placeBid(new PublicKey(pda), bid, max_bid !== undefined ? parseFloat(max_bid) : undefined);
+    });
+
+
+programCommand('open_bid_book')
+    .option('-pda, --pda <string>', 'auction pda address')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const {env, keypair, rpc, pda} = cmd.opts();
+        log.debug(TAG, '[open_bid_book]', 'Solana Env Config:', env);
+        log.debug(TAG, '[open_bid_book]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[open_bid_book]', 'RPC URL:', rpc);
+
+        if (pda === undefined) {
+            log.error(TAG, '[open_bid_book]', "Error PDA Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await openBidBook(new PublicKey(pda));
+    });
+
+
+programCommand('close_bid_book')
+    .option('-pda, --pda <string>', 'closed auction pda address')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const {env, keypair, rpc, pda} = cmd.opts();
+        log.debug(TAG, '[close_bid_book]', 'Solana Env Config:', env);
+        log.debug(TAG, '[close_bid_book]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[close_bid_book]', 'RPC URL:', rpc);
+
+        if (pda === undefined) {
+            log.error(TAG, '[close_bid_book]', "Error PDA Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await closeBidBook(new PublicKey(pda));
+    });
+
+
+programCommand('get_bid_book_info')
+    .option('-pda, --pda <string>', 'auction pda address')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const {env, keypair, rpc, pda} = cmd.opts();
+        log.debug(TAG, '[get_bid_book_info]', 'Solana Env Config:', env);
+        log.debug(TAG, '[get_bid_book_info]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[get_bid_book_info]', 'RPC URL:', rpc);
+
+        if (pda === undefined) {
+            log.error(TAG, '[get_bid_book_info]', "Error PDA Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        log.info(TAG, '[get_bid_book_info]', 'Bid Book:', await getBidBookInfo(new PublicKey(pda)));
     });
     
     
+programCommand('buy_now')
+    .option('-pda, --pda <string>', 'auction pda address')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const {env, keypair, rpc, pda} = cmd.opts();
+        log.debug(TAG, '[buy_now]', 'Solana Env Config:', env);
+        log.debug(TAG, '[buy_now]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[buy_now]', 'RPC URL:', rpc);
+
+        if (pda === undefined) {
+            log.error(TAG, '[buy_now]', "Error PDA Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await buyNow(new PublicKey(pda));
+    });
+
+
+programCommand('commit_bid')
+    .option('-pda, --pda <string>', 'auction pda address')
+    .option('-b, --bid <number>', 'hidden bid amount [10.1 means 10.1 quote tokens]')
+    .option('-dp, --deposit <number>', 'escrowed deposit, at least the bid [10.1 means 10.1 quote tokens]')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const {env, keypair, rpc, pda, bid, deposit} = cmd.opts();
+        log.debug(TAG, '[commit_bid]', 'Solana Env Config:', env);
+        log.debug(TAG, '[commit_bid]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[commit_bid]', 'RPC URL:', rpc);
+
+        if (pda === undefined) {
+            log.error(TAG, '[commit_bid]', "Error PDA Input");
+            return;
+        }
+        if (bid === undefined || isNaN(parseInt(bid))) {
+            log.error(TAG, '[commit_bid]', "Error Bid Amount Input");
+            return;
+        }
+        if (deposit === undefined || isNaN(parseInt(deposit))) {
+            log.error(TAG, '[commit_bid]', "Error Deposit Amount Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await commitBid(new PublicKey(pda), bid, deposit);
+    });
+
+
+programCommand('reveal_bid')
+    .option('-pda, --pda <string>', 'auction pda address')
+    .option('-b, --bid <number>', 'committed bid amount [10.1 means 10.1 quote tokens]')
+    .option('-salt, --salt <string>', 'hex salt printed by commit_bid')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const {env, keypair, rpc, pda, bid, salt} = cmd.opts();
+        log.debug(TAG, '[reveal_bid]', 'Solana Env Config:', env);
+        log.debug(TAG, '[reveal_bid]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[reveal_bid]', 'RPC URL:', rpc);
+
+        if (pda === undefined) {
+            log.error(TAG, '[reveal_bid]', "Error PDA Input");
+            return;
+        }
+        if (bid === undefined || isNaN(parseInt(bid))) {
+            log.error(TAG, '[reveal_bid]', "Error Bid Amount Input");
+            return;
+        }
+        if (salt === undefined) {
+            log.error(TAG, '[reveal_bid]', "Error Salt Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await revealBid(new PublicKey(pda), bid, salt);
+    });
+
+
+programCommand('withdraw_sealed_bid')
+    .option('-pda, --pda <string>', 'auction pda address')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const {env, keypair, rpc, pda} = cmd.opts();
+        log.debug(TAG, '[withdraw_sealed_bid]', 'Solana Env Config:', env);
+        log.debug(TAG, '[withdraw_sealed_bid]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[withdraw_sealed_bid]', 'RPC URL:', rpc);
+
+        if (pda === undefined) {
+            log.error(TAG, '[withdraw_sealed_bid]', "Error PDA Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        await withdrawSealedBid(new PublicKey(pda));
+    });
+
+
 programCommand('get_auction_info')
     .option('-pda, --pda <string>', 'auction pda address')
     // eslint-disable-next-line @typescript-eslint/no-unused-vars
@@ -140,6 +1028,40 @@ programCommand('get_auction_info')
     });
 
     
+programCommand('get_next_min_bid')
+    .option('-pda, --pda <string>', 'auction pda address')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const {env, keypair, rpc, pda} = cmd.opts();
+        log.debug(TAG, '[get_next_min_bid]', 'Solana Env Config:', env);
+        log.debug(TAG, '[get_next_min_bid]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[get_next_min_bid]', 'RPC URL:', rpc);
+
+        if (pda === undefined) {
+            log.error(TAG, '[get_next_min_bid]', "Error PDA Input");
+            return;
+        }
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        log.info(TAG, '[get_next_min_bid]', 'Next Min Bid:', (await getNextMinBid(new PublicKey(pda))).toString());
+    });
+
+
+programCommand('get_legacy_auctions')
+    // eslint-disable-next-line @typescript-eslint/no-unused-vars
+    .action(async (directory, cmd) => {
+        const { env, keypair, rpc } = cmd.opts();
+        log.debug(TAG, '[get_legacy_auctions]', 'Solana Env Config:', env);
+        log.debug(TAG, '[get_legacy_auctions]', 'Keypair Path:', keypair);
+        log.debug(TAG, '[get_legacy_auctions]', 'RPC URL:', rpc);
+
+        const walletKeypair = getWalletKeypair(keypair);
+        await setClusterConfig(env, walletKeypair, rpc);
+        log.info(TAG, '[get_legacy_auctions]', 'Auction PDAs:', (await getLegacyAuctions()).map((auction) => auction.toBase58()));
+    });
+
+
 programCommand('get_all_auctions')
     .option('-s, --seller <string>', '[optional] filter for given seller address only')
     .option('-c, --collection <string>', '[optional] filter for given collection address only')
@@ -157,7 +1079,27 @@ programCommand('get_all_auctions')
     });
 
     
-    function programCommand(name: string, requireKeypair?: boolean | undefined) {
+    // "SOL" lists a native SOL auction, which has no quote mint
+function parseQuoteMint(quoteMint: string | undefined) {
+    if (quoteMint === undefined) return undefined;
+    if (quoteMint.toUpperCase() === 'SOL') return PublicKey.default;
+    return new PublicKey(quoteMint);
+}
+
+// Increment tiers given as from:increment:bps, comma separated
+function parseIncrementTiers(tiers: string | undefined) {
+    if (tiers === undefined) return [];
+    return tiers.split(',').map((tier: string) => {
+        const [fromBid, increment, incrementBps] = tier.trim().split(':');
+        return {
+            fromBid: new anchor.BN(fromBid),
+            increment: new anchor.BN(increment ?? 0),
+            incrementBps: parseInt(incrementBps ?? '0'),
+        };
+    });
+}
+
+function programCommand(name: string, requireKeypair?: boolean | undefined) {
         const p = program
             .command(name)
             .option(
@@ -1,7 +1,8 @@
 import { Program, web3 } from '@project-serum/anchor';
 import * as anchor from '@project-serum/anchor';
-import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID} from '@solana/spl-token';
+import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID } from '@solana/spl-token';
 import fs from 'fs';
+import { createHash, randomBytes } from 'crypto';
 import NodeWallet from '@project-serum/anchor/dist/cjs/nodewallet';
 
 import { IDL as AuctionIDL } from "../target/types/auction";
@@ -9,24 +10,69 @@ import {
     Keypair,
     PublicKey,
     SystemProgram,
+    SYSVAR_INSTRUCTIONS_PUBKEY,
     SYSVAR_RENT_PUBKEY,
     Transaction,
     ParsedAccountData
 } from '@solana/web3.js';
 import {
     AuctionPool,
+    GlobalPool,
+    ASSET_TYPE_COMPRESSED,
+    ASSET_TYPE_CORE,
+    ASSET_TYPE_NFT,
+    AUCTION_TYPE_DUTCH,
+    AUCTION_TYPE_SEALED,
+    BuyNowConfig,
+    DutchConfig,
+    SealedConfig,
+    SoftCloseConfig,
+    SEALED_BID_SEED,
+    SEALED_BID_VAULT_SEED,
+    AUCTION_SEED,
     AUCTION_SIZE,
+    LEGACY_AUCTION_SIZE,
+    AUCTION_VAULT_SEED,
+    BID_BOOK_SEED,
+    BidBook,
+    COLLECTION_CONFIG_SEED,
+    CollectionConfig,
+    CollectionPolicy,
+    COMPRESSED_ASSET_SEED,
+    CompressedAsset,
+    FORCE_CANCEL_SEED,
+    ForceCancelRecord,
+    IncrementTier,
+    TREASURY_SEED,
+    Treasury,
     GLOBAL_AUTHORITY_SEED,
+    QUOTE_MINT_SEED,
+    TOKEN_STANDARD_PROGRAMMABLE_NON_FUNGIBLE,
 } from './types';
 import {
     getAssociatedTokenAccount,
     getATokenAccountsNeedCreate,
+    getCanopyDepth,
+    getCompressedNft,
+    getCoreCollection,
     getNFTTokenAccount,
     getOwnerOfNFT,
+    getMasterEdition,
     getMetadata,
+    getMintTokenProgram,
+    getNftCollection,
+    getProgrammableConfig,
+    getTokenRecord,
+    getTransferHookAccounts,
+    getTreeAuthority,
     isExistAccount,
+    AUTH_RULES_PROGRAM,
+    BUBBLEGUM_PROGRAM_ID,
+    METAPLEX,
+    MPL_CORE_PROGRAM_ID,
+    SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+    SPL_NOOP_PROGRAM_ID,
 } from './utils';
-import { programs } from "@metaplex/js";
 import { AnchorWallet } from 'solana-vue-wallets';
 import * as log from 'loglevel';
 import { auction } from '@metaplex/js/lib/programs';
@@ -38,9 +84,8 @@ let auctionProvider: anchor.Provider = null;
 let auctionSolConnection: web3.Connection = null;
 let auctionProgramID: PublicKey = null;
 
-let vaultWallet: PublicKey = null;
+// Default quote mint of new auctions
 let htoTokenMint: PublicKey = null;
-let htoTokenDecimals: number = null;
 
 export const getWalletKeypair = (keypair: string) => {
     const walletKeypair = Keypair.fromSecretKey(Uint8Array.from(JSON.parse(fs.readFileSync(keypair, 'utf-8'))), {
@@ -77,15 +122,11 @@ export const setClusterConfig = async (cluster: web3.Cluster, wallet: NodeWallet
     auctionProvider = anchor.getProvider();
     if (cluster === 'devnet') {
         auctionProgramID = new PublicKey('6VwSgSesAeqqSw3uXsU8BGMxMAqSzFVQxPPUDUVX8Qw4');
-        vaultWallet = new anchor.web3.PublicKey('J1CHG5pAMT4GRprmLwuQ4JzTcjJxTuXD2nsZDqp7924x');
         htoTokenMint = new PublicKey('htoHLBJV1err8xP5oxyQdV2PLQhtVjxLXpKB7FsgJQD');
-        htoTokenDecimals = 1_000_000_000;
     } else if (cluster === 'mainnet-beta') {
         // TODO
         auctionProgramID = new PublicKey('BekxgfTSzdzQ2yNV3zSZYqifM3XWmUb4i976hKExtRib');
-        vaultWallet = new anchor.web3.PublicKey('J1CHG5pAMT4GRprmLwuQ4JzTcjJxTuXD2nsZDqp7924x');
         htoTokenMint = new PublicKey('htoHLBJV1err8xP5oxyQdV2PLQhtVjxLXpKB7FsgJQD');
-        htoTokenDecimals = 1_000_000_000;
     }
     
     log.debug(TAG, '[setClusterConfig]', 'Cluster:', cluster);
@@ -117,10 +158,134 @@ export const initialize = async (wallet: NodeWallet) => {
     }
 }
 
-export const createAuction = async (mint: PublicKey, startPrice: number, duration: number) => {
-    const tx = await createAuctionTx(auctionProvider.publicKey, mint, startPrice, duration);
+export const migrateGlobalAuthority = async () => {
+    // The upgraded program can't settle auctions of the first release
+    let legacyAuctions = await getLegacyAuctions();
+    if (legacyAuctions.length > 0) {
+        legacyAuctions.map((auction) => log.error(TAG, '[migrateGlobalAuthority]', 'legacy auction:', auction.toBase58()));
+        throw new Error('Settle every legacy auction before upgrading the program');
+    }
+
+    const tx = await createMigrateGlobalAuthorityTx(auctionProvider.publicKey);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[migrateGlobalAuthority]', 'txHash:', txId);
+}
+
+export const proposeAdmin = async (newAdmin: PublicKey) => {
+    const tx = await createProposeAdminTx(auctionProvider.publicKey, newAdmin);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[proposeAdmin]', 'txHash:', txId);
+}
+
+export const acceptAdmin = async () => {
+    const tx = await createAcceptAdminTx(auctionProvider.publicKey);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[acceptAdmin]', 'txHash:', txId);
+}
+
+export const setRole = async (role: number, operator: PublicKey) => {
+    const tx = await createSetRoleTx(auctionProvider.publicKey, role, operator);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[setRole]', 'txHash:', txId);
+}
+
+export const setPaused = async (paused: boolean) => {
+    const tx = await createSetPausedTx(auctionProvider.publicKey, paused);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[setPaused]', 'txHash:', txId);
+}
+
+export const freezeAuction = async (auctionPDA: PublicKey, frozen: boolean) => {
+    const tx = await createFreezeAuctionTx(auctionProvider.publicKey, auctionPDA, frozen);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[freezeAuction]', 'txHash:', txId);
+}
+
+export const setSoftCloseBounds = async (minSecs: number, maxSecs: number) => {
+    const tx = await createSetSoftCloseBoundsTx(auctionProvider.publicKey, minSecs, maxSecs);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[setSoftCloseBounds]', 'txHash:', txId);
+}
+
+export const updateConfig = async (config: GlobalPool) => {
+    const tx = await createUpdateConfigTx(auctionProvider.publicKey, config);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[updateConfig]', 'txHash:', txId);
+}
+
+export const setFeeSplit = async (recipients: PublicKey[], sharesBps: number[]) => {
+    const tx = await createSetFeeSplitTx(auctionProvider.publicKey, recipients, sharesBps);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[setFeeSplit]', 'txHash:', txId);
+}
+
+export const distributeFees = async (quoteMint: PublicKey) => {
+    const tx = await createDistributeFeesTx(auctionProvider.publicKey, quoteMint);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[distributeFees]', 'txHash:', txId);
+}
+
+export const addQuoteMint = async (quoteMint: PublicKey) => {
+    const tx = await createAddQuoteMintTx(auctionProvider.publicKey, quoteMint);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[addQuoteMint]', 'txHash:', txId);
+}
+
+export const removeQuoteMint = async (quoteMint: PublicKey) => {
+    const tx = await createRemoveQuoteMintTx(auctionProvider.publicKey, quoteMint);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[removeQuoteMint]', 'txHash:', txId);
+}
+
+export const addCollection = async (collection: PublicKey, policy: CollectionPolicy) => {
+    const tx = await createAddCollectionTx(auctionProvider.publicKey, collection, policy);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[addCollection]', 'txHash:', txId);
+}
+
+export const updateCollection = async (collection: PublicKey, policy: CollectionPolicy) => {
+    const tx = await createUpdateCollectionTx(auctionProvider.publicKey, collection, policy);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[updateCollection]', 'txHash:', txId);
+}
+
+export const removeCollection = async (collection: PublicKey) => {
+    const tx = await createRemoveCollectionTx(auctionProvider.publicKey, collection);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[removeCollection]', 'txHash:', txId);
+}
+
+export const setStrictCollections = async (strict: boolean) => {
+    const tx = await createSetStrictCollectionsTx(auctionProvider.publicKey, strict);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[setStrictCollections]', 'txHash:', txId);
+}
+
+export const createAuction = async (mint: PublicKey, startPrice: number, duration: number, dutch?: DutchConfig, sealed?: SealedConfig, buyNow?: BuyNowConfig, reservePrice?: number, quoteMint?: PublicKey, softClose?: SoftCloseConfig) => {
+    // Keep the salt: it is needed to reveal the reserve at claim
+    const reserveSalt = randomBytes(32);
+    const tx = await createAuctionTx(auctionProvider.publicKey, mint, startPrice, duration, dutch, sealed, buyNow, reservePrice, reserveSalt, quoteMint, softClose);
     const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
     log.info(TAG, '[createAuction]', 'txHash:', txId);
+    if (reservePrice !== undefined) log.info(TAG, '[createAuction]', 'reserve salt:', reserveSalt.toString('hex'));
+}
+
+export const createCompressedAuction = async (assetId: PublicKey, startPrice: number, duration: number, buyNow?: BuyNowConfig, reservePrice?: number, quoteMint?: PublicKey, softClose?: SoftCloseConfig) => {
+    // Keep the salt: it is needed to reveal the reserve at claim
+    const reserveSalt = randomBytes(32);
+    const tx = await createCompressedAuctionTx(auctionProvider.publicKey, assetId, startPrice, duration, buyNow, reservePrice, reserveSalt, quoteMint, softClose);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[createCompressedAuction]', 'txHash:', txId);
+    if (reservePrice !== undefined) log.info(TAG, '[createCompressedAuction]', 'reserve salt:', reserveSalt.toString('hex'));
+}
+
+export const createCoreAuction = async (asset: PublicKey, startPrice: number, duration: number, buyNow?: BuyNowConfig, reservePrice?: number, quoteMint?: PublicKey, softClose?: SoftCloseConfig) => {
+    // Keep the salt: it is needed to reveal the reserve at claim
+    const reserveSalt = randomBytes(32);
+    const tx = await createCoreAuctionTx(auctionProvider.publicKey, asset, startPrice, duration, buyNow, reservePrice, reserveSalt, quoteMint, softClose);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[createCoreAuction]', 'txHash:', txId);
+    if (reservePrice !== undefined) log.info(TAG, '[createCoreAuction]', 'reserve salt:', reserveSalt.toString('hex'));
 }
 
 export const cancelAuction = async (auctionPDA: PublicKey) => {
@@ -129,18 +294,86 @@ export const cancelAuction = async (auctionPDA: PublicKey) => {
     log.info(TAG, '[cancelAuction]', 'txHash:', txId);
 }
 
-export const placeBid = async (auctionPDA: PublicKey, bid: number) => {
-    const tx = await createPlaceBidTx(auctionProvider.publicKey, auctionPDA, bid);
+export const setIncrementSchedule = async (auctionPDA: PublicKey, tiers: IncrementTier[]) => {
+    const tx = await createSetIncrementScheduleTx(auctionProvider.publicKey, auctionPDA, tiers);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[setIncrementSchedule]', 'txHash:', txId);
+}
+
+export const forceCancel = async (auctionPDA: PublicKey, reason: number, quarantine: boolean) => {
+    const tx = await createForceCancelTx(auctionProvider.publicKey, auctionPDA, reason, quarantine);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[forceCancel]', 'txHash:', txId);
+}
+
+export const releaseQuarantinedNft = async (auctionPDA: PublicKey, destination: PublicKey) => {
+    const tx = await createReleaseQuarantinedNftTx(auctionProvider.publicKey, auctionPDA, destination);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[releaseQuarantinedNft]', 'txHash:', txId);
+}
+
+export const placeBid = async (auctionPDA: PublicKey, bid: number, maxBid?: number) => {
+    const tx = await createPlaceBidTx(auctionProvider.publicKey, auctionPDA, bid, maxBid);
     const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
     log.info(TAG, '[placeBid]', 'txHash:', txId);
 }
 
-export const claimAuction = async (auctionPDA: PublicKey) => {
-    const tx = await createAuctionClaimTx(auctionProvider.publicKey, auctionPDA);
+export const openBidBook = async (auctionPDA: PublicKey) => {
+    const tx = await createOpenBidBookTx(auctionProvider.publicKey, auctionPDA);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[openBidBook]', 'txHash:', txId);
+}
+
+export const closeBidBook = async (auctionPDA: PublicKey) => {
+    const tx = await createCloseBidBookTx(auctionProvider.publicKey, auctionPDA);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[closeBidBook]', 'txHash:', txId);
+}
+
+export const claimAuction = async (auctionPDA: PublicKey, reservePrice?: number, reserveSalt?: string) => {
+    const tx = await createAuctionClaimTx(
+        auctionProvider.publicKey,
+        auctionPDA,
+        reservePrice,
+        reserveSalt !== undefined ? Buffer.from(reserveSalt, 'hex') : undefined
+    );
     const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
     log.info(TAG, '[claimAuction]', 'txHash:', txId);
 }
 
+export const settleExpiredAuction = async (auctionPDA: PublicKey) => {
+    const tx = await createSettleExpiredAuctionTx(auctionProvider.publicKey, auctionPDA);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[settleExpiredAuction]', 'txHash:', txId);
+}
+
+export const buyNow = async (auctionPDA: PublicKey) => {
+    const tx = await createBuyNowTx(auctionProvider.publicKey, auctionPDA);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[buyNow]', 'txHash:', txId);
+}
+
+export const commitBid = async (auctionPDA: PublicKey, bid: number, deposit: number) => {
+    // Keep the salt: it is needed to reveal the bid
+    const salt = randomBytes(32);
+    const tx = await createCommitBidTx(auctionProvider.publicKey, auctionPDA, bid, deposit, salt);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[commitBid]', 'txHash:', txId);
+    log.info(TAG, '[commitBid]', 'salt:', salt.toString('hex'));
+}
+
+export const revealBid = async (auctionPDA: PublicKey, bid: number, salt: string) => {
+    const tx = await createRevealBidTx(auctionProvider.publicKey, auctionPDA, bid, Buffer.from(salt, 'hex'));
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[revealBid]', 'txHash:', txId);
+}
+
+export const withdrawSealedBid = async (auctionPDA: PublicKey) => {
+    const tx = await createWithdrawSealedBidTx(auctionProvider.publicKey, auctionPDA);
+    const txId = await auctionProvider.sendAndConfirm(tx, [], {commitment: "confirmed"});
+    log.info(TAG, '[withdrawSealedBid]', 'txHash:', txId);
+}
+
 /////////////////////////////////////////////////////////////////
 /////////////////////////////////////////////////////////////////
 /////////////////////////////////////////////////////////////////
@@ -162,6 +395,7 @@ export const createInitializeTx = async (userAddress: PublicKey) => {
         accounts: {
             admin: userAddress,
             globalAuthority,
+            treasury: await getTreasury(),
             systemProgram: SystemProgram.programId,
             rent: SYSVAR_RENT_PUBKEY,
         },
@@ -173,106 +407,1237 @@ export const createInitializeTx = async (userAddress: PublicKey) => {
 }
 
 
-export const createAuctionTx = async (userAddress: PublicKey, mint: PublicKey, startPrice: number, duration: number) => {
-    const [globalAuthority, bump] = await PublicKey.findProgramAddress(
+export const createMigrateGlobalAuthorityTx = async (userAddress: PublicKey) => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
         [Buffer.from(GLOBAL_AUTHORITY_SEED)],
         auctionProgramID,
     );
 
-    let d = new Date();
-    let curTime = Math.floor(d.getTime()/1000);
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.migrateGlobalAuthority({
+        accounts: {
+            admin: userAddress,
+            globalAuthority,
+            treasury: await getTreasury(),
+            systemProgram: SystemProgram.programId,
+        },
+        instructions: [],
+        signers: [],
+    }));
 
-    let str = "auction" + curTime.toString();
-    log.debug(TAG, '[createAuctionTx]', 'seed string', str);
-    
-    let auctionPDA = await anchor.web3.PublicKey.createWithSeed(
-        userAddress,
-        str,
+    return tx;
+}
+
+
+export const createProposeAdminTx = async (userAddress: PublicKey, newAdmin: PublicKey) => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
         auctionProgramID,
     );
 
-    let ix = SystemProgram.createAccountWithSeed({
-        fromPubkey: userAddress,
-        basePubkey: userAddress,
-        seed: str,
-        newAccountPubkey: auctionPDA,
-        lamports: await auctionSolConnection.getMinimumBalanceForRentExemption(AUCTION_SIZE),
-        space: AUCTION_SIZE,
-        programId: auctionProgramID,
-    });
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.proposeAdmin(
+        newAdmin, {
+        accounts: {
+            admin: userAddress,
+            globalAuthority,
+        },
+        instructions: [],
+        signers: [],
+    }));
 
-    log.debug(TAG, '[createAuctionTx]', 'auctionPDA', auctionPDA.toBase58);
-    let auctionAta = await getAssociatedTokenAccount(globalAuthority, mint);
+    return tx;
+}
 
-    let userTokenAccount = await getAssociatedTokenAccount(userAddress, mint);
-    if (!await isExistAccount(userTokenAccount, auctionSolConnection)) {
-        let accountOfNFT = await getNFTTokenAccount(mint, auctionSolConnection);
-        if (userTokenAccount.toBase58() != accountOfNFT.toBase58()) {
-            let nftOwner = await getOwnerOfNFT(mint, auctionSolConnection);
-            if (nftOwner.toBase58() == userAddress.toBase58()) userTokenAccount = accountOfNFT;
-        }
-    }
 
-    let mintMetadata = await getMetadata(mint);
+export const createAcceptAdminTx = async (userAddress: PublicKey) => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.acceptAdmin({
+        accounts: {
+            newAdmin: userAddress,
+            globalAuthority,
+        },
+        instructions: [],
+        signers: [],
+    }));
+
+    return tx;
+}
+
+
+export const createSetRoleTx = async (userAddress: PublicKey, role: number, operator: PublicKey) => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
 
     let tx = new Transaction();
+    tx.add(auctionProgram.instruction.setRole(
+        role,
+        operator, {
+        accounts: {
+            admin: userAddress,
+            globalAuthority,
+        },
+        instructions: [],
+        signers: [],
+    }));
+
+    return tx;
+}
+
+
+export const createSetPausedTx = async (userAddress: PublicKey, paused: boolean) => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
 
-    tx.add(ix);
-    tx.add(auctionProgram.instruction.createAuction(
-        new anchor.BN(startPrice * htoTokenDecimals), new anchor.BN(duration), {
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.setPaused(
+        paused, {
         accounts: {
-            auction: auctionPDA,
+            admin: userAddress,
             globalAuthority,
-            auctionAta,
-            owner: userAddress,
-            ownerAta: userTokenAccount,
-            mint,
-            mintMetadata,
-            ataProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-            tokenProgram: TOKEN_PROGRAM_ID,
-            systemProgram: SystemProgram.programId,
-            rentSysvar: SYSVAR_RENT_PUBKEY,
         },
         instructions: [],
         signers: [],
     }));
 
-    log.info(TAG, '[createAuction]', 'auction:', auctionPDA.toBase58());
+    return tx;
+}
+
+
+export const createFreezeAuctionTx = async (userAddress: PublicKey, auctionPDA: PublicKey, frozen: boolean) => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.freezeAuction(
+        frozen, {
+        accounts: {
+            admin: userAddress,
+            globalAuthority,
+            auction: auctionPDA,
+        },
+        instructions: [],
+        signers: [],
+    }));
 
     return tx;
 }
 
-export const createAuctionCancelTx = async (userAddress: PublicKey, auctionPDA: PublicKey) => {
-    let auctionState = await getAuctionState(auctionPDA);
-    let nftMint = auctionState.nftMint;
 
-    const [globalAuthority, bump] = await PublicKey.findProgramAddress(
+export const createUpdateConfigTx = async (userAddress: PublicKey, config: GlobalPool) => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.updateConfig(
+        config.feePercent,
+        config.minIncrement,
+        config.minIncrementPercent,
+        config.minDurationAfterBidSecs,
+        config.day,
+        config.royaltyFallback, {
+        accounts: {
+            admin: userAddress,
+            globalAuthority,
+        },
+        instructions: [],
+        signers: [],
+    }));
+
+    return tx;
+}
+
+
+export const createSetSoftCloseBoundsTx = async (userAddress: PublicKey, minSecs: number, maxSecs: number) => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
         [Buffer.from(GLOBAL_AUTHORITY_SEED)],
         auctionProgramID,
     );
 
-    let auctionAta = await getAssociatedTokenAccount(globalAuthority, nftMint);
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.setSoftCloseBounds(
+        new anchor.BN(minSecs),
+        new anchor.BN(maxSecs), {
+        accounts: {
+            admin: userAddress,
+            globalAuthority,
+        },
+        instructions: [],
+        signers: [],
+    }));
+
+    return tx;
+}
 
-    let { instructions, destinationAccounts } = await getATokenAccountsNeedCreate(
-        auctionSolConnection,
-        userAddress,
-        userAddress,
-        [nftMint]
+
+// An empty schedule brings the auction back to the global increments
+export const createSetIncrementScheduleTx = async (userAddress: PublicKey, auctionPDA: PublicKey, tiers: IncrementTier[]) => {
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.setIncrementSchedule(
+        tiers, {
+        accounts: {
+            seller: userAddress,
+            auction: auctionPDA,
+        },
+        instructions: [],
+        signers: [],
+    }));
+
+    return tx;
+}
+
+
+export const createSetFeeSplitTx = async (userAddress: PublicKey, recipients: PublicKey[], sharesBps: number[]) => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
     );
 
     let tx = new Transaction();
-    if (instructions.length > 0) instructions.map((ix) => tx.add(ix));
-    tx.add(auctionProgram.instruction.cancelAuction(
-        bump, 
-        {
+    tx.add(auctionProgram.instruction.setFeeSplit(
+        recipients,
+        sharesBps, {
+        accounts: {
+            admin: userAddress,
+            globalAuthority,
+            treasury: await getTreasury(),
+        },
+        instructions: [],
+        signers: [],
+    }));
+
+    return tx;
+}
+
+
+// PublicKey.default pays out the native SOL fees
+export const createDistributeFeesTx = async (userAddress: PublicKey, quoteMint: PublicKey) => {
+    let treasury = await getTreasury();
+    let treasuryState = await getTreasuryState();
+    let recipients = treasuryState.recipients.slice(0, treasuryState.recipientCount);
+    let native = quoteMint.equals(PublicKey.default);
+
+    let tx = new Transaction();
+    let treasuryVault = auctionProgramID;
+    let tokenProgram = await getMintTokenProgram(quoteMint, auctionSolConnection);
+    let remainingAccounts = [];
+    if (native) {
+        remainingAccounts = recipients.map((recipient) => ({
+            pubkey: recipient,
+            isWritable: true,
+            isSigner: false,
+        }));
+    } else {
+        treasuryVault = await getAssociatedTokenAccount(treasury, quoteMint, tokenProgram);
+        for (let i = 0; i < recipients.length; i++) {
+            let { instructions, destinationAccounts } = await getATokenAccountsNeedCreate(
+                auctionSolConnection,
+                userAddress,
+                recipients[i],
+                [quoteMint],
+                tokenProgram
+            );
+            if (instructions.length > 0) instructions.map((ix) => tx.add(ix));
+            remainingAccounts.push({
+                pubkey: destinationAccounts[0],
+                isWritable: true,
+                isSigner: false,
+            });
+        }
+        // The quote mint's transfer hook accounts follow the recipients
+        remainingAccounts.push(...await getTransferHookAccounts(quoteMint, auctionSolConnection));
+    }
+
+    tx.add(auctionProgram.instruction.distributeFees({
+        accounts: {
+            treasury,
+            treasuryVault,
+            quoteMint: native ? auctionProgramID : quoteMint,
+            tokenProgram,
+        },
+        remainingAccounts,
+        instructions: [],
+        signers: [],
+    }));
+
+    return tx;
+}
+
+
+export const createAddQuoteMintTx = async (userAddress: PublicKey, quoteMint: PublicKey) => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.addQuoteMint({
+        accounts: {
+            admin: userAddress,
+            globalAuthority,
+            quoteMintInfo: await getQuoteMintInfo(quoteMint),
+            quoteMint,
+            systemProgram: SystemProgram.programId,
+        },
+        instructions: [],
+        signers: [],
+    }));
+
+    return tx;
+}
+
+
+export const createRemoveQuoteMintTx = async (userAddress: PublicKey, quoteMint: PublicKey) => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.removeQuoteMint({
+        accounts: {
+            admin: userAddress,
+            globalAuthority,
+            quoteMintInfo: await getQuoteMintInfo(quoteMint),
+        },
+        instructions: [],
+        signers: [],
+    }));
+
+    return tx;
+}
+
+
+export const createAddCollectionTx = async (userAddress: PublicKey, collection: PublicKey, policy: CollectionPolicy) => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.addCollection(
+        collection, policy, {
+        accounts: {
+            admin: userAddress,
+            globalAuthority,
+            collectionConfig: await getCollectionConfigPDA(collection),
+            systemProgram: SystemProgram.programId,
+        },
+        instructions: [],
+        signers: [],
+    }));
+
+    return tx;
+}
+
+
+export const createUpdateCollectionTx = async (userAddress: PublicKey, collection: PublicKey, policy: CollectionPolicy) => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.updateCollection(
+        policy, {
+        accounts: {
+            admin: userAddress,
+            globalAuthority,
+            collectionConfig: await getCollectionConfigPDA(collection),
+        },
+        instructions: [],
+        signers: [],
+    }));
+
+    return tx;
+}
+
+
+export const createRemoveCollectionTx = async (userAddress: PublicKey, collection: PublicKey) => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.removeCollection({
+        accounts: {
+            admin: userAddress,
+            globalAuthority,
+            collectionConfig: await getCollectionConfigPDA(collection),
+        },
+        instructions: [],
+        signers: [],
+    }));
+
+    return tx;
+}
+
+
+export const createSetStrictCollectionsTx = async (userAddress: PublicKey, strict: boolean) => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.setStrictCollections(
+        strict, {
+        accounts: {
+            admin: userAddress,
+            globalAuthority,
+        },
+        instructions: [],
+        signers: [],
+    }));
+
+    return tx;
+}
+
+
+export const createAuctionTx = async (userAddress: PublicKey, mint: PublicKey, startPrice: number, duration: number, dutch?: DutchConfig, sealed?: SealedConfig, buyNow?: BuyNowConfig, reservePrice?: number, reserveSalt?: Buffer, quoteMint?: PublicKey, softClose?: SoftCloseConfig) => {
+    const [globalAuthority, bump] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    // PublicKey.default lists a native SOL auction
+    quoteMint = quoteMint ?? htoTokenMint;
+    let native = quoteMint.equals(PublicKey.default);
+    let quoteDecimals = await getQuoteDecimals(quoteMint);
+
+    let d = new Date();
+    let nonce = new anchor.BN(Math.floor(d.getTime()/1000));
+    log.debug(TAG, '[createAuctionTx]', 'nonce', nonce.toString());
+
+    let auctionPDA = await getAuctionPDA(userAddress, mint, nonce);
+
+    log.debug(TAG, '[createAuctionTx]', 'auctionPDA', auctionPDA.toBase58());
+    let auctionVault = await getAuctionVault(auctionPDA);
+    let nftTokenProgram = await getMintTokenProgram(mint, auctionSolConnection);
+    let auctionAta = await getAssociatedTokenAccount(globalAuthority, mint, nftTokenProgram);
+
+    let userTokenAccount = await getAssociatedTokenAccount(userAddress, mint, nftTokenProgram);
+    if (!await isExistAccount(userTokenAccount, auctionSolConnection)) {
+        let accountOfNFT = await getNFTTokenAccount(mint, auctionSolConnection);
+        if (userTokenAccount.toBase58() != accountOfNFT.toBase58()) {
+            let nftOwner = await getOwnerOfNFT(mint, auctionSolConnection);
+            if (nftOwner.toBase58() == userAddress.toBase58()) userTokenAccount = accountOfNFT;
+        }
+    }
+
+    // A Token-2022 NFT with its own metadata extension may have no Token Metadata account
+    let mintMetadata = await getMetadata(mint);
+    if (!await isExistAccount(mintMetadata, auctionSolConnection)) mintMetadata = auctionProgramID;
+    // The program requires the config PDA of the collection, registered or not
+    let collection = await getNftCollection(mint, auctionSolConnection);
+    if (!collection) throw new Error('The NFT has no verified collection or creator');
+    // Without Token Metadata, the collection is the Token-2022 group, checked by passing it
+    let nftGroup = mintMetadata.equals(auctionProgramID) && !collection.equals(PublicKey.default) ? collection : auctionProgramID;
+    let { programmable } = await getProgrammableConfig(mint, auctionSolConnection);
+    let pnft = await getPnftAccounts(mint, userTokenAccount, auctionAta, globalAuthority, programmable);
+
+    let tx = new Transaction();
+
+    const accounts = {
+        auction: auctionPDA,
+        globalAuthority,
+        auctionAta,
+        auctionVault: native ? auctionProgramID : auctionVault,
+        quoteMint: native ? auctionProgramID : quoteMint,
+        quoteMintInfo: native ? auctionProgramID : await getQuoteMintInfo(quoteMint),
+        owner: userAddress,
+        ownerAta: userTokenAccount,
+        mint,
+        mintMetadata,
+        nftGroup,
+        collectionConfig: await getCollectionConfigPDA(collection),
+        ataProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        pnft,
+        tokenProgram: await getMintTokenProgram(quoteMint, auctionSolConnection),
+        nftTokenProgram,
+        systemProgram: SystemProgram.programId,
+    };
+
+    if (dutch) {
+        tx.add(auctionProgram.instruction.createDutchAuction(
+            nonce,
+            new anchor.BN(startPrice * quoteDecimals),
+            new anchor.BN(dutch.floorPrice * quoteDecimals),
+            new anchor.BN(duration),
+            dutch.decayType,
+            new anchor.BN(dutch.decayInterval), {
+            accounts,
+            instructions: [],
+            signers: [],
+        }));
+    } else if (sealed) {
+        tx.add(auctionProgram.instruction.createSealedAuction(
+            nonce,
+            new anchor.BN(startPrice * quoteDecimals),
+            new anchor.BN(duration),
+            new anchor.BN(sealed.revealDuration),
+            sealed.settlementType, {
+            accounts,
+            instructions: [],
+            signers: [],
+        }));
+    } else {
+        tx.add(auctionProgram.instruction.createAuction(
+            nonce,
+            new anchor.BN(startPrice * quoteDecimals),
+            new anchor.BN(duration),
+            getEnglishTerms(userAddress, quoteDecimals, buyNow, reservePrice, reserveSalt, softClose), {
+            accounts,
+            instructions: [],
+            signers: [],
+        }));
+    }
+
+    log.info(TAG, '[createAuction]', 'auction:', auctionPDA.toBase58());
+
+    return tx;
+}
+
+export const createCompressedAuctionTx = async (userAddress: PublicKey, assetId: PublicKey, startPrice: number, duration: number, buyNow?: BuyNowConfig, reservePrice?: number, reserveSalt?: Buffer, quoteMint?: PublicKey, softClose?: SoftCloseConfig) => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    // PublicKey.default lists a native SOL auction
+    quoteMint = quoteMint ?? htoTokenMint;
+    let native = quoteMint.equals(PublicKey.default);
+    let quoteDecimals = await getQuoteDecimals(quoteMint);
+
+    let d = new Date();
+    let nonce = new anchor.BN(Math.floor(d.getTime()/1000));
+    log.debug(TAG, '[createCompressedAuctionTx]', 'nonce', nonce.toString());
+
+    let auctionPDA = await getAuctionPDA(userAddress, assetId, nonce);
+    log.debug(TAG, '[createCompressedAuctionTx]', 'auctionPDA', auctionPDA.toBase58());
+
+    // The proof nodes kept in the tree's canopy are left out
+    let cnft = await getCompressedNft(assetId, auctionSolConnection);
+    // Listed under its verified collection, else its first verified creator
+    let collection = cnft.metadata.collection?.verified
+        ? cnft.metadata.collection.key
+        : cnft.metadata.creators.find((creator) => creator.verified)?.address;
+    if (!collection) throw new Error('The cNFT has no verified collection or creator');
+    let canopyDepth = await getCanopyDepth(cnft.merkleTree, auctionSolConnection);
+    let proofAccounts = cnft.proof.slice(0, cnft.proof.length - canopyDepth).map((node) => ({
+        pubkey: node,
+        isWritable: false,
+        isSigner: false,
+    }));
+
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.createCompressedAuction(
+        nonce,
+        new anchor.BN(startPrice * quoteDecimals),
+        new anchor.BN(duration),
+        getEnglishTerms(userAddress, quoteDecimals, buyNow, reservePrice, reserveSalt, softClose),
+        {
+            metadata: cnft.metadata,
+            nonce: cnft.leafNonce,
+            index: cnft.leafIndex,
+        }, {
+        accounts: {
+            auction: auctionPDA,
+            globalAuthority,
+            auctionVault: native ? auctionProgramID : await getAuctionVault(auctionPDA),
+            quoteMint: native ? auctionProgramID : quoteMint,
+            quoteMintInfo: native ? auctionProgramID : await getQuoteMintInfo(quoteMint),
+            owner: userAddress,
+            leafDelegate: cnft.delegate ?? userAddress,
+            assetId,
+            collectionConfig: await getCollectionConfigPDA(collection),
+            compressedAsset: await getCompressedAssetPDA(auctionPDA),
+            treeAuthority: await getTreeAuthority(cnft.merkleTree),
+            merkleTree: cnft.merkleTree,
+            logWrapper: SPL_NOOP_PROGRAM_ID,
+            compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+            bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+            tokenProgram: await getMintTokenProgram(quoteMint, auctionSolConnection),
+            systemProgram: SystemProgram.programId,
+        },
+        remainingAccounts: proofAccounts,
+        instructions: [],
+        signers: [],
+    }));
+
+    log.info(TAG, '[createCompressedAuction]', 'auction:', auctionPDA.toBase58());
+
+    return tx;
+}
+
+export const createCoreAuctionTx = async (userAddress: PublicKey, asset: PublicKey, startPrice: number, duration: number, buyNow?: BuyNowConfig, reservePrice?: number, reserveSalt?: Buffer, quoteMint?: PublicKey, softClose?: SoftCloseConfig) => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    // PublicKey.default lists a native SOL auction
+    quoteMint = quoteMint ?? htoTokenMint;
+    let native = quoteMint.equals(PublicKey.default);
+    let quoteDecimals = await getQuoteDecimals(quoteMint);
+
+    let d = new Date();
+    let nonce = new anchor.BN(Math.floor(d.getTime()/1000));
+    log.debug(TAG, '[createCoreAuctionTx]', 'nonce', nonce.toString());
+
+    let auctionPDA = await getAuctionPDA(userAddress, asset, nonce);
+    log.debug(TAG, '[createCoreAuctionTx]', 'auctionPDA', auctionPDA.toBase58());
+
+    let collection = await getCoreCollection(asset, auctionSolConnection);
+    if (!collection) throw new Error('The Core asset belongs to no collection');
+
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.createCoreAuction(
+        nonce,
+        new anchor.BN(startPrice * quoteDecimals),
+        new anchor.BN(duration),
+        getEnglishTerms(userAddress, quoteDecimals, buyNow, reservePrice, reserveSalt, softClose), {
+        accounts: {
+            auction: auctionPDA,
+            globalAuthority,
+            auctionVault: native ? auctionProgramID : await getAuctionVault(auctionPDA),
+            quoteMint: native ? auctionProgramID : quoteMint,
+            quoteMintInfo: native ? auctionProgramID : await getQuoteMintInfo(quoteMint),
+            owner: userAddress,
+            asset,
+            collection,
+            collectionConfig: await getCollectionConfigPDA(collection),
+            coreProgram: MPL_CORE_PROGRAM_ID,
+            tokenProgram: await getMintTokenProgram(quoteMint, auctionSolConnection),
+            systemProgram: SystemProgram.programId,
+        },
+        instructions: [],
+        signers: [],
+    }));
+
+    log.info(TAG, '[createCoreAuction]', 'auction:', auctionPDA.toBase58());
+
+    return tx;
+}
+
+export const createAuctionCancelTx = async (userAddress: PublicKey, auctionPDA: PublicKey) => {
+    let auctionState = await getAuctionState(auctionPDA);
+    let nftMint = auctionState.nftMint;
+
+    const [globalAuthority, bump] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    let compressed = isCompressed(auctionState);
+    let splNft = isSplNft(auctionState);
+    let { tokenProgram, nftTokenProgram } = await getTokenPrograms(auctionState);
+    let auctionAta = splNft ? await getAssociatedTokenAccount(globalAuthority, nftMint, nftTokenProgram) : auctionProgramID;
+
+    let tx = new Transaction();
+
+    // cNFTs go back through Bubblegum and Core assets through Core, neither needs an ATA
+    let ownerAta = auctionProgramID;
+    if (splNft) {
+        let { instructions, destinationAccounts } = await getATokenAccountsNeedCreate(
+            auctionSolConnection,
+            userAddress,
+            userAddress,
+            [nftMint],
+            nftTokenProgram
+        );
+        if (instructions.length > 0) instructions.map((ix) => tx.add(ix));
+        ownerAta = destinationAccounts[0];
+    }
+    let { cnft, proofAccounts } = await getCnftAccounts(auctionPDA, nftMint, userAddress, compressed);
+    let core = await getCoreAccounts(nftMint, auctionState.nftCollection, userAddress, isCore(auctionState));
+
+    tx.add(auctionProgram.instruction.cancelAuction(
+        bump, 
+        {
+            accounts: {
+                auction: auctionPDA,
+                ownerAta,
+                globalAuthority,
+                auctionAta,
+                auctionVault: isNativeAuction(auctionState) ? auctionProgramID : await getAuctionVault(auctionPDA),
+                nftMint: splNft ? nftMint : auctionProgramID,
+                seller: userAddress,
+                pnft: await getPnftAccounts(nftMint, auctionAta, ownerAta, userAddress, isProgrammable(auctionState)),
+                cnft,
+                core,
+                tokenProgram,
+                nftTokenProgram,
+            },
+            remainingAccounts: proofAccounts,
+            instructions: [],
+            signers: []
+        }
+    ));
+
+    return tx;
+}
+
+
+export const createForceCancelTx = async (userAddress: PublicKey, auctionPDA: PublicKey, reason: number, quarantine: boolean) => {
+    let auctionState = await getAuctionState(auctionPDA);
+    let nftMint = auctionState.nftMint;
+    let bidder = auctionState.bidder;
+    let native = isNativeAuction(auctionState);
+    let refund = auctionState.auctionType !== AUCTION_TYPE_SEALED && !bidder.equals(PublicKey.default);
+
+    const [globalAuthority, bump] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    let compressed = isCompressed(auctionState);
+    let splNft = isSplNft(auctionState);
+    let { tokenProgram, nftTokenProgram } = await getTokenPrograms(auctionState);
+    let auctionAta = splNft ? await getAssociatedTokenAccount(globalAuthority, nftMint, nftTokenProgram) : auctionProgramID;
+
+    let tx = new Transaction();
+
+    let sellerNftAta = auctionProgramID;
+    if (!quarantine && splNft) {
+        let { instructions, destinationAccounts } = await getATokenAccountsNeedCreate(
+            auctionSolConnection,
+            userAddress,
+            auctionState.seller,
+            [nftMint],
+            nftTokenProgram
+        );
+        if (instructions.length > 0) instructions.map((ix) => tx.add(ix));
+        sellerNftAta = destinationAccounts[0];
+    }
+    // A quarantined cNFT stays escrowed, so only a returned one needs Bubblegum accounts
+    let { cnft, proofAccounts } = await getCnftAccounts(auctionPDA, nftMint, auctionState.seller, compressed && !quarantine);
+    let core = await getCoreAccounts(nftMint, auctionState.nftCollection, auctionState.seller, isCore(auctionState) && !quarantine);
+
+    tx.add(auctionProgram.instruction.forceCancel(
+        bump, reason, quarantine, {
+            accounts: {
+                admin: userAddress,
+                globalAuthority,
+                auction: auctionPDA,
+                forceCancelRecord: await getForceCancelRecord(auctionPDA, auctionState.auctionId),
+                auctionAta,
+                auctionVault: native ? auctionProgramID : await getAuctionVault(auctionPDA),
+                seller: auctionState.seller,
+                sellerNftAta,
+                bidderAccount: refund && !native ? await getQuoteAccount(userAddress, bidder, auctionState, tx) : auctionProgramID,
+                bidder: refund && native ? bidder : auctionProgramID,
+                quoteMint: native ? auctionProgramID : auctionState.quoteMint,
+                pnft: await getPnftAccounts(nftMint, auctionAta, sellerNftAta, auctionState.seller, !quarantine && isProgrammable(auctionState)),
+                cnft,
+                core,
+                tokenProgram,
+                nftTokenProgram,
+                systemProgram: SystemProgram.programId,
+            },
+            // The merkle proof ends the remaining accounts
+            remainingAccounts: [...await getQuoteHookAccounts(auctionState), ...proofAccounts],
+            instructions: [],
+            signers: []
+        }
+    ));
+
+    return tx;
+}
+
+
+export const createReleaseQuarantinedNftTx = async (userAddress: PublicKey, auctionPDA: PublicKey, destination: PublicKey) => {
+    const [globalAuthority, bump] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    // The auction is closed by now, so look its quarantined record up by the auction field
+    let records = await auctionProgram.account.forceCancelRecord.all([
+        { memcmp: { offset: 8, bytes: auctionPDA.toBase58() } },
+    ]);
+    let quarantined = records.find((r) => (r.account as unknown as ForceCancelRecord).quarantined);
+    if (quarantined === undefined) {
+        throw new Error('No quarantined NFT for this auction');
+    }
+    let forceCancelRecord = quarantined.publicKey;
+    let record = quarantined.account as unknown as ForceCancelRecord;
+    let compressed = record.assetType === ASSET_TYPE_COMPRESSED;
+    let coreAsset = record.assetType === ASSET_TYPE_CORE;
+
+    let tx = new Transaction();
+
+    // A cNFT or a Core asset is released straight to the destination wallet
+    let nftTokenProgram = TOKEN_PROGRAM_ID;
+    let destinationAta = auctionProgramID;
+    if (record.assetType === ASSET_TYPE_NFT) {
+        nftTokenProgram = await getMintTokenProgram(record.nftMint, auctionSolConnection);
+        let { instructions, destinationAccounts } = await getATokenAccountsNeedCreate(
+            auctionSolConnection,
+            userAddress,
+            destination,
+            [record.nftMint],
+            nftTokenProgram
+        );
+        if (instructions.length > 0) instructions.map((ix) => tx.add(ix));
+        destinationAta = destinationAccounts[0];
+    }
+    let { cnft, proofAccounts } = await getCnftAccounts(auctionPDA, record.nftMint, destination, compressed);
+    // The record keeps no collection, so it is read from the asset
+    let core = await getCoreAccounts(
+        record.nftMint,
+        coreAsset ? await getCoreCollection(record.nftMint, auctionSolConnection) : auctionProgramID,
+        destination,
+        coreAsset
+    );
+
+    tx.add(auctionProgram.instruction.releaseQuarantinedNft(
+        bump, {
+            accounts: {
+                admin: userAddress,
+                globalAuthority,
+                forceCancelRecord,
+                auction: auctionPDA,
+                quarantineAta: record.assetType === ASSET_TYPE_NFT ? record.quarantineAta : auctionProgramID,
+                destinationAta,
+                pnft: await getPnftAccounts(
+                    record.nftMint,
+                    record.quarantineAta,
+                    destinationAta,
+                    destination,
+                    record.tokenStandard === TOKEN_STANDARD_PROGRAMMABLE_NON_FUNGIBLE
+                ),
+                cnft,
+                core,
+                nftTokenProgram,
+            },
+            remainingAccounts: proofAccounts,
+            instructions: [],
+            signers: []
+        }
+    ));
+
+    return tx;
+}
+
+
+// With maxBid the bid is a proxy, opening the auction's bid book first if needed
+export const createPlaceBidTx = async (userAddress: PublicKey, auctionPDA: PublicKey, bid: number, maxBid?: number) => {
+    let auctionState = await getAuctionState(auctionPDA);
+    let outBidder = auctionState.bidder;
+    let quoteDecimals = await getQuoteDecimals(auctionState.quoteMint);
+
+    const [globalAuthority, bump] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    let tx = new Transaction();
+    let auctionVault = isNativeAuction(auctionState) ? auctionProgramID : await getAuctionVault(auctionPDA);
+    let newBidderAccount = await getQuoteAccount(userAddress, userAddress, auctionState, tx);
+
+    let outBidderAccount = newBidderAccount;
+    if (outBidder.toBase58() === PublicKey.default.toBase58()) {
+        outBidder = userAddress;
+    } else {
+        outBidderAccount = await getQuoteAccount(userAddress, outBidder, auctionState, tx);
+    }
+
+    // Dutch auctions settle inside place_bid, English ones leave these accounts empty
+    let settlementAccounts: any = {
+        auctionAta: auctionProgramID,
+        treasury: await getTreasury(),
+        treasuryVault: auctionProgramID,
+        winnerAta: auctionProgramID,
+        sellerAta: auctionProgramID,
+        seller: auctionProgramID,
+        quoteMint: isNativeAuction(auctionState) ? auctionProgramID : auctionState.quoteMint,
+        ataProgram: auctionProgramID,
+        pnft: await getPnftAccounts(auctionState.nftMint, auctionProgramID, auctionProgramID, auctionProgramID, false),
+        cnft: (await getCnftAccounts(auctionPDA, auctionState.nftMint, auctionProgramID, false)).cnft,
+        core: await getCoreAccounts(auctionState.nftMint, auctionState.nftCollection, auctionProgramID, false),
+    };
+    let remainingAccounts = await getQuoteHookAccounts(auctionState);
+    if (auctionState.auctionType === AUCTION_TYPE_DUTCH) {
+        ({ settlementAccounts, remainingAccounts } = await getSettlementAccounts(
+            userAddress, auctionPDA, auctionState, userAddress, tx
+        ));
+    }
+
+    let bidBook = auctionProgramID;
+    if (auctionState.hasBidBook !== 0) {
+        bidBook = await getBidBookPDA(auctionPDA, auctionState.auctionId);
+    } else if (maxBid !== undefined) {
+        (await createOpenBidBookTx(userAddress, auctionPDA)).instructions.map((ix) => tx.add(ix));
+        bidBook = await getBidBookPDA(auctionPDA, auctionState.auctionId);
+    }
+
+    tx.add(auctionProgram.instruction.placeBid(
+        new anchor.BN(bid * quoteDecimals),
+        bump,
+        maxBid !== undefined ? new anchor.BN(maxBid * quoteDecimals) : null, {
+            accounts: {
+                bidder: userAddress,
+                auction: auctionPDA,
+                globalAuthority,
+                auctionVault,
+                outBidderAccount,
+                outBidder,
+                newBidderAccount,
+                bidBook,
+                ...settlementAccounts,
+                ...await getTokenPrograms(auctionState),
+                systemProgram: SystemProgram.programId,
+            },
+            remainingAccounts,
+            instructions: [],
+            signers: []
+        }
+    ));
+
+    return tx;
+}
+
+
+export const createOpenBidBookTx = async (userAddress: PublicKey, auctionPDA: PublicKey) => {
+    let auctionState = await getAuctionState(auctionPDA);
+
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.openBidBook({
+        accounts: {
+            payer: userAddress,
+            auction: auctionPDA,
+            bidBook: await getBidBookPDA(auctionPDA, auctionState.auctionId),
+            systemProgram: SystemProgram.programId,
+        },
+        instructions: [],
+        signers: [],
+    }));
+
+    return tx;
+}
+
+
+// Only possible once the auction is closed or relisted, refunding the rent to whoever opened the book
+export const createCloseBidBookTx = async (userAddress: PublicKey, auctionPDA: PublicKey) => {
+    // The book of a live auction stays open, every older book the user opened is closed
+    let liveId = await auctionSolConnection.getAccountInfo(auctionPDA) !== null
+        ? (await getAuctionState(auctionPDA)).auctionId
+        : null;
+    let bidBooks = await auctionProgram.account.bidBook.all([
+        { memcmp: { offset: 8, bytes: auctionPDA.toBase58() } },
+        { memcmp: { offset: 48, bytes: userAddress.toBase58() } },
+    ]);
+
+    let tx = new Transaction();
+    for (const { publicKey, account } of bidBooks) {
+        if (liveId !== null && (account as unknown as BidBook).auctionId.eq(liveId)) continue;
+        tx.add(auctionProgram.instruction.closeBidBook({
+            accounts: {
+                payer: userAddress,
+                auction: auctionPDA,
+                bidBook: publicKey,
+            },
+            instructions: [],
+            signers: [],
+        }));
+    }
+
+    return tx;
+}
+
+
+export const createAuctionClaimTx = async (userAddress: PublicKey, auctionPDA: PublicKey, reservePrice?: number, reserveSalt?: Buffer) => {
+    let auctionState = await getAuctionState(auctionPDA);
+    let winner = auctionState.bidder;
+    let nftMint = auctionState.nftMint;
+    let quoteDecimals = await getQuoteDecimals(auctionState.quoteMint);
+
+    const [globalAuthority, bump] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    let auctionVault = isNativeAuction(auctionState) ? auctionProgramID : await getAuctionVault(auctionPDA);
+    let tx = new Transaction();
+    let { settlementAccounts, remainingAccounts } = await getSettlementAccounts(
+        userAddress, auctionPDA, auctionState, winner, tx
+    );
+
+    if (auctionState.auctionType === AUCTION_TYPE_SEALED) {
+        let winnerBid = await getSealedBid(auctionPDA, winner);
+        let winnerAccount = await getQuoteAccount(userAddress, winner, auctionState, tx);
+
+        tx.add(auctionProgram.instruction.claimSealedAuction(
+            bump, {
+                accounts: {
+                    claimer: userAddress,
+                    auction: auctionPDA,
+                    globalAuthority,
+                    winnerBid,
+                    winnerBidVault: await getSealedBidVault(winnerBid),
+                    winner,
+                    winnerAccount,
+                    auctionVault,
+                    nftMint,
+                    ...settlementAccounts,
+                    ...await getTokenPrograms(auctionState),
+                    // Gets back the auction vault rent, sealed auctions being never native
+                    seller: auctionState.seller,
+                },
+                remainingAccounts,
+                instructions: [],
+                signers: []
+            }
+        ));
+        return tx;
+    }
+
+    // Refund and return accounts, used when the revealed reserve is not met
+    let reserveAccounts: any = {
+        bidderAccount: auctionProgramID,
+        sellerNftAta: auctionProgramID,
+        bidder: auctionProgramID,
+    };
+    // The sale is also called off once the seller let a day pass without revealing the reserve
+    let global = await getGlobalState();
+    let revealLapsed = auctionState.reserveHash.some((byte) => byte !== 0)
+        && reservePrice === undefined
+        && Date.now() / 1000 >= auctionState.endTime.add(global.day).toNumber();
+    if (reservePrice !== undefined || revealLapsed) {
+        let bidderAccount = await getQuoteAccount(userAddress, winner, auctionState, tx);
+        let sellerNftAta = auctionProgramID;
+        if (isSplNft(auctionState)) {
+            let ret2 = await getATokenAccountsNeedCreate(
+                auctionSolConnection,
+                userAddress,
+                auctionState.seller,
+                [nftMint],
+                await getMintTokenProgram(nftMint, auctionSolConnection)
+            );
+            if (ret2.instructions.length > 0) ret2.instructions.map((ix) => tx.add(ix));
+            sellerNftAta = ret2.destinationAccounts[0];
+        }
+        reserveAccounts = {
+            bidderAccount,
+            sellerNftAta,
+            bidder: isNativeAuction(auctionState) ? winner : auctionProgramID,
+        };
+        // A missed reserve sends the NFT back to the seller instead of the winner
+        if (revealLapsed || new anchor.BN(reservePrice * quoteDecimals).gt(auctionState.currentBid)) {
+            reserveAccounts.pnft = await getPnftAccounts(
+                nftMint,
+                settlementAccounts.auctionAta,
+                sellerNftAta,
+                auctionState.seller,
+                isProgrammable(auctionState)
+            );
+            reserveAccounts.cnft = { ...settlementAccounts.cnft, newLeafOwner: isCompressed(auctionState) ? auctionState.seller : auctionProgramID };
+            reserveAccounts.core = { ...settlementAccounts.core, newOwner: isCore(auctionState) ? auctionState.seller : auctionProgramID };
+        }
+    } else if (auctionState.escrowedBid.gt(auctionState.currentBid)) {
+        // The winner's proxy gets its unused escrow back
+        reserveAccounts.bidderAccount = await getQuoteAccount(userAddress, winner, auctionState, tx);
+        reserveAccounts.bidder = isNativeAuction(auctionState) ? winner : auctionProgramID;
+    }
+
+    tx.add(auctionProgram.instruction.claimAuction(
+        bump,
+        new anchor.BN((reservePrice ?? 0) * quoteDecimals),
+        [...(reserveSalt ?? Buffer.alloc(32))], {
+            accounts: {
+                claimer: userAddress,
+                auction: auctionPDA,
+                globalAuthority,
+                auctionVault,
+                nftMint: isSplNft(auctionState) ? nftMint : auctionProgramID,
+                ...settlementAccounts,
+                ...reserveAccounts,
+                ...await getTokenPrograms(auctionState),
+            },
+            remainingAccounts,
+            instructions: [],
+            signers: []
+        }
+    ));
+
+    return tx;
+}
+
+
+export const createSettleExpiredAuctionTx = async (userAddress: PublicKey, auctionPDA: PublicKey) => {
+    let auctionState = await getAuctionState(auctionPDA);
+    let seller = auctionState.seller;
+    let nftMint = auctionState.nftMint;
+
+    const [globalAuthority, bump] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    let compressed = isCompressed(auctionState);
+    let splNft = isSplNft(auctionState);
+    let { tokenProgram, nftTokenProgram } = await getTokenPrograms(auctionState);
+    let auctionAta = splNft ? await getAssociatedTokenAccount(globalAuthority, nftMint, nftTokenProgram) : auctionProgramID;
+
+    let tx = new Transaction();
+
+    let sellerNftAta = auctionProgramID;
+    if (splNft) {
+        let { instructions, destinationAccounts } = await getATokenAccountsNeedCreate(
+            auctionSolConnection,
+            userAddress,
+            seller,
+            [nftMint],
+            nftTokenProgram
+        );
+        if (instructions.length > 0) instructions.map((ix) => tx.add(ix));
+        sellerNftAta = destinationAccounts[0];
+    }
+    let { cnft, proofAccounts } = await getCnftAccounts(auctionPDA, nftMint, seller, compressed);
+    let core = await getCoreAccounts(nftMint, auctionState.nftCollection, seller, isCore(auctionState));
+
+    tx.add(auctionProgram.instruction.settleExpiredAuction(
+        bump, {
+            accounts: {
+                payer: userAddress,
+                auction: auctionPDA,
+                globalAuthority,
+                seller,
+                auctionAta,
+                sellerNftAta,
+                auctionVault: isNativeAuction(auctionState) ? auctionProgramID : await getAuctionVault(auctionPDA),
+                pnft: await getPnftAccounts(nftMint, auctionAta, sellerNftAta, seller, isProgrammable(auctionState)),
+                cnft,
+                core,
+                tokenProgram,
+                nftTokenProgram,
+            },
+            remainingAccounts: proofAccounts,
+            instructions: [],
+            signers: []
+        }
+    ));
+
+    return tx;
+}
+
+
+export const createBuyNowTx = async (userAddress: PublicKey, auctionPDA: PublicKey) => {
+    let auctionState = await getAuctionState(auctionPDA);
+    let outBidder = auctionState.bidder;
+
+    const [globalAuthority, bump] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    let tx = new Transaction();
+    let auctionVault = isNativeAuction(auctionState) ? auctionProgramID : await getAuctionVault(auctionPDA);
+    let buyerAccount = await getQuoteAccount(userAddress, userAddress, auctionState, tx);
+
+    let outBidderAccount = buyerAccount;
+    if (outBidder.toBase58() === PublicKey.default.toBase58()) {
+        outBidder = userAddress;
+    } else {
+        outBidderAccount = await getQuoteAccount(userAddress, outBidder, auctionState, tx);
+    }
+
+    let { settlementAccounts, remainingAccounts } = await getSettlementAccounts(
+        userAddress, auctionPDA, auctionState, userAddress, tx
+    );
+
+    let bidBook = auctionState.hasBidBook !== 0 ? await getBidBookPDA(auctionPDA, auctionState.auctionId) : auctionProgramID;
+
+    tx.add(auctionProgram.instruction.buyNow(
+        bump, {
+            accounts: {
+                buyer: userAddress,
+                auction: auctionPDA,
+                globalAuthority,
+                auctionVault,
+                outBidderAccount,
+                outBidder,
+                buyerAccount,
+                bidBook,
+                nftMint: isSplNft(auctionState) ? auctionState.nftMint : auctionProgramID,
+                ...settlementAccounts,
+                ...await getTokenPrograms(auctionState),
+                systemProgram: SystemProgram.programId,
+            },
+            remainingAccounts,
+            instructions: [],
+            signers: []
+        }
+    ));
+
+    return tx;
+}
+
+
+export const createCommitBidTx = async (userAddress: PublicKey, auctionPDA: PublicKey, bid: number, deposit: number, salt: Buffer) => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    let auctionState = await getAuctionState(auctionPDA);
+    let quoteDecimals = await getQuoteDecimals(auctionState.quoteMint);
+
+    let sealedBid = await getSealedBid(auctionPDA, userAddress);
+    let { tokenProgram } = await getTokenPrograms(auctionState);
+    let bidderAccount = await getAssociatedTokenAccount(userAddress, auctionState.quoteMint, tokenProgram);
+
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.commitBid(
+        getBidCommitment(new anchor.BN(bid * quoteDecimals), salt, userAddress),
+        new anchor.BN(deposit * quoteDecimals), {
+            accounts: {
+                bidder: userAddress,
+                auction: auctionPDA,
+                globalAuthority,
+                sealedBid,
+                bidVault: await getSealedBidVault(sealedBid),
+                quoteMint: auctionState.quoteMint,
+                bidderAccount,
+                tokenProgram,
+                systemProgram: SystemProgram.programId,
+            },
+            remainingAccounts: await getQuoteHookAccounts(auctionState),
+            instructions: [],
+            signers: []
+        }
+    ));
+
+    return tx;
+}
+
+
+export const createRevealBidTx = async (userAddress: PublicKey, auctionPDA: PublicKey, bid: number, salt: Buffer) => {
+    let auctionState = await getAuctionState(auctionPDA);
+    let quoteDecimals = await getQuoteDecimals(auctionState.quoteMint);
+
+    let tx = new Transaction();
+    tx.add(auctionProgram.instruction.revealBid(
+        new anchor.BN(bid * quoteDecimals), [...salt], {
             accounts: {
+                bidder: userAddress,
                 auction: auctionPDA,
-                ownerAta: destinationAccounts[0],
-                globalAuthority,
-                auctionAta,
-                nftMint,
-                seller: userAddress,
-                tokenProgram: TOKEN_PROGRAM_ID,
+                sealedBid: await getSealedBid(auctionPDA, userAddress),
             },
             instructions: [],
             signers: []
@@ -283,57 +1648,63 @@ export const createAuctionCancelTx = async (userAddress: PublicKey, auctionPDA:
 }
 
 
-export const createPlaceBidTx = async (userAddress: PublicKey, auctionPDA: PublicKey, bid: number) => {
-    let auctionState = await getAuctionState(auctionPDA);
-    let outBidder = auctionState.bidder;
-
+export const createWithdrawSealedBidTx = async (userAddress: PublicKey, auctionPDA: PublicKey) => {
     const [globalAuthority, bump] = await PublicKey.findProgramAddress(
         [Buffer.from(GLOBAL_AUTHORITY_SEED)],
         auctionProgramID,
     );
 
+    let sealedBid = await getSealedBid(auctionPDA, userAddress);
+    let bidVault = await getSealedBidVault(sealedBid);
+
+    // The auction may already be closed, so take the quote mint from the deposit vault
+    let vaultInfo = await auctionSolConnection.getParsedAccountInfo(bidVault);
+    let quoteMint = new PublicKey((vaultInfo.value.data as ParsedAccountData).parsed.info.mint);
+    let tokenProgram = vaultInfo.value.owner;
+
     let ret1 = await getATokenAccountsNeedCreate(
-        auctionSolConnection,
-        userAddress,
-        globalAuthority,
-        [htoTokenMint]
-    );
-    let ret2 = await getATokenAccountsNeedCreate(
         auctionSolConnection,
         userAddress,
         userAddress,
-        [htoTokenMint]
+        [quoteMint],
+        tokenProgram
     );
-    let ret3 = await getATokenAccountsNeedCreate(
+    let treasury = await getTreasury();
+    let ret2 = await getATokenAccountsNeedCreate(
         auctionSolConnection,
         userAddress,
-        outBidder,
-        [htoTokenMint]
+        treasury,
+        [quoteMint],
+        tokenProgram
     );
-    let tx = new Transaction();
 
+    // Bids of a force cancelled auction are withdrawn without forfeit
+    let { auctionId } = await auctionProgram.account.sealedBid.fetch(sealedBid);
+    let forceCancelRecord = await getForceCancelRecord(auctionPDA, auctionId as anchor.BN);
+    if (await auctionSolConnection.getAccountInfo(forceCancelRecord) === null) {
+        forceCancelRecord = auctionProgramID;
+    }
+
+    let tx = new Transaction();
     if (ret1.instructions.length > 0) ret1.instructions.map((ix) => tx.add(ix));
     if (ret2.instructions.length > 0) ret2.instructions.map((ix) => tx.add(ix));
-    if (ret3.instructions.length > 0) ret3.instructions.map((ix) => tx.add(ix));
-    
-    let outBidderAccount = ret3.destinationAccounts[0];
-    if (outBidder.toBase58() === PublicKey.default.toBase58()) {
-        outBidder = userAddress;
-        outBidderAccount = ret2.destinationAccounts[0];
-    }
 
-    tx.add(auctionProgram.instruction.placeBid(
-        new anchor.BN(bid * htoTokenDecimals), bump, {
+    tx.add(auctionProgram.instruction.withdrawSealedBid(
+        bump, {
             accounts: {
                 bidder: userAddress,
                 auction: auctionPDA,
                 globalAuthority,
-                auctionVault: ret1.destinationAccounts[0],
-                outBidderAccount,
-                outBidder,
-                newBidderAccount: ret2.destinationAccounts[0],
-                tokenProgram: TOKEN_PROGRAM_ID,
+                sealedBid,
+                bidVault,
+                quoteMint,
+                forceCancelRecord,
+                bidderAccount: ret1.destinationAccounts[0],
+                treasury,
+                treasuryVault: ret2.destinationAccounts[0],
+                tokenProgram,
             },
+            remainingAccounts: await getTransferHookAccounts(quoteMint, auctionSolConnection),
             instructions: [],
             signers: []
         }
@@ -343,100 +1714,280 @@ export const createPlaceBidTx = async (userAddress: PublicKey, auctionPDA: Publi
 }
 
 
-export const createAuctionClaimTx = async (userAddress: PublicKey, auctionPDA: PublicKey) => {
-    let auctionState = await getAuctionState(auctionPDA);
-    let winner = auctionState.bidder;
+// Builds the accounts a sale is settled with, adding ATA creations to the tx
+const getSettlementAccounts = async (userAddress: PublicKey, auctionPDA: PublicKey, auctionState: AuctionPool, winner: PublicKey, tx: Transaction) => {
     let seller = auctionState.seller;
     let nftMint = auctionState.nftMint;
+    let native = isNativeAuction(auctionState);
 
-    const [globalAuthority, bump] = await PublicKey.findProgramAddress(
+    const [globalAuthority] = await PublicKey.findProgramAddress(
         [Buffer.from(GLOBAL_AUTHORITY_SEED)],
         auctionProgramID,
     );
 
-    let ret1 = await getATokenAccountsNeedCreate(
-        auctionSolConnection,
-        userAddress,
-        globalAuthority,
-        [htoTokenMint, nftMint]
-    );
+    let { tokenProgram, nftTokenProgram } = await getTokenPrograms(auctionState);
 
-    let ret2 = await getATokenAccountsNeedCreate(
-        auctionSolConnection,
-        userAddress,
-        vaultWallet,
-        [htoTokenMint]
-    );
+    // cNFTs move through Bubblegum and Core assets through Core, neither has ATAs
+    let auctionAta = auctionProgramID;
+    let winnerAta = auctionProgramID;
+    if (isSplNft(auctionState)) {
+        let ret1 = await getATokenAccountsNeedCreate(
+            auctionSolConnection,
+            userAddress,
+            globalAuthority,
+            [nftMint],
+            nftTokenProgram
+        );
 
-    let ret3 = await getATokenAccountsNeedCreate(
-        auctionSolConnection,
-        userAddress,
-        winner,
-        [nftMint]
-    );
+        let ret3 = await getATokenAccountsNeedCreate(
+            auctionSolConnection,
+            userAddress,
+            winner,
+            [nftMint],
+            nftTokenProgram
+        );
 
-    let ret4 = await getATokenAccountsNeedCreate(
-        auctionSolConnection,
-        userAddress,
-        seller,
-        [htoTokenMint]
-    );
+        if (ret1.instructions.length > 0) ret1.instructions.map((ix) => tx.add(ix));
+        if (ret3.instructions.length > 0) ret3.instructions.map((ix) => tx.add(ix));
+        auctionAta = ret1.destinationAccounts[0];
+        winnerAta = ret3.destinationAccounts[0];
+    }
 
-    let { metadata: { Metadata } } = programs;
-    let metadataAccount = await Metadata.getPDA(nftMint);
-    const metadata = await Metadata.load(auctionSolConnection, metadataAccount);
-    let creators = metadata.data.data.creators;
+    // Royalties are paid to the creators snapshotted at listing
+    let creators = auctionState.creators.slice(0, auctionState.creatorCount);
 
-    let mintMetadata = await getMetadata(nftMint);
+    // Native SOL auctions pay the fee, the seller and the creators in lamports
+    let treasury = await getTreasury();
+    let treasuryVault = await getQuoteAccount(userAddress, treasury, auctionState, tx);
+    let sellerAta = await getQuoteAccount(userAddress, seller, auctionState, tx);
 
-    let tx = new Transaction();
-    if (ret1.instructions.length > 0) ret1.instructions.map((ix) => tx.add(ix));
-    if (ret2.instructions.length > 0) ret2.instructions.map((ix) => tx.add(ix));
-    if (ret3.instructions.length > 0) ret3.instructions.map((ix) => tx.add(ix));
-    if (ret4.instructions.length > 0) ret4.instructions.map((ix) => tx.add(ix));
-    
+    // The program creates missing creator ATAs, so each ATA goes with its creator's wallet
     let remainingAccounts = [];
     for (let i = 0; i< creators.length; i++) {
-        let { instructions, destinationAccounts } = await getATokenAccountsNeedCreate(
-            auctionSolConnection,
-            userAddress,
-            new PublicKey(creators[i].address),
-            [htoTokenMint]
-        );
-        log.debug(TAG, '[createAuctionClaimTx]', 'creator', creators[i].address);
-        log.debug(TAG, '[createAuctionClaimTx]', 'creatorATA', destinationAccounts[0].toBase58());
+        let creator = creators[i];
+        let creatorAccount = native ? creator : await getAssociatedTokenAccount(creator, auctionState.quoteMint, tokenProgram);
+        log.debug(TAG, '[getSettlementAccounts]', 'creator', creator.toBase58());
+        log.debug(TAG, '[getSettlementAccounts]', 'creatorAccount', creatorAccount.toBase58());
         remainingAccounts.push({
-            pubkey: destinationAccounts[0],
+            pubkey: creatorAccount,
             isWritable: true,
             isSigner: false,
         })
-        if (instructions.length > 0) instructions.map((ix) => tx.add(ix));
+        if (!native) {
+            remainingAccounts.push({
+                pubkey: creator,
+                isWritable: false,
+                isSigner: false,
+            })
+        }
     }
+    // Creator accounts are matched by address, so the hook accounts can follow them
+    remainingAccounts.push(...await getQuoteHookAccounts(auctionState));
+    // The merkle proof of a cNFT ends the remaining accounts
+    let { cnft, proofAccounts } = await getCnftAccounts(auctionPDA, nftMint, winner, isCompressed(auctionState));
+    remainingAccounts.push(...proofAccounts);
 
-    tx.add(auctionProgram.instruction.claimAuction(
-        bump, {
-            accounts: {
-                claimer: userAddress,
-                auction: auctionPDA,
-                globalAuthority,
-                auctionAta: ret1.destinationAccounts[1], 
-                auctionVault: ret1.destinationAccounts[0],
-                hlVault: ret2.destinationAccounts[0],
+    return {
+        settlementAccounts: {
+            auctionAta,
+            treasury,
+            treasuryVault,
+            winnerAta,
+            sellerAta,
+            seller: native ? seller : auctionProgramID,
+            quoteMint: native ? auctionProgramID : auctionState.quoteMint,
+            ataProgram: native ? auctionProgramID : ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            pnft: await getPnftAccounts(
                 nftMint,
-                winnerAta: ret3.destinationAccounts[0],
-                sellerAta: ret4.destinationAccounts[0],
-                mintMetadata,
-                tokenProgram: TOKEN_PROGRAM_ID,
+                auctionAta,
+                winnerAta,
+                winner,
+                isProgrammable(auctionState)
+            ),
+            cnft,
+            core: await getCoreAccounts(nftMint, auctionState.nftCollection, winner, isCore(auctionState)),
+        },
+        remainingAccounts,
+    };
+}
+
+// Quote token ATA of owner, created in the tx when missing.
+// Native SOL auctions have none and get the program ID placeholder
+const getQuoteAccount = async (userAddress: PublicKey, owner: PublicKey, auctionState: AuctionPool, tx: Transaction) => {
+    if (isNativeAuction(auctionState)) return auctionProgramID;
+
+    let { instructions, destinationAccounts } = await getATokenAccountsNeedCreate(
+        auctionSolConnection,
+        userAddress,
+        owner,
+        [auctionState.quoteMint],
+        await getMintTokenProgram(auctionState.quoteMint, auctionSolConnection)
+    );
+    if (instructions.length > 0) instructions.map((ix) => tx.add(ix));
+    return destinationAccounts[0];
+}
+
+// Token programs of the quote mint and of the NFT mint, Token or Token-2022
+const getTokenPrograms = async (auctionState: AuctionPool) => {
+    return {
+        tokenProgram: await getMintTokenProgram(auctionState.quoteMint, auctionSolConnection),
+        // Any token program fills the slot of a cNFT or Core auction
+        nftTokenProgram: isSplNft(auctionState)
+            ? await getMintTokenProgram(auctionState.nftMint, auctionSolConnection)
+            : TOKEN_PROGRAM_ID,
+    };
+}
+
+// Transfer hook accounts of the quote mint, passed as remaining accounts
+const getQuoteHookAccounts = async (auctionState: AuctionPool) => {
+    if (isNativeAuction(auctionState)) return [];
+    return await getTransferHookAccounts(auctionState.quoteMint, auctionSolConnection);
+}
+
+// Soft-close rules of an English auction, null for the global default
+// Buy-now, hidden reserve and soft-close terms of an English auction listing
+const getEnglishTerms = (userAddress: PublicKey, quoteDecimals: number, buyNow?: BuyNowConfig, reservePrice?: number, reserveSalt?: Buffer, softClose?: SoftCloseConfig) => {
+    return {
+        buyNowPrice: buyNow ? new anchor.BN(buyNow.price * quoteDecimals) : null,
+        buyNowCutoffBps: buyNow ? buyNow.cutoffBps : 0,
+        reserveHash: reservePrice !== undefined
+            ? getReserveCommitment(new anchor.BN(reservePrice * quoteDecimals), reserveSalt, userAddress)
+            : null,
+        softClose: softClose
+            ? {
+                windowSecs: new anchor.BN(softClose.windowSecs),
+                extensionSecs: new anchor.BN(softClose.extensionSecs),
+                maxExtensionSecs: new anchor.BN(softClose.maxExtensionSecs),
+            }
+            : null,
+    };
+}
+
+const isNativeAuction = (auctionState: AuctionPool) => {
+    return auctionState.quoteMint.equals(PublicKey.default);
+}
+
+const isCompressed = (auctionState: AuctionPool) => {
+    return auctionState.assetType === ASSET_TYPE_COMPRESSED;
+}
+
+const isCore = (auctionState: AuctionPool) => {
+    return auctionState.assetType === ASSET_TYPE_CORE;
+}
+
+const isSplNft = (auctionState: AuctionPool) => {
+    return auctionState.assetType === ASSET_TYPE_NFT;
+}
+
+const isProgrammable = (auctionState: AuctionPool) => {
+    return auctionState.tokenStandard === TOKEN_STANDARD_PROGRAMMABLE_NON_FUNGIBLE;
+}
+
+// Token Metadata accounts moving a pNFT from source to destination.
+// Legacy NFTs are moved by the token program and get the program ID placeholder
+const getPnftAccounts = async (mint: PublicKey, source: PublicKey, destination: PublicKey, destinationOwner: PublicKey, programmable: boolean) => {
+    if (!programmable) {
+        return {
+            destinationOwner: auctionProgramID,
+            mint: auctionProgramID,
+            metadata: auctionProgramID,
+            edition: auctionProgramID,
+            ownerTokenRecord: auctionProgramID,
+            destinationTokenRecord: auctionProgramID,
+            authRules: auctionProgramID,
+            authRulesProgram: auctionProgramID,
+            sysvarInstructions: auctionProgramID,
+            tokenMetadataProgram: auctionProgramID,
+            ataProgram: auctionProgramID,
+            systemProgram: auctionProgramID,
+        };
+    }
+
+    let { ruleSet } = await getProgrammableConfig(mint, auctionSolConnection);
+    log.debug(TAG, '[getPnftAccounts]', 'ruleSet', ruleSet?.toBase58());
+
+    return {
+        destinationOwner,
+        mint,
+        metadata: await getMetadata(mint),
+        edition: await getMasterEdition(mint),
+        ownerTokenRecord: await getTokenRecord(mint, source),
+        destinationTokenRecord: await getTokenRecord(mint, destination),
+        authRules: ruleSet ?? auctionProgramID,
+        authRulesProgram: ruleSet ? AUTH_RULES_PROGRAM : auctionProgramID,
+        sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenMetadataProgram: METAPLEX,
+        ataProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+    };
+}
+
+// Bubblegum accounts moving an escrowed cNFT to newLeafOwner, with the merkle
+// proof to end the remaining accounts. SPL NFTs get the program ID placeholder
+const getCnftAccounts = async (auctionPDA: PublicKey, assetId: PublicKey, newLeafOwner: PublicKey, compressed: boolean) => {
+    if (!compressed) {
+        return {
+            cnft: {
+                compressedAsset: auctionProgramID,
+                treeAuthority: auctionProgramID,
+                merkleTree: auctionProgramID,
+                newLeafOwner: auctionProgramID,
+                logWrapper: auctionProgramID,
+                compressionProgram: auctionProgramID,
+                bubblegumProgram: auctionProgramID,
+                systemProgram: auctionProgramID,
             },
-            remainingAccounts,
-            instructions: [],
-            signers: []
-        }
-    ));
+            proofAccounts: [],
+        };
+    }
 
-    return tx;
+    let compressedAsset = await getCompressedAssetPDA(auctionPDA);
+    let record = await auctionProgram.account.compressedAsset.fetch(compressedAsset) as unknown as CompressedAsset;
+    // The program takes as many proof nodes as the listing passed
+    let { proof } = await getCompressedNft(assetId, auctionSolConnection);
+
+    return {
+        cnft: {
+            compressedAsset,
+            treeAuthority: await getTreeAuthority(record.merkleTree),
+            merkleTree: record.merkleTree,
+            newLeafOwner,
+            logWrapper: SPL_NOOP_PROGRAM_ID,
+            compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+            bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        },
+        proofAccounts: proof.slice(0, record.proofLen).map((node) => ({
+            pubkey: node,
+            isWritable: false,
+            isSigner: false,
+        })),
+    };
+}
+
+// Core accounts moving an escrowed asset to newOwner.
+// Other auctions get the program ID placeholder
+const getCoreAccounts = async (asset: PublicKey, collection: PublicKey, newOwner: PublicKey, core: boolean) => {
+    if (!core) {
+        return {
+            asset: auctionProgramID,
+            collection: auctionProgramID,
+            newOwner: auctionProgramID,
+            coreProgram: auctionProgramID,
+            systemProgram: auctionProgramID,
+        };
     }
 
+    return {
+        asset,
+        collection,
+        newOwner,
+        coreProgram: MPL_CORE_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+    };
+}
+
 /////////////////////////////////////////////////////////////////
 /////////////////////////////////////////////////////////////////
 /////////////////////////////////////////////////////////////////
@@ -476,7 +2027,7 @@ export const getAllAuction = async (seller: string | undefined, nftCollection: s
         filters.push(
             {
                 memcmp: {
-                    offset: 104,
+                    offset: 136,
                     bytes: bidder
                 }
             }
@@ -491,16 +2042,18 @@ export const getAllAuction = async (seller: string | undefined, nftCollection: s
         const seller = new PublicKey(data.slice(8, 40));
         const nftMint = new PublicKey(data.slice(40, 72));
         const nftCollection = new PublicKey(data.slice(72, 104));
-        const bidder = new PublicKey(data.slice(104, 136));
-        const currentBid = new anchor.BN(data.slice(136, 144).reverse());
-        const startPrice = new anchor.BN(data.slice(144, 152).reverse());
-        const endTime = new anchor.BN(data.slice(152, 160).reverse());
+        const quoteMint = new PublicKey(data.slice(104, 136));
+        const bidder = new PublicKey(data.slice(136, 168));
+        const currentBid = new anchor.BN(data.slice(168, 176).reverse());
+        const startPrice = new anchor.BN(data.slice(176, 184).reverse());
+        const endTime = new anchor.BN(data.slice(184, 192).reverse());
 
         auctions.push({
             pda : auctionAccounts[i].pubkey.toBase58(),
             seller: seller.toBase58(),
             nftMint: nftMint.toBase58(),
             nftCollection: nftCollection.toBase58(),
+            quoteMint: quoteMint.toBase58(),
             bidder: bidder.toBase58(),
             currentBid: currentBid.toNumber(),
             startPrice: startPrice.toNumber(),
@@ -511,6 +2064,16 @@ export const getAllAuction = async (seller: string | undefined, nftCollection: s
 }
 
 
+// Auctions left over from the first release
+export const getLegacyAuctions = async (): Promise<PublicKey[]> => {
+    let accounts = await auctionSolConnection.getProgramAccounts(auctionProgramID, {
+        filters: [{ dataSize: LEGACY_AUCTION_SIZE }],
+        dataSlice: { offset: 0, length: 0 },
+    });
+    return accounts.map((account) => account.pubkey);
+}
+
+
 export const getAuctionInfo = async (auctionPDA: PublicKey) => {
     const auctionInfo: AuctionPool = await getAuctionState(auctionPDA);
     return {
@@ -518,10 +2081,279 @@ export const getAuctionInfo = async (auctionPDA: PublicKey) => {
         seller: auctionInfo.seller.toBase58(),
         nftMint: auctionInfo.nftMint.toBase58(),
         nftCollection: auctionInfo.nftCollection.toBase58(),
+        quoteMint: auctionInfo.quoteMint.toBase58(),
         bidder: auctionInfo.bidder.toBase58(),
         currentBid: auctionInfo.currentBid.toNumber(),
         startPrice: auctionInfo.startPrice.toNumber(),
         endTime: auctionInfo.endTime.toNumber(),
+        originalEndTime: auctionInfo.originalEndTime.toNumber(),
+        softCloseWindow: auctionInfo.softCloseWindow.toNumber(),
+        extensionSecs: auctionInfo.extensionSecs.toNumber(),
+        maxExtensionSecs: auctionInfo.maxExtensionSecs.toNumber(),
+        escrowedBid: auctionInfo.escrowedBid.toNumber(),
+        hasBidBook: auctionInfo.hasBidBook !== 0,
+        incrementTiers: auctionInfo.tierFromBids.slice(0, auctionInfo.incrementTierCount).map((fromBid, i) => ({
+            fromBid: fromBid.toString(),
+            increment: auctionInfo.tierIncrements[i].toString(),
+            incrementBps: auctionInfo.tierIncrementBps[i],
+        })),
+        collectionIncrements: auctionInfo.collectionIncrements !== 0,
+        frozen: auctionInfo.frozen !== 0,
+        feeOverride: auctionInfo.hasFeeOverride !== 0 ? auctionInfo.feeOverride.toNumber() : null,
+        sellerFeeBasisPoints: auctionInfo.sellerFeeBasisPoints,
+        creators: auctionInfo.creators.slice(0, auctionInfo.creatorCount).map((creator, i) => ({
+            address: creator.toBase58(),
+            share: auctionInfo.creatorShares[i],
+            verified: auctionInfo.creatorVerified[i] !== 0,
+        })),
+    };
+}
+
+
+export const getQuoteMintInfo = async (quoteMint: PublicKey): Promise<PublicKey> => {
+    const [quoteMintInfo] = await PublicKey.findProgramAddress(
+        [Buffer.from(QUOTE_MINT_SEED), quoteMint.toBuffer()],
+        auctionProgramID,
+    );
+    return quoteMintInfo;
+}
+
+
+// Multiplier from UI amounts to base units of the quote mint
+export const getQuoteDecimals = async (quoteMint: PublicKey): Promise<number> => {
+    if (quoteMint.equals(PublicKey.default)) return web3.LAMPORTS_PER_SOL;
+    const mintInfo = await auctionSolConnection.getParsedAccountInfo(quoteMint);
+    return 10 ** (mintInfo.value.data as ParsedAccountData).parsed.info.decimals;
+}
+
+
+export const getAuctionPDA = async (seller: PublicKey, mint: PublicKey, nonce: anchor.BN): Promise<PublicKey> => {
+    const [auctionPDA] = await PublicKey.findProgramAddress(
+        [Buffer.from(AUCTION_SEED), seller.toBuffer(), mint.toBuffer(), nonce.toArrayLike(Buffer, 'le', 8)],
+        auctionProgramID,
+    );
+    return auctionPDA;
+}
+
+
+export const getAuctionVault = async (auctionPDA: PublicKey): Promise<PublicKey> => {
+    const [auctionVault] = await PublicKey.findProgramAddress(
+        [Buffer.from(AUCTION_VAULT_SEED), auctionPDA.toBuffer()],
+        auctionProgramID,
+    );
+    return auctionVault;
+}
+
+
+export const getSealedBid = async (auctionPDA: PublicKey, bidder: PublicKey): Promise<PublicKey> => {
+    const [sealedBid] = await PublicKey.findProgramAddress(
+        [Buffer.from(SEALED_BID_SEED), auctionPDA.toBuffer(), bidder.toBuffer()],
+        auctionProgramID,
+    );
+    return sealedBid;
+}
+
+
+export const getSealedBidVault = async (sealedBid: PublicKey): Promise<PublicKey> => {
+    const [bidVault] = await PublicKey.findProgramAddress(
+        [Buffer.from(SEALED_BID_VAULT_SEED), sealedBid.toBuffer()],
+        auctionProgramID,
+    );
+    return bidVault;
+}
+
+
+export const getTreasury = async (): Promise<PublicKey> => {
+    const [treasury] = await PublicKey.findProgramAddress(
+        [Buffer.from(TREASURY_SEED)],
+        auctionProgramID,
+    );
+    return treasury;
+}
+
+
+export const getCompressedAssetPDA = async (auctionPDA: PublicKey): Promise<PublicKey> => {
+    const [compressedAsset] = await PublicKey.findProgramAddress(
+        [Buffer.from(COMPRESSED_ASSET_SEED), auctionPDA.toBuffer()],
+        auctionProgramID,
+    );
+    return compressedAsset;
+}
+
+
+export const getCollectionConfigPDA = async (collection: PublicKey): Promise<PublicKey> => {
+    const [collectionConfig] = await PublicKey.findProgramAddress(
+        [Buffer.from(COLLECTION_CONFIG_SEED), collection.toBuffer()],
+        auctionProgramID,
+    );
+    return collectionConfig;
+}
+
+export const getBidBookPDA = async (auctionPDA: PublicKey, auctionId: anchor.BN): Promise<PublicKey> => {
+    const [bidBook] = await PublicKey.findProgramAddress(
+        [Buffer.from(BID_BOOK_SEED), auctionPDA.toBuffer(), auctionId.toArrayLike(Buffer, 'le', 8)],
+        auctionProgramID,
+    );
+    return bidBook;
+}
+
+export const getForceCancelRecord = async (auctionPDA: PublicKey, auctionId: anchor.BN): Promise<PublicKey> => {
+    const [forceCancelRecord] = await PublicKey.findProgramAddress(
+        [Buffer.from(FORCE_CANCEL_SEED), auctionPDA.toBuffer(), auctionId.toArrayLike(Buffer, 'le', 8)],
+        auctionProgramID,
+    );
+    return forceCancelRecord;
+}
+
+
+// sha256(amount as u64 LE || salt || bidder), as checked by reveal_bid
+export const getBidCommitment = (amount: anchor.BN, salt: Buffer, bidder: PublicKey): number[] => {
+    return [...createHash('sha256')
+        .update(amount.toArrayLike(Buffer, 'le', 8))
+        .update(salt)
+        .update(bidder.toBuffer())
+        .digest()];
+}
+
+
+// sha256(reserve_price as u64 LE || salt || seller), as checked by claim_auction
+export const getReserveCommitment = (reservePrice: anchor.BN, salt: Buffer, seller: PublicKey): number[] => {
+    return [...createHash('sha256')
+        .update(reservePrice.toArrayLike(Buffer, 'le', 8))
+        .update(salt)
+        .update(seller.toBuffer())
+        .digest()];
+}
+
+
+export const getGlobalState = async (): Promise<GlobalPool | null> => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+    try {
+        let globalState = await auctionProgram.account.globalPool.fetch(globalAuthority);
+        return globalState as unknown as GlobalPool;
+    } catch {
+        return null;
+    }
+}
+
+
+export const getTreasuryState = async (): Promise<Treasury | null> => {
+    try {
+        let treasuryState = await auctionProgram.account.treasury.fetch(await getTreasury());
+        return treasuryState as unknown as Treasury;
+    } catch {
+        return null;
+    }
+}
+
+
+export const getTreasuryInfo = async () => {
+    const treasuryInfo: Treasury = await getTreasuryState();
+    let split = [];
+    for (let i = 0; i < treasuryInfo.recipientCount; i++) {
+        split.push({
+            recipient: treasuryInfo.recipients[i].toBase58(),
+            sharesBps: treasuryInfo.sharesBps[i],
+        });
+    }
+    return {
+        treasury: (await getTreasury()).toBase58(),
+        split,
+    };
+}
+
+
+export const getGlobalInfo = async () => {
+    const globalInfo: GlobalPool = await getGlobalState();
+    return {
+        superAdmin: globalInfo.superAdmin.toBase58(),
+        pendingAdmin: globalInfo.pendingAdmin.toBase58(),
+        feeManager: globalInfo.feeManager.toBase58(),
+        pauser: globalInfo.pauser.toBase58(),
+        curator: globalInfo.curator.toBase58(),
+        feePercent: globalInfo.feePercent.toNumber(),
+        minIncrement: globalInfo.minIncrement.toString(),
+        minIncrementPercent: globalInfo.minIncrementPercent.toNumber(),
+        minDurationAfterBidSecs: globalInfo.minDurationAfterBidSecs.toNumber(),
+        day: globalInfo.day.toNumber(),
+        paused: globalInfo.paused,
+        royaltyFallback: globalInfo.royaltyFallback,
+        strictCollections: globalInfo.strictCollections,
+        minSoftCloseSecs: globalInfo.minSoftCloseSecs.toNumber(),
+        maxSoftCloseSecs: globalInfo.maxSoftCloseSecs.toNumber(),
+    };
+}
+
+
+export const getCollectionState = async (collection: PublicKey): Promise<CollectionConfig | null> => {
+    try {
+        let collectionConfig = await getCollectionConfigPDA(collection);
+        return await auctionProgram.account.collectionConfig.fetch(collectionConfig) as unknown as CollectionConfig;
+    } catch {
+        return null;
+    }
+}
+
+export const getCollectionInfo = async (collection: PublicKey) => {
+    const collectionInfo: CollectionConfig = await getCollectionState(collection);
+    if (!collectionInfo) return null;
+    const policy = collectionInfo.policy;
+    return {
+        collection: collectionInfo.collection.toBase58(),
+        blocked: policy.blocked,
+        feeOverride: policy.feeOverride ? policy.feeOverride.toNumber() : null,
+        minPrice: policy.minPrice.toString(),
+        minDuration: policy.minDuration.toNumber(),
+        maxDuration: policy.maxDuration.toNumber(),
+        quoteMints: policy.quoteMints.map((quoteMint) => quoteMint.toBase58()),
+        incrementTiers: policy.incrementTiers.map((tier) => ({
+            fromBid: tier.fromBid.toString(),
+            increment: tier.increment.toString(),
+            incrementBps: tier.incrementBps,
+        })),
+    };
+}
+
+
+// Lowest bid the auction accepts right now, read through the next_min_bid view
+export const getNextMinBid = async (auctionPDA: PublicKey): Promise<anchor.BN> => {
+    const [globalAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        auctionProgramID,
+    );
+
+    return await auctionProgram.methods
+        .nextMinBid()
+        .accounts({
+            globalAuthority,
+            auction: auctionPDA,
+        })
+        .view();
+}
+
+
+export const getBidBookState = async (auctionPDA: PublicKey): Promise<BidBook | null> => {
+    try {
+        let bidBook = await getBidBookPDA(auctionPDA, (await getAuctionState(auctionPDA)).auctionId);
+        return await auctionProgram.account.bidBook.fetch(bidBook) as unknown as BidBook;
+    } catch {
+        return null;
+    }
+}
+
+export const getBidBookInfo = async (auctionPDA: PublicKey) => {
+    const bidBook: BidBook = await getBidBookState(auctionPDA);
+    if (!bidBook) return null;
+    return {
+        auction: bidBook.auction.toBase58(),
+        payer: bidBook.payer.toBase58(),
+        leader: bidBook.leader.toBase58(),
+        maxBid: bidBook.maxBid.toString(),
+        bidCount: bidBook.bidCount.toNumber(),
+        counterBidCount: bidBook.counterBidCount.toNumber(),
     };
 }
 
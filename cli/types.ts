@@ -3,19 +3,222 @@ import { PublicKey } from '@solana/web3.js';
 
 export const GLOBAL_AUTHORITY_SEED = "global-authority";
 export const AUCTION_SEED = "auction";
+export const AUCTION_VAULT_SEED = "auction-vault";
+export const SEALED_BID_SEED = "sealed-bid";
+export const SEALED_BID_VAULT_SEED = "sealed-bid-vault";
+export const QUOTE_MINT_SEED = "quote-mint";
+export const FORCE_CANCEL_SEED = "force-cancel";
+export const TREASURY_SEED = "treasury";
+export const COMPRESSED_ASSET_SEED = "compressed-asset";
+export const COLLECTION_CONFIG_SEED = "collection-config";
+export const BID_BOOK_SEED = "bid-book";
 
-export const AUCTION_SIZE = 160;
+export const AUCTION_SIZE = 608;
+// Auctions of the first release, which the current program can't read
+export const LEGACY_AUCTION_SIZE = 160;
+
+export const AUCTION_TYPE_ENGLISH = 0;
+export const AUCTION_TYPE_DUTCH = 1;
+export const AUCTION_TYPE_SEALED = 2;
+
+export const SETTLE_FIRST_PRICE = 0;
+export const SETTLE_SECOND_PRICE = 1;
+
+export const ASSET_TYPE_NFT = 0;
+export const ASSET_TYPE_COMPRESSED = 1;
+export const ASSET_TYPE_CORE = 2;
+
+export const DECAY_LINEAR = 0;
+export const DECAY_STEPPED = 1;
+
+export const ROLE_FEE_MANAGER = 0;
+export const ROLE_PAUSER = 1;
+export const ROLE_CURATOR = 2;
+
+export const ROYALTY_FALLBACK_SELLER = 0;
+export const ROYALTY_FALLBACK_TREASURY = 1;
+
+export const TOKEN_STANDARD_NON_FUNGIBLE = 0;
+export const TOKEN_STANDARD_PROGRAMMABLE_NON_FUNGIBLE = 4;
+
+export const CANCEL_REASON_STOLEN = 0;
+export const CANCEL_REASON_COUNTERFEIT = 1;
+export const CANCEL_REASON_SELLER_FRAUD = 2;
+export const CANCEL_REASON_OTHER = 3;
+
+export interface GlobalPool {
+    // 8 + 227
+    superAdmin: PublicKey,              // 32
+    pendingAdmin: PublicKey,            // 32
+    feeManager: PublicKey,              // 32
+    pauser: PublicKey,                  // 32
+    curator: PublicKey,                 // 32
+    feePercent: anchor.BN,              // 8
+    minIncrement: anchor.BN,            // 8
+    minIncrementPercent: anchor.BN,     // 8
+    minDurationAfterBidSecs: anchor.BN, // 8
+    day: anchor.BN,                     // 8
+    paused: boolean,                    // 1
+    royaltyFallback: number,            // 1
+    strictCollections: boolean,         // 1
+    minSoftCloseSecs: anchor.BN,        // 8
+    maxSoftCloseSecs: anchor.BN,        // 8
+    auctionCount: anchor.BN,            // 8
+}
+
+export interface Treasury {
+    // 8 + 172
+    recipients: PublicKey[],    // 32 * 5
+    sharesBps: number[],        // 2 * 5
+    recipientCount: number,     // 1
+    bump: number,               // 1
+}
+
+export interface CollectionPolicy {
+    blocked: boolean,                   // 1
+    feeOverride: anchor.BN | null,      // 9
+    minPrice: anchor.BN,                // 8
+    minDuration: anchor.BN,             // 8
+    maxDuration: anchor.BN,             // 8
+    quoteMints: PublicKey[],            // 4 + 32 * 5
+    incrementTiers: IncrementTier[],    // 4 + 18 * 4
+}
+
+export interface IncrementTier {
+    fromBid: anchor.BN,         // 8
+    increment: anchor.BN,       // 8
+    incrementBps: number,       // 2
+}
+
+export interface CollectionConfig {
+    // 8 + 307
+    collection: PublicKey,      // 32
+    policy: CollectionPolicy,   // 274
+    bump: number,               // 1
+}
+
+export interface ForceCancelRecord {
+    // 8 + 221
+    auction: PublicKey,         // 32
+    auctionId: anchor.BN,       // 8
+    seller: PublicKey,          // 32
+    nftMint: PublicKey,         // 32
+    bidder: PublicKey,          // 32
+    refund: anchor.BN,          // 8
+    admin: PublicKey,           // 32
+    quarantineAta: PublicKey,   // 32
+    timestamp: anchor.BN,       // 8
+    reason: number,             // 1
+    quarantined: boolean,       // 1
+    tokenStandard: number,      // 1
+    assetType: number,          // 1
+    bump: number,               // 1
+}
+
+export interface CompressedAsset {
+    // 8 + 183
+    auction: PublicKey,         // 32
+    seller: PublicKey,          // 32
+    auctionNonce: anchor.BN,    // 8
+    auctionBump: number,        // 1
+    merkleTree: PublicKey,      // 32
+    dataHash: number[],         // 32
+    creatorHash: number[],      // 32
+    nonce: anchor.BN,           // 8
+    index: number,              // 4
+    proofLen: number,           // 1
+    bump: number,               // 1
+}
+
+export interface BidBook {
+    // 8 + 129
+    auction: PublicKey,         // 32
+    auctionId: anchor.BN,       // 8
+    payer: PublicKey,           // 32
+    leader: PublicKey,          // 32
+    maxBid: anchor.BN,          // 8
+    bidCount: anchor.BN,        // 8
+    counterBidCount: anchor.BN, // 8
+    bump: number,               // 1
+}
 
 export interface AuctionPool {
-    // 8 + 152
+    // 8 + 600
     seller: PublicKey,          // 32
     nftMint: PublicKey,         // 32
     nftCollection: PublicKey,   // 32
+    quoteMint: PublicKey,       // 32
 
     bidder: PublicKey,          // 32
     currentBid: anchor.BN,      // 8
 
     startPrice: anchor.BN,      // 8
     endTime: anchor.BN,         // 8
-    
+
+    startTime: anchor.BN,       // 8
+    floorPrice: anchor.BN,      // 8
+    decayInterval: anchor.BN,   // 8
+
+    revealEndTime: anchor.BN,   // 8
+    secondBid: anchor.BN,       // 8
+    commitCount: anchor.BN,     // 8
+
+    buyNowPrice: anchor.BN,     // 8
+    reserveHash: number[],      // 32
+
+    nonce: anchor.BN,           // 8
+    bump: number,               // 1
+    vaultBump: number,          // 1
+    auctionType: number,        // 1
+    decayType: number,          // 1
+    settlementType: number,     // 1
+    frozen: number,             // 1
+    buyNowCutoffBps: number,    // 2
+
+    sellerFeeBasisPoints: number,   // 2
+    creatorCount: number,           // 1
+    creatorShares: number[],        // 5
+    creatorVerified: number[],      // 5
+    tokenStandard: number,          // 1
+    assetType: number,              // 1
+    hasFeeOverride: number,         // 1
+    creators: PublicKey[],          // 32 * 5
+    feeOverride: anchor.BN,         // 8
+
+    originalEndTime: anchor.BN,     // 8
+    softCloseWindow: anchor.BN,     // 8
+    extensionSecs: anchor.BN,       // 8
+    maxExtensionSecs: anchor.BN,    // 8
+
+    tierFromBids: anchor.BN[],          // 8 * 4
+    tierIncrements: anchor.BN[],        // 8 * 4
+    tierIncrementBps: number[],         // 2 * 4
+    incrementTierCount: number,         // 1
+    collectionIncrements: number,       // 1
+    hasBidBook: number,                 // 1
+    padding: number[],                  // 5
+    escrowedBid: anchor.BN,             // 8
+    auctionId: anchor.BN,               // 8
+}
+
+export interface DutchConfig {
+    floorPrice: number,
+    decayType: number,
+    decayInterval: number,
+}
+
+export interface SealedConfig {
+    revealDuration: number,
+    settlementType: number,
+}
+
+export interface SoftCloseConfig {
+    windowSecs: number,
+    extensionSecs: number,
+    maxExtensionSecs: number,
+}
+
+export interface BuyNowConfig {
+    price: number,
+    cutoffBps: number,
 }
@@ -8,8 +8,20 @@ import {
     Keypair,
 } from '@solana/web3.js';
 import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, Token, MintLayout } from "@solana/spl-token";
+import * as anchor from '@project-serum/anchor';
 
 export const METAPLEX = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
+export const AUTH_RULES_PROGRAM = new PublicKey('auth9SigNpDKz4sJJ1DfCTuZrZNSAgh9sFD3rboVmgg');
+export const TOKEN_2022_PROGRAM_ID = new PublicKey('TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb');
+export const BUBBLEGUM_PROGRAM_ID = new PublicKey('BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY');
+export const SPL_ACCOUNT_COMPRESSION_PROGRAM_ID = new PublicKey('cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK');
+export const SPL_NOOP_PROGRAM_ID = new PublicKey('noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV');
+export const MPL_CORE_PROGRAM_ID = new PublicKey('CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d');
+
+// Token-2022 mint extensions start after the padded base mint and the account type byte
+const EXTENSIONS_OFFSET = 166;
+const EXTENSION_TRANSFER_HOOK = 14;
+const EXTENSION_TOKEN_GROUP_MEMBER = 23;
 
 export const getOwnerOfNFT = async (nftMintPk: PublicKey, connection: Connection): Promise<PublicKey> => {
     let tokenAccountPK = await getNFTTokenAccount(nftMintPk, connection);
@@ -75,11 +87,11 @@ export const getNFTTokenAccount = async (nftMintPk: PublicKey, connection: Conne
     return tokenAccount[0].pubkey;
 }
 
-export const getAssociatedTokenAccount = async (ownerPubkey: PublicKey, mintPk: PublicKey): Promise<PublicKey> => {
+export const getAssociatedTokenAccount = async (ownerPubkey: PublicKey, mintPk: PublicKey, tokenProgram: PublicKey = TOKEN_PROGRAM_ID): Promise<PublicKey> => {
     let associatedTokenAccountPubkey = (await PublicKey.findProgramAddress(
         [
             ownerPubkey.toBuffer(),
-            TOKEN_PROGRAM_ID.toBuffer(),
+            tokenProgram.toBuffer(),
             mintPk.toBuffer(), // mint address
         ],
         ASSOCIATED_TOKEN_PROGRAM_ID
@@ -92,10 +104,11 @@ export const getATokenAccountsNeedCreate = async (
     walletAddress: PublicKey,
     owner: PublicKey,
     nfts: PublicKey[],
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
 ) => {
     let instructions = [], destinationAccounts = [];
     for (const mint of nfts) {
-        const destinationPubkey = await getAssociatedTokenAccount(owner, mint);
+        const destinationPubkey = await getAssociatedTokenAccount(owner, mint, tokenProgram);
         const response = await connection.getAccountInfo(destinationPubkey);
         if (!response) {
             const createATAIx = createAssociatedTokenAccountInstruction(
@@ -103,6 +116,7 @@ export const getATokenAccountsNeedCreate = async (
                 walletAddress,
                 owner,
                 mint,
+                tokenProgram,
             );
             instructions.push(createATAIx);
         }
@@ -118,7 +132,8 @@ export const createAssociatedTokenAccountInstruction = (
     associatedTokenAddress: PublicKey,
     payer: PublicKey,
     walletAddress: PublicKey,
-    splTokenMintAddress: PublicKey
+    splTokenMintAddress: PublicKey,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
 ) => {
     const keys = [
         { pubkey: payer, isSigner: true, isWritable: true },
@@ -130,7 +145,7 @@ export const createAssociatedTokenAccountInstruction = (
             isSigner: false,
             isWritable: false,
         },
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: tokenProgram, isSigner: false, isWritable: false },
         {
             pubkey: SYSVAR_RENT_PUBKEY,
             isSigner: false,
@@ -144,6 +159,62 @@ export const createAssociatedTokenAccountInstruction = (
     });
 }
 
+/** Token program owning the mint, Token for the native SOL placeholder */
+export const getMintTokenProgram = async (mint: PublicKey, connection: Connection): Promise<PublicKey> => {
+    if (mint.equals(PublicKey.default)) return TOKEN_PROGRAM_ID;
+    const mintInfo = await connection.getAccountInfo(mint);
+    return mintInfo.owner;
+}
+
+/**
+ * Accounts a Token-2022 transfer hook needs: its program, its validation account
+ * and the fixed extra accounts listed there. Seed-derived extras are not resolved
+ */
+export const getTransferHookAccounts = async (mint: PublicKey, connection: Connection) => {
+    const mintInfo = await connection.getAccountInfo(mint);
+    if (!mintInfo || !mintInfo.owner.equals(TOKEN_2022_PROGRAM_ID)) return [];
+
+    let data = mintInfo.data;
+    let hookProgram: PublicKey = null;
+    for (let offset = EXTENSIONS_OFFSET; offset + 4 <= data.length;) {
+        const type = data.readUInt16LE(offset);
+        const length = data.readUInt16LE(offset + 2);
+        if (type === 0) break;
+        if (type === EXTENSION_TRANSFER_HOOK) {
+            // authority (32) || program id (32)
+            hookProgram = new PublicKey(data.slice(offset + 4 + 32, offset + 4 + 64));
+        }
+        offset += 4 + length;
+    }
+    if (!hookProgram || hookProgram.equals(PublicKey.default)) return [];
+
+    const [validation] = await PublicKey.findProgramAddress(
+        [Buffer.from('extra-account-metas'), mint.toBuffer()],
+        hookProgram,
+    );
+    let accounts = [
+        { pubkey: hookProgram, isSigner: false, isWritable: false },
+        { pubkey: validation, isSigner: false, isWritable: false },
+    ];
+
+    const validationInfo = await connection.getAccountInfo(validation);
+    if (validationInfo) {
+        // discriminator (8) || length (4) || count (4) || 35 byte account metas
+        const metas = validationInfo.data;
+        const count = metas.readUInt32LE(12);
+        for (let i = 0; i < count; i++) {
+            const meta = metas.slice(16 + i * 35, 16 + (i + 1) * 35);
+            if (meta[0] !== 0) continue;
+            accounts.push({
+                pubkey: new PublicKey(meta.slice(1, 33)),
+                isSigner: false,
+                isWritable: meta[34] === 1,
+            });
+        }
+    }
+    return accounts;
+}
+
 /** Get metaplex mint metadata account address */
 export const getMetadata = async (mint: PublicKey): Promise<PublicKey> => {
     return (
@@ -151,6 +222,207 @@ export const getMetadata = async (mint: PublicKey): Promise<PublicKey> => {
     )[0];
 };
 
+/** Get metaplex master edition account address */
+export const getMasterEdition = async (mint: PublicKey): Promise<PublicKey> => {
+    return (
+        await PublicKey.findProgramAddress([Buffer.from('metadata'), METAPLEX.toBuffer(), mint.toBuffer(), Buffer.from('edition')], METAPLEX)
+    )[0];
+};
+
+/** Get metaplex token record account address of a pNFT token account */
+export const getTokenRecord = async (mint: PublicKey, token: PublicKey): Promise<PublicKey> => {
+    return (
+        await PublicKey.findProgramAddress([Buffer.from('metadata'), METAPLEX.toBuffer(), mint.toBuffer(), Buffer.from('token_record'), token.toBuffer()], METAPLEX)
+    )[0];
+};
+
+/** Read the token standard and the rule set of a mint from its metadata */
+export const getProgrammableConfig = async (mint: PublicKey, connection: Connection): Promise<{ programmable: boolean, ruleSet: PublicKey | null }> => {
+    let info = await connection.getAccountInfo(await getMetadata(mint));
+    // A Token-2022 NFT without Token Metadata is never programmable
+    if (!info) return { programmable: false, ruleSet: null };
+    let data = info.data;
+
+    // key, update authority, mint
+    let offset = 1 + 32 + 32;
+    // name, symbol, uri
+    for (let i = 0; i < 3; i++) offset += 4 + data.readUInt32LE(offset);
+    // seller fee basis points
+    offset += 2;
+    // creators
+    if (data[offset++] === 1) offset += 4 + data.readUInt32LE(offset) * 34;
+    // primary sale happened, is mutable
+    offset += 2;
+    // edition nonce
+    if (data[offset++] === 1) offset += 1;
+
+    let tokenStandard = null;
+    if (offset < data.length && data[offset++] === 1) tokenStandard = data[offset++];
+    // collection, uses, collection details
+    if (offset < data.length && data[offset++] === 1) offset += 33;
+    if (offset < data.length && data[offset++] === 1) offset += 17;
+    if (offset < data.length && data[offset++] === 1) offset += 9;
+
+    // programmable config V1 with an optional rule set
+    let ruleSet = null;
+    if (offset < data.length && data[offset++] === 1 && data[offset + 1] === 1) {
+        ruleSet = new PublicKey(data.slice(offset + 2, offset + 34));
+    }
+
+    // TokenStandard::ProgrammableNonFungible
+    return { programmable: tokenStandard === 4, ruleSet };
+}
+
+/**
+ * The token group a Token-2022 mint is a member of, which the program lists
+ * such an NFT under when no Token Metadata account exists. The default pubkey,
+ * for no collection, outside any group and null for a Token mint
+ */
+export const getTokenGroup = async (mint: PublicKey, connection: Connection): Promise<PublicKey | null> => {
+    const mintInfo = await connection.getAccountInfo(mint);
+    if (!mintInfo || !mintInfo.owner.equals(TOKEN_2022_PROGRAM_ID)) return null;
+
+    let data = mintInfo.data;
+    for (let offset = EXTENSIONS_OFFSET; offset + 4 <= data.length;) {
+        const type = data.readUInt16LE(offset);
+        const length = data.readUInt16LE(offset + 2);
+        if (type === 0) break;
+        if (type === EXTENSION_TOKEN_GROUP_MEMBER) {
+            // mint (32) || group (32) || member number (8)
+            return new PublicKey(data.slice(offset + 4 + 32, offset + 4 + 64));
+        }
+        offset += 4 + length;
+    }
+    return PublicKey.default;
+}
+
+/** Read the collection the program lists an NFT under: its verified collection, else its first verified creator */
+export const getNftCollection = async (mint: PublicKey, connection: Connection): Promise<PublicKey | null> => {
+    let info = await connection.getAccountInfo(await getMetadata(mint));
+    if (!info) return getTokenGroup(mint, connection);
+    let data = info.data;
+
+    // key, update authority, mint
+    let offset = 1 + 32 + 32;
+    // name, symbol, uri
+    for (let i = 0; i < 3; i++) offset += 4 + data.readUInt32LE(offset);
+    // seller fee basis points
+    offset += 2;
+    // creators, each an address, verified and share
+    let creator = null;
+    if (data[offset++] === 1) {
+        let count = data.readUInt32LE(offset);
+        offset += 4;
+        for (let i = 0; i < count; i++, offset += 34) {
+            if (!creator && data[offset + 32] === 1) creator = new PublicKey(data.slice(offset, offset + 32));
+        }
+    }
+    // primary sale happened, is mutable
+    offset += 2;
+    // edition nonce, token standard
+    if (data[offset++] === 1) offset += 1;
+    if (offset < data.length && data[offset++] === 1) offset += 1;
+
+    // collection, verified then key
+    if (offset < data.length && data[offset++] === 1 && data[offset] === 1) {
+        return new PublicKey(data.slice(offset + 1, offset + 33));
+    }
+    return creator;
+}
+
+/** Get the asset id of the cNFT minted into the tree with this nonce */
+export const getAssetId = async (merkleTree: PublicKey, nonce: anchor.BN): Promise<PublicKey> => {
+    return (
+        await PublicKey.findProgramAddress([Buffer.from('asset'), merkleTree.toBuffer(), nonce.toArrayLike(Buffer, 'le', 8)], BUBBLEGUM_PROGRAM_ID)
+    )[0];
+};
+
+/** Get the Bubblegum tree config account address of a merkle tree */
+export const getTreeAuthority = async (merkleTree: PublicKey): Promise<PublicKey> => {
+    return (
+        await PublicKey.findProgramAddress([merkleTree.toBuffer()], BUBBLEGUM_PROGRAM_ID)
+    )[0];
+};
+
+// Calls a Digital Asset Standard method of the RPC, which indexes cNFTs
+const dasRequest = async (connection: Connection, method: string, params: any) => {
+    const response = await fetch(connection.rpcEndpoint, {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify({ jsonrpc: '2.0', id: method, method, params }),
+    });
+    const { result, error } = await response.json();
+    if (error) throw new Error(`${method}: ${error.message}`);
+    return result;
+}
+
+/**
+ * Read a cNFT and its merkle proof from a DAS RPC. The metadata is rebuilt in
+ * Bubblegum's MetadataArgs layout, which the program hashes into the leaf
+ */
+export const getCompressedNft = async (assetId: PublicKey, connection: Connection) => {
+    const asset = await dasRequest(connection, 'getAsset', { id: assetId.toBase58() });
+    const assetProof = await dasRequest(connection, 'getAssetProof', { id: assetId.toBase58() });
+
+    const collection = (asset.grouping ?? []).find((group) => group.group_key === 'collection');
+    const uses = asset.uses
+        ? {
+            useMethod: { [asset.uses.use_method.charAt(0).toLowerCase() + asset.uses.use_method.slice(1)]: {} },
+            remaining: new anchor.BN(asset.uses.remaining),
+            total: new anchor.BN(asset.uses.total),
+        }
+        : null;
+    const metadata = {
+        name: asset.content.metadata.name,
+        symbol: asset.content.metadata.symbol ?? '',
+        uri: asset.content.json_uri,
+        sellerFeeBasisPoints: asset.royalty.basis_points,
+        primarySaleHappened: asset.royalty.primary_sale_happened,
+        isMutable: asset.mutable,
+        editionNonce: asset.supply?.edition_nonce ?? null,
+        tokenStandard: { nonFungible: {} },
+        collection: collection ? { verified: collection.verified ?? true, key: new PublicKey(collection.group_value) } : null,
+        uses,
+        tokenProgramVersion: { original: {} },
+        creators: asset.creators.map((creator) => ({
+            address: new PublicKey(creator.address),
+            verified: creator.verified,
+            share: creator.share,
+        })),
+    };
+
+    return {
+        metadata,
+        merkleTree: new PublicKey(asset.compression.tree),
+        leafNonce: new anchor.BN(asset.compression.leaf_id),
+        leafIndex: assetProof.node_index - 2 ** assetProof.proof.length,
+        owner: new PublicKey(asset.ownership.owner),
+        delegate: asset.ownership.delegate ? new PublicKey(asset.ownership.delegate) : null,
+        proof: assetProof.proof.map((node: string) => new PublicKey(node)),
+    };
+}
+
+/** Read the collection of a Core asset, null when it belongs to none */
+export const getCoreCollection = async (asset: PublicKey, connection: Connection): Promise<PublicKey | null> => {
+    const data = (await connection.getAccountInfo(asset)).data;
+    // key (1) and owner (32), then the update authority, UpdateAuthority::Collection is 2
+    if (data[33] !== 2) return null;
+    return new PublicKey(data.slice(34, 66));
+}
+
+/** Read the canopy depth of a concurrent merkle tree, the proof nodes it keeps on chain */
+export const getCanopyDepth = async (merkleTree: PublicKey, connection: Connection): Promise<number> => {
+    const data = (await connection.getAccountInfo(merkleTree)).data;
+    const maxBufferSize = data.readUInt32LE(2);
+    const maxDepth = data.readUInt32LE(6);
+
+    // header (56), sequence number, active index and buffer size (24),
+    // the change logs and the rightmost proof
+    const treeSize = 56 + 24 + maxBufferSize * (40 + 32 * maxDepth) + (40 + 32 * maxDepth);
+    const canopyNodes = (data.length - treeSize) / 32;
+    return canopyNodes > 0 ? Math.log2(canopyNodes + 2) - 1 : 0;
+}
+
 export const airdropSOL = async (address: PublicKey, amount: number, connection: Connection) => {
     try {
         const txId = await connection.requestAirdrop(address, amount);
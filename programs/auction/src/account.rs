@@ -1,10 +1,319 @@
+// derive(Pod) checks the zero-copy AuctionPool for padding through a helper
+// struct that is never read, which newer compilers report as dead code
+#![allow(dead_code)]
+
 use anchor_lang::prelude::*;
+use mpl_token_metadata::state::{Metadata, TokenStandard, MAX_CREATOR_LIMIT};
+
+use crate::bubblegum::MetadataArgs;
+use crate::mpl_core::Royalties;
+use crate::error::AuctionError;
+use crate::utils::{
+    ASSET_TYPE_COMPRESSED, ASSET_TYPE_CORE, DAY, DECAY_STEPPED, FEE_PERCENT,
+    MAX_COLLECTION_QUOTE_MINTS, MAX_FEE_PERCENT, MAX_FEE_RECIPIENTS, MAX_INCREMENT_TIERS,
+    MAX_SOFT_CLOSE_SECS, MIN_DURATION_AFTER_BID_SECS, MIN_INCREMENT, MIN_INCREMENT_PERCENT,
+    MIN_SOFT_CLOSE_SECS, PERMYRIAD, ROLE_CURATOR, ROLE_FEE_MANAGER, ROLE_PAUSER, VAULT_WALLET,
+};
 
 #[account]
 #[derive(Default)]
 pub struct GlobalPool {
-    // 8 + 32
-    pub super_admin: Pubkey,     // 32
+    // 8 + 227
+    pub super_admin: Pubkey,                    // 32
+    pub pending_admin: Pubkey,                  // 32
+    pub fee_manager: Pubkey,                    // 32
+    pub pauser: Pubkey,                         // 32
+    pub curator: Pubkey,                        // 32
+    pub fee_percent: u64,                       // 8
+    pub min_increment: u64,                     // 8
+    pub min_increment_percent: u64,             // 8
+    pub min_duration_after_bid_secs: u64,       // 8
+    pub day: u64,                               // 8
+    pub paused: bool,                           // 1
+    pub royalty_fallback: u8,                   // 1
+    // Only collections in the registry can be listed
+    pub strict_collections: bool,               // 1
+    // Bounds of the soft-close window and extension sellers choose
+    pub min_soft_close_secs: u64,               // 8
+    pub max_soft_close_secs: u64,               // 8
+    // Auctions created so far, which numbers them
+    pub auction_count: u64,                     // 8
+}
+
+impl GlobalPool {
+    /// The config of a newly initialized program
+    pub fn new(super_admin: Pubkey) -> Self {
+        GlobalPool {
+            super_admin,
+            fee_percent: FEE_PERCENT,
+            min_increment: MIN_INCREMENT,
+            min_increment_percent: MIN_INCREMENT_PERCENT,
+            min_duration_after_bid_secs: MIN_DURATION_AFTER_BID_SECS,
+            day: DAY,
+            min_soft_close_secs: MIN_SOFT_CLOSE_SECS,
+            max_soft_close_secs: MAX_SOFT_CLOSE_SECS,
+            ..GlobalPool::default()
+        }
+    }
+
+    /// Numbers a new auction. Unlike its PDA, the id isn't reused by a relisting
+    pub fn next_auction_id(&mut self) -> u64 {
+        self.auction_count += 1;
+        self.auction_count
+    }
+
+    /// The super admin holds every role
+    pub fn has_role(&self, key: &Pubkey, role: u8) -> bool {
+        if *key == self.super_admin {
+            return true;
+        }
+
+        let operator = match role {
+            ROLE_FEE_MANAGER => self.fee_manager,
+            ROLE_PAUSER => self.pauser,
+            ROLE_CURATOR => self.curator,
+            _ => return false,
+        };
+        operator != Pubkey::default() && *key == operator
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct Treasury {
+    // 8 + 172
+    pub recipients: [Pubkey; MAX_FEE_RECIPIENTS],   // 32 * 5
+    pub shares_bps: [u16; MAX_FEE_RECIPIENTS],      // 2 * 5
+    pub recipient_count: u8,                        // 1
+    pub bump: u8,                                   // 1
+}
+
+impl Treasury {
+    /// All fees go to the vault wallet until the fee manager sets a split
+    pub fn new(bump: u8) -> Self {
+        let mut treasury = Treasury {
+            recipient_count: 1,
+            bump,
+            ..Treasury::default()
+        };
+        treasury.recipients[0] = VAULT_WALLET.parse::<Pubkey>().unwrap();
+        treasury.shares_bps[0] = PERMYRIAD as u16;
+        treasury
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct QuoteMint {
+    // 8 + 33
+    pub mint: Pubkey,           // 32
+    pub bump: u8,               // 1
+}
+
+// A price band of a bid increment schedule. A bid must beat the current one
+// by the absolute increment or by increment_bps of it, whichever is smaller,
+// a zero value leaving that minimum out
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct IncrementTier {
+    // The tier applies once the current bid reaches this
+    pub from_bid: u64,
+    pub increment: u64,
+    pub increment_bps: u16,
+}
+
+impl IncrementTier {
+    /// Checks a schedule is 1~4 tiers in ascending bands, starting from 0
+    pub fn validate_schedule(tiers: &[IncrementTier]) -> Result<()> {
+        if tiers.is_empty()
+            || tiers.len() > MAX_INCREMENT_TIERS
+            || tiers[0].from_bid != 0
+            || tiers.windows(2).any(|pair| pair[0].from_bid >= pair[1].from_bid)
+            || tiers.iter().any(|tier| {
+                (tier.increment == 0 && tier.increment_bps == 0)
+                    || tier.increment_bps as u64 > PERMYRIAD
+            })
+        {
+            return Err(error!(AuctionError::InvalidIncrementSchedule));
+        }
+
+        Ok(())
+    }
+
+    /// Smallest raise over current_bid this tier accepts, at least 1
+    pub fn min_raise(&self, current_bid: u64) -> Result<u64> {
+        let relative = (current_bid as u128)
+            .checked_mul(self.increment_bps as u128)
+            .ok_or(AuctionError::MathOverflow)?
+            / PERMYRIAD as u128;
+        let raise = match (self.increment, self.increment_bps) {
+            (0, _) => relative,
+            (increment, 0) => increment as u128,
+            (increment, _) => relative.min(increment as u128),
+        };
+
+        u64::try_from(raise.max(1)).map_err(|_| error!(AuctionError::MathOverflow))
+    }
+}
+
+// Listing rules of a collection, set by the curator
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct CollectionPolicy {
+    // 8 + 274
+    pub blocked: bool,                  // 1
+    // Fee percent charged instead of the global one
+    pub fee_override: Option<u64>,      // 9
+    // Lowest start price, or floor price of a Dutch auction, 0 for none
+    pub min_price: u64,                 // 8
+    // Duration bounds within the global 1~14 days, 0 for none
+    pub min_duration: u64,              // 8
+    pub max_duration: u64,              // 8
+    // Quote mints listings may use, the default pubkey for native SOL. Empty allows any
+    pub quote_mints: Vec<Pubkey>,       // 4 + 32 * 5
+    // Increment schedule of its English auctions, empty for the global one
+    pub increment_tiers: Vec<IncrementTier>,    // 4 + 18 * 4
+}
+
+impl CollectionPolicy {
+    pub fn validate(&self) -> Result<()> {
+        if self.fee_override.unwrap_or(0) > MAX_FEE_PERCENT
+            || (self.max_duration != 0 && self.min_duration > self.max_duration)
+            || self.quote_mints.len() > MAX_COLLECTION_QUOTE_MINTS
+        {
+            return Err(error!(AuctionError::InvalidCollectionPolicy));
+        }
+        if !self.increment_tiers.is_empty() {
+            IncrementTier::validate_schedule(&self.increment_tiers)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks a new listing of the collection against the policy
+    pub fn check_listing(&self, min_price: u64, duration: u64, quote_mint: &Pubkey) -> Result<()> {
+        if self.blocked {
+            return Err(error!(AuctionError::CollectionBlocked));
+        }
+        if min_price < self.min_price {
+            return Err(error!(AuctionError::PriceBelowCollectionMin));
+        }
+        if duration < self.min_duration || (self.max_duration != 0 && duration > self.max_duration) {
+            return Err(error!(AuctionError::DurationOutsideCollectionRange));
+        }
+        if !self.quote_mints.is_empty() && !self.quote_mints.contains(quote_mint) {
+            return Err(error!(AuctionError::QuoteMintNotAllowed));
+        }
+
+        Ok(())
+    }
+}
+
+// Anti-sniping rules of an English auction, chosen by the seller
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct SoftClose {
+    // Bids this close to the end extend the auction
+    pub window_secs: u64,
+    // How far past a late bid the auction then ends
+    pub extension_secs: u64,
+    // Cap on the total extension past the original end, 0 for none
+    pub max_extension_secs: u64,
+}
+
+// Buy-now, hidden reserve and anti-sniping terms of an English auction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct EnglishTerms {
+    // Instant-sale price, None for no buy-now
+    pub buy_now_price: Option<u64>,
+    // Buy-now stays open until bids reach this fraction of its price, in bps
+    pub buy_now_cutoff_bps: u16,
+    // sha256(reserve_price as u64 LE || salt || seller) of a hidden reserve
+    pub reserve_hash: Option<[u8; 32]>,
+    // The global default applies when None
+    pub soft_close: Option<SoftClose>,
+}
+
+// The registry entry of a collection
+#[account]
+#[derive(Default)]
+pub struct CollectionConfig {
+    // 8 + 307
+    pub collection: Pubkey,         // 32
+    pub policy: CollectionPolicy,   // 274
+    pub bump: u8,                   // 1
+}
+
+#[account]
+#[derive(Default)]
+pub struct ForceCancelRecord {
+    // 8 + 221
+    pub auction: Pubkey,        // 32
+    pub auction_id: u64,        // 8
+    pub seller: Pubkey,         // 32
+    pub nft_mint: Pubkey,       // 32
+    pub bidder: Pubkey,         // 32
+    pub refund: u64,            // 8
+    pub admin: Pubkey,          // 32
+    pub quarantine_ata: Pubkey, // 32
+    pub timestamp: i64,         // 8
+    pub reason: u8,             // 1
+    pub quarantined: bool,      // 1
+    pub token_standard: u8,     // 1
+    pub asset_type: u8,         // 1
+    pub bump: u8,               // 1
+}
+
+// The leaf of an escrowed cNFT, as needed to transfer it out, and the seeds
+// of the auction PDA owning it, which outlive a force-cancelled auction
+#[account]
+#[derive(Default)]
+pub struct CompressedAsset {
+    // 8 + 183
+    pub auction: Pubkey,        // 32
+    pub seller: Pubkey,         // 32
+    pub auction_nonce: u64,     // 8
+    pub auction_bump: u8,       // 1
+    pub merkle_tree: Pubkey,    // 32
+    pub data_hash: [u8; 32],    // 32
+    pub creator_hash: [u8; 32], // 32
+    pub nonce: u64,             // 8
+    pub index: u32,             // 4
+    // Number of proof nodes ending the remaining accounts
+    pub proof_len: u8,          // 1
+    pub bump: u8,               // 1
+}
+
+#[account]
+#[derive(Default)]
+pub struct SealedBid {
+    // 8 + 131
+    pub auction: Pubkey,        // 32
+    pub auction_id: u64,        // 8
+    pub bidder: Pubkey,         // 32
+    pub commitment: [u8; 32],   // 32
+    pub deposit: u64,           // 8
+    pub amount: u64,            // 8
+    pub reveal_end_time: u64,   // 8
+    pub revealed: bool,         // 1
+    pub bump: u8,               // 1
+    pub vault_bump: u8,         // 1
+}
+
+// Proxy bidding state of an English auction. The leader's whole ceiling is
+// escrowed, while the auction only records what it takes to lead
+#[account]
+#[derive(Default)]
+pub struct BidBook {
+    // 8 + 129
+    pub auction: Pubkey,        // 32
+    pub auction_id: u64,        // 8
+    // Opened the book and gets its rent back
+    pub payer: Pubkey,          // 32
+    pub leader: Pubkey,         // 32
+    // Ceiling of the leader's proxy
+    pub max_bid: u64,           // 8
+    pub bid_count: u64,         // 8
+    // Bids the leading proxy answered on its own
+    pub counter_bid_count: u64, // 8
+    pub bump: u8,               // 1
 }
 
 #[account(zero_copy)]
@@ -13,6 +322,7 @@ pub struct AuctionPool {
     pub seller: Pubkey,         // 32
     pub nft_mint: Pubkey,       // 32
     pub nft_collection: Pubkey, // 32
+    pub quote_mint: Pubkey,     // 32
 
     pub bidder: Pubkey,         // 32
     pub current_bid: u64,       // 8
@@ -20,6 +330,62 @@ pub struct AuctionPool {
     pub start_price: u64,       // 8
     pub end_time: u64,          // 8
 
+    pub start_time: u64,        // 8
+    pub floor_price: u64,       // 8
+    pub decay_interval: u64,    // 8
+
+    pub reveal_end_time: u64,   // 8
+    pub second_bid: u64,        // 8
+    pub commit_count: u64,      // 8
+
+    pub buy_now_price: u64,     // 8
+    pub reserve_hash: [u8; 32], // 32
+
+    pub nonce: u64,             // 8
+    pub bump: u8,               // 1
+    pub vault_bump: u8,         // 1
+    pub auction_type: u8,       // 1
+    pub decay_type: u8,         // 1
+    pub settlement_type: u8,    // 1
+    pub frozen: u8,             // 1
+    pub buy_now_cutoff_bps: u16, // 2
+
+    // Royalties as listed, so a metadata update can't change them mid-auction
+    pub seller_fee_basis_points: u16,               // 2
+    pub creator_count: u8,                          // 1
+    pub creator_shares: [u8; MAX_CREATOR_LIMIT],    // 5
+    pub creator_verified: [u8; MAX_CREATOR_LIMIT],  // 5
+    // Token Metadata's TokenStandard of the NFT, fixing how it's transferred
+    pub token_standard: u8,                         // 1
+    // An SPL NFT, a compressed NFT or a Core asset
+    pub asset_type: u8,                             // 1
+    // Whether fee_override replaces the global fee percent
+    pub has_fee_override: u8,                       // 1
+    pub creators: [Pubkey; MAX_CREATOR_LIMIT],      // 160
+    // The collection's fee percent at listing
+    pub fee_override: u64,                          // 8
+
+    // end_time as listed, before any extension
+    pub original_end_time: u64,                     // 8
+    pub soft_close_window: u64,                     // 8
+    pub extension_secs: u64,                        // 8
+    pub max_extension_secs: u64,                    // 8
+
+    // Bid increment schedule, tier i applying from a current bid of tier_from_bids[i].
+    // Without tiers the global min_increment and min_increment_percent apply
+    pub tier_from_bids: [u64; MAX_INCREMENT_TIERS],     // 32
+    pub tier_increments: [u64; MAX_INCREMENT_TIERS],    // 32
+    pub tier_increment_bps: [u16; MAX_INCREMENT_TIERS], // 8
+    pub increment_tier_count: u8,                       // 1
+    // Whether the schedule is the collection's, which the seller can't replace
+    pub collection_increments: u8,                      // 1
+    // Whether bids must go through the auction's BidBook
+    pub has_bid_book: u8,                               // 1
+    pub _padding: [u8; 5],                              // 5
+    // What the leader holds in escrow, above current_bid for a proxy
+    pub escrowed_bid: u64,                              // 8
+    // Tells apart auctions reusing the same PDA
+    pub auction_id: u64,                                // 8
 }
 
 impl Default for AuctionPool {
@@ -30,12 +396,464 @@ impl Default for AuctionPool {
 
             nft_mint: Pubkey::default(),
             nft_collection: Pubkey::default(),
+            quote_mint: Pubkey::default(),
 
             bidder: Pubkey::default(),
             current_bid: 0,
 
             start_price: 0,
             end_time: 0,
+
+            start_time: 0,
+            floor_price: 0,
+            decay_interval: 0,
+
+            reveal_end_time: 0,
+            second_bid: 0,
+            commit_count: 0,
+
+            buy_now_price: 0,
+            reserve_hash: [0; 32],
+
+            nonce: 0,
+            bump: 0,
+            vault_bump: 0,
+            auction_type: 0,
+            decay_type: 0,
+            settlement_type: 0,
+            frozen: 0,
+            buy_now_cutoff_bps: 0,
+
+            seller_fee_basis_points: 0,
+            creator_count: 0,
+            creator_shares: [0; MAX_CREATOR_LIMIT],
+            creator_verified: [0; MAX_CREATOR_LIMIT],
+            token_standard: 0,
+            asset_type: 0,
+            has_fee_override: 0,
+            creators: [Pubkey::default(); MAX_CREATOR_LIMIT],
+            fee_override: 0,
+
+            original_end_time: 0,
+            soft_close_window: 0,
+            extension_secs: 0,
+            max_extension_secs: 0,
+
+            tier_from_bids: [0; MAX_INCREMENT_TIERS],
+            tier_increments: [0; MAX_INCREMENT_TIERS],
+            tier_increment_bps: [0; MAX_INCREMENT_TIERS],
+            increment_tier_count: 0,
+            collection_increments: 0,
+            has_bid_book: 0,
+            _padding: [0; 5],
+            escrowed_bid: 0,
+            auction_id: 0,
         }
     }
-}
\ No newline at end of file
+}
+
+impl AuctionPool {
+    /// Native SOL auctions escrow bids as lamports in the auction PDA
+    pub fn is_native(&self) -> bool {
+        self.quote_mint == Pubkey::default()
+    }
+
+    /// Compressed NFTs are leaves of a merkle tree, moved through Bubblegum
+    pub fn is_compressed(&self) -> bool {
+        self.asset_type == ASSET_TYPE_COMPRESSED
+    }
+
+    /// Core assets have no mint or ATA and move through Metaplex Core
+    pub fn is_core(&self) -> bool {
+        self.asset_type == ASSET_TYPE_CORE
+    }
+
+    /// Programmable NFTs only move through Token Metadata's Transfer instruction
+    pub fn is_programmable(&self) -> bool {
+        self.token_standard == TokenStandard::ProgrammableNonFungible as u8
+    }
+
+    /// Sets the listed end time, which extensions are capped against
+    pub fn set_end_time(&mut self, end_time: u64) {
+        self.end_time = end_time;
+        self.original_end_time = end_time;
+    }
+
+    pub fn set_soft_close(&mut self, soft_close: &SoftClose) {
+        self.soft_close_window = soft_close.window_secs;
+        self.extension_secs = soft_close.extension_secs;
+        self.max_extension_secs = soft_close.max_extension_secs;
+    }
+
+    /// Extends the auction for a bid at `now` inside the soft-close window,
+    /// never past original_end_time + max_extension_secs when capped
+    pub fn extend_for_bid(&mut self, now: u64) {
+        if now.saturating_add(self.soft_close_window) <= self.end_time {
+            return;
+        }
+        let mut end_time = now.saturating_add(self.extension_secs);
+        if self.max_extension_secs != 0 {
+            end_time = end_time.min(
+                self.original_end_time
+                    .saturating_add(self.max_extension_secs),
+            );
+        }
+        self.end_time = self.end_time.max(end_time);
+    }
+
+    /// Records the collection's fee override and increment schedule, kept
+    /// for the whole auction
+    pub fn snapshot_policy(&mut self, policy: Option<&CollectionPolicy>) {
+        let fee_override = policy.and_then(|policy| policy.fee_override);
+        self.has_fee_override = fee_override.is_some() as u8;
+        self.fee_override = fee_override.unwrap_or_default();
+
+        if let Some(policy) = policy.filter(|policy| !policy.increment_tiers.is_empty()) {
+            self.set_increment_tiers(&policy.increment_tiers);
+            self.collection_increments = 1;
+        }
+    }
+
+    /// Replaces the increment schedule, an empty one falling back to the global
+    pub fn set_increment_tiers(&mut self, tiers: &[IncrementTier]) {
+        self.tier_from_bids = [0; MAX_INCREMENT_TIERS];
+        self.tier_increments = [0; MAX_INCREMENT_TIERS];
+        self.tier_increment_bps = [0; MAX_INCREMENT_TIERS];
+        for (i, tier) in tiers.iter().take(MAX_INCREMENT_TIERS).enumerate() {
+            self.tier_from_bids[i] = tier.from_bid;
+            self.tier_increments[i] = tier.increment;
+            self.tier_increment_bps[i] = tier.increment_bps;
+        }
+        self.increment_tier_count = tiers.len().min(MAX_INCREMENT_TIERS) as u8;
+    }
+
+    /// The tier of a bid, the global increments without a schedule
+    pub fn increment_tier(&self, global_authority: &GlobalPool, bid: u64) -> IncrementTier {
+        let count = self.increment_tier_count as usize;
+        match (0..count).rev().find(|i| self.tier_from_bids[*i] <= bid) {
+            Some(i) => IncrementTier {
+                from_bid: self.tier_from_bids[i],
+                increment: self.tier_increments[i],
+                increment_bps: self.tier_increment_bps[i],
+            },
+            None => IncrementTier {
+                from_bid: 0,
+                increment: global_authority.min_increment,
+                increment_bps: (global_authority.min_increment_percent * 100) as u16,
+            },
+        }
+    }
+
+    /// Lowest bid place_bid accepts: the start price for the first bid, then
+    /// the current bid raised by its tier's increment
+    pub fn min_next_bid(&self, global_authority: &GlobalPool) -> Result<u64> {
+        if self.current_bid == 0 {
+            return Ok(self.start_price);
+        }
+
+        self.min_bid_over(global_authority, self.current_bid)
+    }
+
+    /// Lowest bid that beats bid by its tier's increment
+    pub fn min_bid_over(&self, global_authority: &GlobalPool, bid: u64) -> Result<u64> {
+        let raise = self.increment_tier(global_authority, bid).min_raise(bid)?;
+
+        bid.checked_add(raise)
+            .ok_or(error!(AuctionError::MathOverflow))
+    }
+
+    /// Escrow the leader gets back on winning, left over by their proxy
+    pub fn unused_escrow(&self) -> u64 {
+        self.escrowed_bid.saturating_sub(self.current_bid)
+    }
+
+    /// Fee percent the sale pays, the collection's override or the global one
+    pub fn fee_percent(&self, global_authority: &GlobalPool) -> u64 {
+        if self.has_fee_override != 0 {
+            self.fee_override
+        } else {
+            global_authority.fee_percent
+        }
+    }
+
+    /// Records the royalties settlement pays out.
+    /// A Token-2022 NFT listed without Token Metadata has none
+    pub fn snapshot_royalties(&mut self, metadata: Option<&Metadata>) {
+        let Some(metadata) = metadata else {
+            return;
+        };
+        self.seller_fee_basis_points = metadata.data.seller_fee_basis_points;
+        if let Some(creators) = &metadata.data.creators {
+            for (i, creator) in creators.iter().take(MAX_CREATOR_LIMIT).enumerate() {
+                self.creators[i] = creator.address;
+                self.creator_shares[i] = creator.share;
+                self.creator_verified[i] = creator.verified as u8;
+            }
+            self.creator_count = creators.len().min(MAX_CREATOR_LIMIT) as u8;
+        }
+    }
+
+    /// Records the token standard, legacy NFTs without one being NonFungible
+    pub fn snapshot_token_standard(&mut self, metadata: Option<&Metadata>) {
+        self.token_standard = metadata
+            .and_then(|metadata| metadata.token_standard)
+            .unwrap_or(TokenStandard::NonFungible) as u8;
+    }
+
+    /// Records the royalties of a cNFT from the metadata hashed into its leaf
+    pub fn snapshot_compressed(&mut self, metadata: &MetadataArgs) {
+        self.seller_fee_basis_points = metadata.seller_fee_basis_points;
+        for (i, creator) in metadata.creators.iter().take(MAX_CREATOR_LIMIT).enumerate() {
+            self.creators[i] = creator.address;
+            self.creator_shares[i] = creator.share;
+            self.creator_verified[i] = creator.verified as u8;
+        }
+        self.creator_count = metadata.creators.len().min(MAX_CREATOR_LIMIT) as u8;
+        self.token_standard = TokenStandard::NonFungible as u8;
+        self.asset_type = ASSET_TYPE_COMPRESSED;
+    }
+
+    /// Records the royalty plugin of a Core asset, or of its collection.
+    /// Its creators are set by the update authority, so they count as verified
+    pub fn snapshot_core(&mut self, royalties: Option<&Royalties>) {
+        if let Some(royalties) = royalties {
+            self.seller_fee_basis_points = royalties.basis_points;
+            for (i, creator) in royalties.creators.iter().take(MAX_CREATOR_LIMIT).enumerate() {
+                self.creators[i] = creator.address;
+                self.creator_shares[i] = creator.percentage;
+                self.creator_verified[i] = 1;
+            }
+            self.creator_count = royalties.creators.len().min(MAX_CREATOR_LIMIT) as u8;
+        }
+        self.token_standard = TokenStandard::NonFungible as u8;
+        self.asset_type = ASSET_TYPE_CORE;
+    }
+
+    /// Price of a Dutch auction at `now`, decaying from start_price to floor_price
+    pub fn current_price(&self, now: u64) -> u64 {
+        let duration = self.end_time - self.start_time;
+        let mut elapsed = now.saturating_sub(self.start_time).min(duration);
+        // The last step is cut short, so the price reaches the floor at end_time
+        if self.decay_type == DECAY_STEPPED && elapsed < duration {
+            elapsed -= elapsed % self.decay_interval;
+        }
+
+        let decay = (self.start_price - self.floor_price) as u128 * elapsed as u128
+            / duration as u128;
+        self.start_price - decay as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::DECAY_LINEAR;
+
+    fn dutch(decay_type: u8, decay_interval: u64) -> AuctionPool {
+        AuctionPool {
+            start_time: 1_000,
+            end_time: 1_100,
+            start_price: 1_000,
+            floor_price: 100,
+            decay_type,
+            decay_interval,
+            ..AuctionPool::default()
+        }
+    }
+
+    #[test]
+    fn current_price_decays_linearly() {
+        let auction = dutch(DECAY_LINEAR, 0);
+        for (now, price) in [
+            (900, 1_000),
+            (1_000, 1_000),
+            (1_001, 991),
+            (1_050, 550),
+            (1_099, 109),
+            (1_100, 100),
+            (5_000, 100),
+        ] {
+            assert_eq!(auction.current_price(now), price, "now = {}", now);
+        }
+    }
+
+    #[test]
+    fn current_price_decays_in_steps() {
+        let auction = dutch(DECAY_STEPPED, 30);
+        for (now, price) in [
+            (1_000, 1_000),
+            (1_029, 1_000),
+            (1_030, 730),
+            (1_059, 730),
+            (1_060, 460),
+            (1_090, 190),
+            (1_099, 190),
+            // The 10 seconds left of the last step still end at the floor
+            (1_100, 100),
+            (1_200, 100),
+        ] {
+            assert_eq!(auction.current_price(now), price, "now = {}", now);
+        }
+    }
+
+    #[test]
+    fn current_price_with_one_step_drops_at_end() {
+        let auction = dutch(DECAY_STEPPED, 100);
+        assert_eq!(auction.current_price(1_099), 1_000);
+        assert_eq!(auction.current_price(1_100), 100);
+    }
+
+    fn soft_close(window_secs: u64, extension_secs: u64, max_extension_secs: u64) -> AuctionPool {
+        let mut auction = AuctionPool::default();
+        auction.set_end_time(1_000);
+        auction.set_soft_close(&SoftClose {
+            window_secs,
+            extension_secs,
+            max_extension_secs,
+        });
+        auction
+    }
+
+    #[test]
+    fn extend_for_bid_within_window() {
+        for (window, extension, cap, now, end_time) in [
+            // Outside the window nothing changes
+            (60, 120, 0, 900, 1_000),
+            (60, 120, 0, 940, 1_000),
+            // Inside it the auction ends extension_secs after the bid
+            (60, 120, 0, 941, 1_061),
+            (60, 120, 0, 1_000, 1_120),
+            // An extension never shortens the auction
+            (600, 30, 0, 950, 1_000),
+            // The cap counts from the listed end_time
+            (60, 120, 100, 990, 1_100),
+            (60, 120, 100, 950, 1_070),
+            // Large values saturate instead of overflowing
+            (u64::MAX, u64::MAX, u64::MAX, 990, u64::MAX),
+            (60, u64::MAX, 500, 990, 1_500),
+        ] {
+            let mut auction = soft_close(window, extension, cap);
+            auction.extend_for_bid(now);
+            assert_eq!(
+                auction.end_time, end_time,
+                "window = {}, extension = {}, cap = {}, now = {}",
+                window, extension, cap, now
+            );
+        }
+    }
+
+    #[test]
+    fn extend_for_bid_keeps_extending_up_to_cap() {
+        let mut auction = soft_close(60, 60, 150);
+        for (now, end_time) in [(950, 1_010), (1_000, 1_060), (1_050, 1_110), (1_100, 1_150), (1_149, 1_150)] {
+            auction.extend_for_bid(now);
+            assert_eq!(auction.end_time, end_time, "now = {}", now);
+        }
+        assert_eq!(auction.original_end_time, 1_000);
+    }
+
+    fn tier(from_bid: u64, increment: u64, increment_bps: u16) -> IncrementTier {
+        IncrementTier {
+            from_bid,
+            increment,
+            increment_bps,
+        }
+    }
+
+    #[test]
+    fn min_raise_takes_the_smaller_minimum() {
+        for (increment, increment_bps, current_bid, raise) in [
+            // Absolute only
+            (10, 0, 1_000, 10),
+            // Basis points only, rounded down
+            (0, 500, 1_000, 50),
+            (0, 500, 1_019, 50),
+            // Both: whichever is smaller
+            (10, 500, 1_000, 10),
+            (100, 500, 1_000, 50),
+            (50, 500, 1_000, 50),
+            // Never below 1
+            (0, 1, 100, 1),
+            (0, 500, 0, 1),
+            // The whole u64 range fits
+            (0, 10_000, u64::MAX, u64::MAX),
+        ] {
+            assert_eq!(
+                tier(0, increment, increment_bps).min_raise(current_bid).unwrap(),
+                raise,
+                "increment = {}, bps = {}, current_bid = {}",
+                increment, increment_bps, current_bid
+            );
+        }
+    }
+
+    #[test]
+    fn validate_schedule_checks_bands() {
+        for (tiers, valid) in [
+            (vec![], false),
+            (vec![tier(0, 10, 0)], true),
+            (vec![tier(0, 0, 500)], true),
+            (vec![tier(1, 10, 0)], false),
+            (vec![tier(0, 0, 0)], false),
+            (vec![tier(0, 0, 10_001)], false),
+            (vec![tier(0, 0, 10_000)], true),
+            (vec![tier(0, 10, 0), tier(100, 0, 500)], true),
+            (vec![tier(0, 10, 0), tier(100, 0, 500), tier(100, 20, 0)], false),
+            (vec![tier(0, 10, 0), tier(100, 0, 500), tier(50, 20, 0)], false),
+            (vec![tier(0, 1, 0), tier(1, 1, 0), tier(2, 1, 0), tier(3, 1, 0)], true),
+            (vec![tier(0, 1, 0), tier(1, 1, 0), tier(2, 1, 0), tier(3, 1, 0), tier(4, 1, 0)], false),
+        ] {
+            assert_eq!(
+                IncrementTier::validate_schedule(&tiers).is_ok(),
+                valid,
+                "{} tiers from {:?}",
+                tiers.len(),
+                tiers.iter().map(|tier| tier.from_bid).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn min_next_bid_follows_bands() {
+        let global = GlobalPool {
+            min_increment: 10,
+            min_increment_percent: 5,
+            ..GlobalPool::default()
+        };
+        let mut auction = AuctionPool {
+            start_price: 500,
+            ..AuctionPool::default()
+        };
+
+        // The first bid only has to meet the start price
+        assert_eq!(auction.min_next_bid(&global).unwrap(), 500);
+
+        // Without a schedule: 10 or 5%, whichever is smaller
+        for (current_bid, min_bid) in [(100, 105), (200, 210), (1_000, 1_010)] {
+            auction.current_bid = current_bid;
+            assert_eq!(auction.min_next_bid(&global).unwrap(), min_bid, "current_bid = {}", current_bid);
+        }
+
+        auction.set_increment_tiers(&[tier(0, 5, 0), tier(1_000, 0, 200), tier(10_000, 100, 0)]);
+        for (current_bid, min_bid) in [
+            (999, 1_004),
+            // Band edges belong to the band they start
+            (1_000, 1_020),
+            (9_999, 10_198),
+            (10_000, 10_100),
+            (50_000, 50_100),
+        ] {
+            auction.current_bid = current_bid;
+            assert_eq!(auction.min_next_bid(&global).unwrap(), min_bid, "current_bid = {}", current_bid);
+        }
+
+        // A raise past u64::MAX is an error, not a wrap
+        auction.current_bid = u64::MAX;
+        assert!(auction.min_next_bid(&global).is_err());
+
+        // Clearing the schedule goes back to the global increments
+        auction.set_increment_tiers(&[]);
+        auction.current_bid = 1_000;
+        assert_eq!(auction.min_next_bid(&global).unwrap(), 1_010);
+    }
+}
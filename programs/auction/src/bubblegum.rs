@@ -0,0 +1,230 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    keccak,
+    program::invoke_signed,
+    pubkey,
+};
+
+use crate::account::CompressedAsset;
+use crate::error::AuctionError;
+
+pub const BUBBLEGUM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+pub const SPL_ACCOUNT_COMPRESSION_ID: Pubkey =
+    pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+pub const SPL_NOOP_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+// sha256("global:transfer")[..8]
+const TRANSFER_DISCRIMINATOR: [u8; 8] = [163, 52, 200, 231, 140, 3, 69, 186];
+const ASSET_PREFIX: &str = "asset";
+
+// account type (1) + header version (1) + max buffer size (4) + max depth (4)
+// + authority (32) + creation slot (8) + padding (6)
+const TREE_HEADER_SIZE: usize = 56;
+// sequence number, active index and buffer size of the tree, all u64
+const TREE_ACTIVE_INDEX_OFFSET: usize = TREE_HEADER_SIZE + 8;
+const TREE_CHANGE_LOGS_OFFSET: usize = TREE_HEADER_SIZE + 24;
+
+// Bubblegum's metadata of a cNFT, hashed into its leaf
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<TokenStandard>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+    pub token_program_version: TokenProgramVersion,
+    pub creators: Vec<Creator>,
+}
+
+// A cNFT's leaf as the seller lists it: its metadata and position in the tree
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedLeaf {
+    pub metadata: MetadataArgs,
+    pub nonce: u64,
+    pub index: u32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum TokenStandard {
+    NonFungible,
+    FungibleAsset,
+    Fungible,
+    NonFungibleEdition,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum TokenProgramVersion {
+    Original,
+    Token2022,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum UseMethod {
+    Burn,
+    Multiple,
+    Single,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct Uses {
+    pub use_method: UseMethod,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+/// Accounts of Bubblegum's transfer instruction
+pub struct TransferAccounts<'info> {
+    pub tree_authority: AccountInfo<'info>,
+    // Signs the transfer
+    pub leaf_owner: AccountInfo<'info>,
+    pub leaf_delegate: AccountInfo<'info>,
+    pub new_leaf_owner: AccountInfo<'info>,
+    pub merkle_tree: AccountInfo<'info>,
+    pub log_wrapper: AccountInfo<'info>,
+    pub compression_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub bubblegum_program: AccountInfo<'info>,
+}
+
+// Asset id of the leaf minted into the tree with this nonce
+pub fn get_asset_id(merkle_tree: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            ASSET_PREFIX.as_bytes(),
+            merkle_tree.as_ref(),
+            &nonce.to_le_bytes(),
+        ],
+        &BUBBLEGUM_ID,
+    )
+    .0
+}
+
+// Data hash and creator hash of the leaf, computed as Bubblegum does
+pub fn hash_metadata(metadata: &MetadataArgs) -> Result<([u8; 32], [u8; 32])> {
+    let metadata_hash = keccak::hashv(&[metadata.try_to_vec()?.as_slice()]);
+    let data_hash = keccak::hashv(&[
+        &metadata_hash.to_bytes(),
+        &metadata.seller_fee_basis_points.to_le_bytes(),
+    ]);
+
+    let creator_data = metadata
+        .creators
+        .iter()
+        .map(|creator| {
+            [
+                creator.address.as_ref(),
+                &[creator.verified as u8],
+                &[creator.share],
+            ]
+            .concat()
+        })
+        .collect::<Vec<_>>();
+    let creator_hash = keccak::hashv(
+        &creator_data
+            .iter()
+            .map(|creator| creator.as_slice())
+            .collect::<Vec<_>>(),
+    );
+
+    Ok((data_hash.to_bytes(), creator_hash.to_bytes()))
+}
+
+// Current root of the concurrent merkle tree, the one proofs are built against
+pub fn current_root(merkle_tree: &AccountInfo) -> Result<[u8; 32]> {
+    let data = merkle_tree.try_borrow_data()?;
+    if data.len() < TREE_CHANGE_LOGS_OFFSET {
+        return Err(error!(AuctionError::InvalidCompressedNft));
+    }
+    let max_depth = u32::from_le_bytes(data[6..10].try_into().unwrap()) as usize;
+    let active_index = u64::from_le_bytes(
+        data[TREE_ACTIVE_INDEX_OFFSET..TREE_ACTIVE_INDEX_OFFSET + 8]
+            .try_into()
+            .unwrap(),
+    ) as usize;
+
+    // Each change log holds its root, the path of max_depth nodes, an index and padding
+    let change_log_size = 32 + 32 * max_depth + 8;
+    let start = TREE_CHANGE_LOGS_OFFSET + active_index * change_log_size;
+    if start + 32 > data.len() {
+        return Err(error!(AuctionError::InvalidCompressedNft));
+    }
+
+    Ok(data[start..start + 32].try_into().unwrap())
+}
+
+/**
+ * @dev Transfers the leaf to new_leaf_owner. Bubblegum verifies the proof
+ * against the current root, so the leaf's owner, delegate and hashes must match
+ */
+pub fn transfer<'info>(
+    accounts: TransferAccounts<'info>,
+    leaf: &CompressedAsset,
+    proof: &[AccountInfo<'info>],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let root = current_root(&accounts.merkle_tree)?;
+
+    let mut data = TRANSFER_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&root);
+    data.extend_from_slice(&leaf.data_hash);
+    data.extend_from_slice(&leaf.creator_hash);
+    data.extend_from_slice(&leaf.nonce.to_le_bytes());
+    data.extend_from_slice(&leaf.index.to_le_bytes());
+
+    let mut metas = vec![
+        AccountMeta::new_readonly(accounts.tree_authority.key(), false),
+        AccountMeta::new_readonly(accounts.leaf_owner.key(), true),
+        AccountMeta::new_readonly(accounts.leaf_delegate.key(), false),
+        AccountMeta::new_readonly(accounts.new_leaf_owner.key(), false),
+        AccountMeta::new(accounts.merkle_tree.key(), false),
+        AccountMeta::new_readonly(accounts.log_wrapper.key(), false),
+        AccountMeta::new_readonly(accounts.compression_program.key(), false),
+        AccountMeta::new_readonly(accounts.system_program.key(), false),
+    ];
+    let mut account_infos = vec![
+        accounts.tree_authority,
+        accounts.leaf_owner,
+        accounts.leaf_delegate,
+        accounts.new_leaf_owner,
+        accounts.merkle_tree,
+        accounts.log_wrapper,
+        accounts.compression_program,
+        accounts.system_program,
+    ];
+    for node in proof {
+        metas.push(AccountMeta::new_readonly(node.key(), false));
+        account_infos.push(node.clone());
+    }
+    account_infos.push(accounts.bubblegum_program);
+
+    invoke_signed(
+        &Instruction {
+            program_id: BUBBLEGUM_ID,
+            accounts: metas,
+            data,
+        },
+        &account_infos,
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
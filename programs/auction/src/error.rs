@@ -30,7 +30,7 @@ pub enum AuctionError {
     EndedAuction,
 
     // 0x1775
-    #[msg("Must bid at least 5% over or 10 HTO higher than current Bid.")]
+    #[msg("The bid must beat the current one by the increment of its price band.")]
     InsufficientBid,
 
     // 0x1776
@@ -54,7 +54,7 @@ pub enum AuctionError {
     InvalidWinner,
 
     // 0x177b
-    #[msg("The HTO ATA's owner should be the auction creator.")]
+    #[msg("The quote token ATA's owner should be the auction creator.")]
     InvalidSeller,
 
     // 0x177c
@@ -63,6 +63,245 @@ pub enum AuctionError {
 
     // 0x177d
     #[msg("Cannot cancel auction if there is a bid.")]
-    InvalidCancel
+    InvalidCancel,
 
-}
\ No newline at end of file
+    // 0x177e
+    #[msg("The NFT is already listed in another auction.")]
+    DuplicateAuction,
+
+    // 0x177f
+    #[msg("Dutch start price must be higher than the floor price.")]
+    InvalidDutchPrice,
+
+    // 0x1780
+    #[msg("Invalid price decay schedule.")]
+    InvalidDecaySchedule,
+
+    // 0x1781
+    #[msg("An account required to settle the auction is missing.")]
+    MissingSettlementAccount,
+
+    // 0x1782
+    #[msg("This instruction does not support the auction type.")]
+    InvalidAuctionType,
+
+    // 0x1783
+    #[msg("Settlement must be first-price or second-price.")]
+    InvalidSettlementType,
+
+    // 0x1784
+    #[msg("The deposit must cover at least the start price.")]
+    InsufficientDeposit,
+
+    // 0x1785
+    #[msg("The auction is not in its reveal phase.")]
+    NotRevealPhase,
+
+    // 0x1786
+    #[msg("The bid is already revealed.")]
+    AlreadyRevealed,
+
+    // 0x1787
+    #[msg("The revealed bid does not match the commitment.")]
+    CommitmentMismatch,
+
+    // 0x1788
+    #[msg("The winning deposit is released at settlement.")]
+    WinnerCannotWithdraw,
+
+    // 0x1789
+    #[msg("Buy-now price must be above the start price with a cutoff of at most 100%.")]
+    InvalidBuyNowPrice,
+
+    // 0x178a
+    #[msg("Buy-now is not available for this auction.")]
+    BuyNowUnavailable,
+
+    // 0x178b
+    #[msg("The revealed reserve does not match the commitment.")]
+    ReserveMismatch,
+
+    // 0x178c
+    #[msg("The seller can still reveal the reserve price.")]
+    ReserveNotRevealed,
+
+    // 0x178d
+    #[msg("The auction has a bid and must be claimed.")]
+    AuctionHasBid,
+
+    // 0x178e
+    #[msg("Only the super admin can do this.")]
+    InvalidAdmin,
+
+    // 0x178f
+    #[msg("The quote mint accounts are invalid for this auction.")]
+    InvalidQuoteMint,
+
+    // 0x1790
+    #[msg("Invalid config value.")]
+    InvalidConfig,
+
+    // 0x1791
+    #[msg("The signer is not the proposed admin.")]
+    InvalidPendingAdmin,
+
+    // 0x1792
+    #[msg("Invalid operator role.")]
+    InvalidRole,
+
+    // 0x1793
+    #[msg("The signer does not hold the role this needs.")]
+    MissingRole,
+
+    // 0x1794
+    #[msg("The program is paused.")]
+    ProgramPaused,
+
+    // 0x1795
+    #[msg("The auction is frozen.")]
+    AuctionFrozen,
+
+    // 0x1796
+    #[msg("Invalid force cancel reason code.")]
+    InvalidCancelReason,
+
+    // 0x1797
+    #[msg("The NFT is not quarantined.")]
+    NotQuarantined,
+
+    // 0x1798
+    #[msg("The fee split needs 1~5 recipients with shares adding up to 100%.")]
+    InvalidFeeSplit,
+
+    // 0x1799
+    #[msg("The account does not match the fee recipient.")]
+    InvalidFeeRecipient,
+
+    // 0x179a
+    #[msg("A creator's royalty account is missing.")]
+    MissingCreatorAccount,
+
+    // 0x179b
+    #[msg("A Token Metadata account required to transfer the pNFT is missing.")]
+    MissingPnftAccount,
+
+    // 0x179c
+    #[msg("The mint has a Token-2022 extension the auction can't escrow.")]
+    UnsupportedMintExtension,
+
+    // 0x179d
+    #[msg("A Bubblegum account required to transfer the cNFT is missing.")]
+    MissingCnftAccount,
+
+    // 0x179e
+    #[msg("The cNFT leaf or its tree does not match the auction.")]
+    InvalidCompressedNft,
+
+    // 0x179f
+    #[msg("A Core account required to transfer the asset is missing.")]
+    MissingCoreAccount,
+
+    // 0x17a0
+    #[msg("The Core asset or its collection does not match the auction.")]
+    InvalidCoreAsset,
+
+    // 0x17a1
+    #[msg("The Core asset has a permanent delegate the auction can't escrow.")]
+    UnsupportedCorePlugin,
+
+    // 0x17a2
+    #[msg("The NFT mint must have a supply of 1.")]
+    InvalidNftSupply,
+
+    // 0x17a3
+    #[msg("The NFT mint must have 0 decimals.")]
+    InvalidNftDecimals,
+
+    // 0x17a4
+    #[msg("Fungible and semi-fungible tokens can't be auctioned.")]
+    NotNonFungible,
+
+    // 0x17a5
+    #[msg("The NFT mint's freeze authority could freeze the escrowed NFT.")]
+    RiskyFreezeAuthority,
+
+    // 0x17a6
+    #[msg("The seller's NFT account is frozen.")]
+    FrozenOwnerAta,
+
+    // 0x17a7
+    #[msg("The seller's NFT account has a delegate.")]
+    DelegatedOwnerAta,
+
+    // 0x17a8
+    #[msg("The collection config account does not match the collection.")]
+    InvalidCollectionConfig,
+
+    // 0x17a9
+    #[msg("The collection is not in the registry.")]
+    CollectionNotRegistered,
+
+    // 0x17aa
+    #[msg("The collection is blocked from listing.")]
+    CollectionBlocked,
+
+    // 0x17ab
+    #[msg("Invalid collection policy.")]
+    InvalidCollectionPolicy,
+
+    // 0x17ac
+    #[msg("The price is below the collection's minimum.")]
+    PriceBelowCollectionMin,
+
+    // 0x17ad
+    #[msg("The duration is outside the collection's range.")]
+    DurationOutsideCollectionRange,
+
+    // 0x17ae
+    #[msg("The collection can't be listed in this quote mint.")]
+    QuoteMintNotAllowed,
+
+    // 0x17af
+    #[msg("The soft-close window and extension must be within the admin bounds.")]
+    InvalidSoftClose,
+
+    // 0x17b0
+    #[msg("An increment schedule needs 1~4 ascending bands starting from 0, each with a minimum.")]
+    InvalidIncrementSchedule,
+
+    // 0x17b1
+    #[msg("The increment schedule can't be changed once bidding started or when the collection sets it.")]
+    IncrementScheduleLocked,
+
+    // 0x17b2
+    #[msg("Arithmetic overflow.")]
+    MathOverflow,
+
+    // 0x17b3
+    #[msg("The maximum bid can't be below the bid.")]
+    InvalidMaxBid,
+
+    // 0x17b4
+    #[msg("Bids on this auction go through its bid book, which must be passed.")]
+    MissingBidBook,
+
+    // 0x17b5
+    #[msg("Only English auctions that haven't ended take proxy bids.")]
+    ProxyBiddingUnavailable,
+
+    // 0x17b6
+    #[msg("The bid book can only be closed once its auction is.")]
+    AuctionNotClosed,
+
+    // 0x17b7
+    #[msg("The global authority is already in the current layout.")]
+    AlreadyMigrated,
+
+    // 0x17b8
+    #[msg("The sealed bid was committed to an earlier auction at this address.")]
+    StaleSealedBid,
+
+    // 0x17b9
+    #[msg("The NFT's token group must be passed and count the NFT as a member.")]
+    InvalidTokenGroup,
+}
@@ -1,15 +1,25 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
 use anchor_lang::solana_program::{system_program, sysvar};
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
-use mpl_token_metadata::state::{Creator, Metadata, TokenMetadataAccount};
-use solana_program::log::sol_log_compute_units;
-use solana_program::program::invoke_signed;
+use anchor_lang::solana_program::instruction::AccountMeta;
+use anchor_lang::solana_program::program::invoke;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::transfer_fee::{instruction::harvest_withheld_tokens_to_mint, TransferFeeConfig},
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_lang::solana_program::program::invoke_signed;
+use mpl_token_metadata::instruction::{builders::TransferBuilder, InstructionBuilder, TransferArgs};
+use mpl_token_metadata::state::{Metadata, TokenMetadataAccount, TokenStandard};
 
 pub mod account;
+pub mod bubblegum;
 pub mod error;
+pub mod mpl_core;
 pub mod utils;
 
 use account::*;
+use bubblegum::{get_asset_id, CompressedLeaf, MetadataArgs};
 use error::*;
 use utils::*;
 
@@ -20,438 +30,4633 @@ pub mod auction {
     use super::*;
 
     pub fn initialize(ctx: Context<Initialize>, _global_bump: u8) -> Result<()> {
-        let global_authority = &mut ctx.accounts.global_authority;
-        global_authority.super_admin = ctx.accounts.admin.key();
+        let admin = ctx.accounts.admin.key();
+        ctx.accounts
+            .global_authority
+            .set_inner(GlobalPool::new(admin));
+        ctx.accounts
+            .treasury
+            .set_inner(Treasury::new(*ctx.bumps.get("treasury").unwrap()));
 
         Ok(())
     }
 
-    pub fn create_auction(
-        ctx: Context<CreateAuction>,
-        start_price: u64,
-        duration: u64,
-    ) -> Result<()> {
-        let mut auction = ctx.accounts.auction.load_init()?;
-        let auction_ata = &ctx.accounts.auction_ata;
-        let owner = &ctx.accounts.owner;
-        let owner_ata = &ctx.accounts.owner_ata;
-        let mint = &ctx.accounts.mint;
-        let token_program = &ctx.accounts.token_program;
-        let ata_program = &ctx.accounts.ata_program;
-        let system_program = &ctx.accounts.system_program;
-        let rent_sysvar = &ctx.accounts.rent_sysvar;
-
-        let mint_metadata = &mut &ctx.accounts.mint_metadata;
-
-        let (metadata, _) = Pubkey::find_program_address(
-            &[
-                mpl_token_metadata::state::PREFIX.as_bytes(),
-                mpl_token_metadata::id().as_ref(),
-                mint.key().as_ref(),
-            ],
-            &mpl_token_metadata::id(),
-        );
-
-        if metadata != mint_metadata.key() {
-            return Err(error!(AuctionError::InvalidMetadata));
-        }
-        // verify metadata is legit
-        let nft_metadata = Metadata::from_account_info(mint_metadata)?;
-
-        let mut _collection: Pubkey = Pubkey::default();
-
-        if let Some(collection) = nft_metadata.collection {
-            if collection.verified {
-                _collection = collection.key;
-            }
-        }
-
-        if _collection == Pubkey::default() {
-            if let Some(creators) = nft_metadata.data.creators {
-                for i in 0..creators.len() {
-                    if creators[i].verified {
-                        _collection = creators[i].address;
-                        break;
-                    }
-                }
-            }
+    /**
+     * @dev Migrate the global authority of the first release, which only held
+     * the super admin, to the current layout with the initial config, and create the treasury
+     * Auctions of the first release can't be read by this version, so they all
+     * have to be settled before the program is upgraded
+     */
+    pub fn migrate_global_authority(ctx: Context<MigrateGlobalAuthority>) -> Result<()> {
+        let global_info = ctx.accounts.global_authority.to_account_info();
+        if global_info.data_len() != LEGACY_GLOBAL_SIZE {
+            return Err(error!(AuctionError::AlreadyMigrated));
         }
 
-        if _collection == Pubkey::default() {
-            return Err(error!(AuctionError::MetadataCreatorParseError));
+        let super_admin = Pubkey::try_from(&global_info.try_borrow_data()?[8..40]).unwrap();
+        if super_admin != ctx.accounts.admin.key() {
+            return Err(error!(AuctionError::InvalidAdmin));
         }
 
-        msg!("Collection= {:?}", _collection);
+        // The admin tops up the rent of the larger account
+        let rent = Rent::get()?.minimum_balance(GLOBAL_SIZE);
+        if rent > global_info.lamports() {
+            transfer_sol(
+                ctx.accounts.admin.to_account_info(),
+                global_info.clone(),
+                rent - global_info.lamports(),
+                ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+        global_info.realloc(GLOBAL_SIZE, true)?;
+        GlobalPool::new(super_admin)
+            .try_serialize(&mut &mut global_info.try_borrow_mut_data()?[..])?;
 
-        let cur_time: u64 = Clock::get()?.unix_timestamp as u64;
+        ctx.accounts
+            .treasury
+            .set_inner(Treasury::new(*ctx.bumps.get("treasury").unwrap()));
 
-        if duration < DAY || duration > 14 * DAY {
-            return Err(error!(AuctionError::InvalidDuration));
-        }
+        Ok(())
+    }
 
-        if start_price <= 0 {
-            return Err(error!(AuctionError::InvalidBidFloor));
+    /**
+     * @dev Propose a new super admin, who takes over once accepting
+     * Proposing the default pubkey withdraws a pending proposal
+     */
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        let global_authority = &mut ctx.accounts.global_authority;
+
+        if global_authority.super_admin != ctx.accounts.admin.key() {
+            return Err(error!(AuctionError::InvalidAdmin));
         }
 
-        auction.seller = *owner.key;
-        auction.nft_mint = mint.key();
-        auction.nft_collection = _collection;
+        global_authority.pending_admin = new_admin;
 
-        auction.end_time = cur_time + duration;
-        auction.start_price = start_price;
+        Ok(())
+    }
 
-        auction.bidder = Pubkey::default();
-        auction.current_bid = 0;
+    /**
+     * @dev Accept a pending super admin proposal
+     */
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let global_authority = &mut ctx.accounts.global_authority;
 
-        if auction_ata.to_account_info().data_is_empty() {
-            create_ata(
-                owner.to_account_info(),
-                ctx.accounts.global_authority.to_account_info(),
-                mint.to_account_info(),
-                auction_ata.to_account_info(),
-                token_program.to_account_info(),
-                ata_program.to_account_info(),
-                system_program.to_account_info(),
-                rent_sysvar.to_account_info(),
-            )?;
-        } else {
-            // TODO: what happens if we get here?
+        if global_authority.pending_admin == Pubkey::default()
+            || global_authority.pending_admin != ctx.accounts.new_admin.key()
+        {
+            return Err(error!(AuctionError::InvalidPendingAdmin));
         }
-        transfer_spl(
-            owner.to_account_info(),
-            owner_ata.to_account_info(),
-            auction_ata.to_account_info(),
-            1,
-            token_program.to_account_info(),
-            &[],
-        )?;
+
+        global_authority.super_admin = global_authority.pending_admin;
+        global_authority.pending_admin = Pubkey::default();
 
         Ok(())
     }
 
     /**
-     * @dev Cancel Auction
-     * In this function the owner of the auction can cancel his auction
+     * @dev Assign an operator role: fee manager, pauser or collection curator
+     * Assigning the default pubkey revokes the role
      */
-    pub fn cancel_auction(ctx: Context<CancelAuction>, bump: u8) -> Result<()> {
-        let auction = ctx.accounts.auction.load_mut()?;
+    pub fn set_role(ctx: Context<SetRole>, role: u8, operator: Pubkey) -> Result<()> {
+        let global_authority = &mut ctx.accounts.global_authority;
 
-        if auction.current_bid != 0 {
-            return Err(error!(AuctionError::InvalidCancel));
+        if global_authority.super_admin != ctx.accounts.admin.key() {
+            return Err(error!(AuctionError::InvalidAdmin));
         }
-        if auction.seller != ctx.accounts.seller.key() {
-            return Err(error!(AuctionError::InvalidCancel));
+
+        match role {
+            ROLE_FEE_MANAGER => global_authority.fee_manager = operator,
+            ROLE_PAUSER => global_authority.pauser = operator,
+            ROLE_CURATOR => global_authority.curator = operator,
+            _ => return Err(error!(AuctionError::InvalidRole)),
         }
 
-        // Transfer Back nft to the seller
-        transfer_spl(
-            ctx.accounts.global_authority.to_account_info(),
-            ctx.accounts.auction_ata.to_account_info(),
-            ctx.accounts.owner_ata.to_account_info(),
-            1,
-            ctx.accounts.token_program.to_account_info(),
-            &[&[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]]],
-        )?;
+        Ok(())
+    }
 
-        // Close the auction PDA
-        let owner = &mut ctx.accounts.seller;
-        let origin_lamports: u64 = owner.lamports();
-        **owner.lamports.borrow_mut() = origin_lamports + ctx.accounts.auction.as_ref().lamports();
-        **ctx.accounts.auction.as_ref().lamports.borrow_mut() = 0;
+    /**
+     * @dev Pause or resume the program
+     * While paused no auction can be created, bid on or settled. Refunds,
+     * cancellations and expired-auction settlements stay available
+     */
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        let global_authority = &mut ctx.accounts.global_authority;
+
+        if !global_authority.has_role(&ctx.accounts.admin.key(), ROLE_PAUSER) {
+            return Err(error!(AuctionError::MissingRole));
+        }
+
+        global_authority.paused = paused;
 
         Ok(())
     }
 
     /**
-     * @dev Uers can palce bid for the auction with this function
-     * In this function, users can place bid by HTO amount
+     * @dev Freeze or unfreeze a single auction, with the same effect as a pause
      */
-    pub fn place_bid(ctx: Context<PlaceBid>, bid: u64, bump: u8) -> Result<()> {
-        let mut auction_data_info = ctx.accounts.auction.load_mut()?;
-        let timestamp: u64 = Clock::get()?.unix_timestamp as u64;
-        msg!("Place Date: {}", timestamp);
+    pub fn freeze_auction(ctx: Context<FreezeAuction>, frozen: bool) -> Result<()> {
+        if !ctx
+            .accounts
+            .global_authority
+            .has_role(&ctx.accounts.admin.key(), ROLE_PAUSER)
+        {
+            return Err(error!(AuctionError::MissingRole));
+        }
+
+        let mut auction = ctx.accounts.auction.load_mut()?;
+        auction.frozen = frozen as u8;
+
+        Ok(())
+    }
+
+    /**
+     * @dev Update the fee, bid increment and duration config
+     * Callable by the fee manager. Live auctions pick up the new values immediately
+     */
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        fee_percent: u64,
+        min_increment: u64,
+        min_increment_percent: u64,
+        min_duration_after_bid_secs: u64,
+        day: u64,
+        royalty_fallback: u8,
+    ) -> Result<()> {
+        let global_authority = &mut ctx.accounts.global_authority;
 
-        if auction_data_info.current_bid == 0 && auction_data_info.start_price > bid {
-            return Err(error!(AuctionError::InsufficientFirstBid));
+        if !global_authority.has_role(&ctx.accounts.admin.key(), ROLE_FEE_MANAGER) {
+            return Err(error!(AuctionError::MissingRole));
         }
 
-        // Assert Auction Already Ended
-        if auction_data_info.end_time < timestamp {
-            return Err(error!(AuctionError::EndedAuction));
+        if fee_percent > MAX_FEE_PERCENT
+            || min_increment_percent > 100
+            || day == 0
+            || min_duration_after_bid_secs > day
+            || royalty_fallback > ROYALTY_FALLBACK_TREASURY
+        {
+            return Err(error!(AuctionError::InvalidConfig));
+        }
+
+        global_authority.fee_percent = fee_percent;
+        global_authority.min_increment = min_increment;
+        global_authority.min_increment_percent = min_increment_percent;
+        global_authority.min_duration_after_bid_secs = min_duration_after_bid_secs;
+        global_authority.day = day;
+        global_authority.royalty_fallback = royalty_fallback;
+
+        Ok(())
+    }
+
+    /**
+     * @dev Set the bounds of the soft-close window and extension sellers choose
+     * Callable by the fee manager. Live auctions keep the values they were listed with
+     */
+    pub fn set_soft_close_bounds(
+        ctx: Context<UpdateConfig>,
+        min_soft_close_secs: u64,
+        max_soft_close_secs: u64,
+    ) -> Result<()> {
+        let global_authority = &mut ctx.accounts.global_authority;
+
+        if !global_authority.has_role(&ctx.accounts.admin.key(), ROLE_FEE_MANAGER) {
+            return Err(error!(AuctionError::MissingRole));
         }
 
-        // New Bid should be increased more than min_increase_amount
-        if auction_data_info.current_bid + MIN_INCREMENT > bid
-            || auction_data_info.current_bid * (1 + MIN_INCREMENT_PERCENT / 100) > bid
+        if min_soft_close_secs == 0
+            || min_soft_close_secs > max_soft_close_secs
+            || max_soft_close_secs > global_authority.day
         {
-            return Err(error!(AuctionError::InsufficientBid));
+            return Err(error!(AuctionError::InvalidConfig));
         }
 
-        // Assert OutBidder Address with the Last Bidder
-        if Pubkey::default() != auction_data_info.bidder
-            && ctx.accounts.out_bidder.key() != auction_data_info.bidder
+        global_authority.min_soft_close_secs = min_soft_close_secs;
+        global_authority.max_soft_close_secs = max_soft_close_secs;
+
+        Ok(())
+    }
+
+    /**
+     * @dev Set how the treasury's fees are split, in basis points of the balance
+     * Callable by the fee manager. The shares must add up to 100%
+     */
+    pub fn set_fee_split(
+        ctx: Context<SetFeeSplit>,
+        recipients: Vec<Pubkey>,
+        shares_bps: Vec<u16>,
+    ) -> Result<()> {
+        if !ctx
+            .accounts
+            .global_authority
+            .has_role(&ctx.accounts.admin.key(), ROLE_FEE_MANAGER)
         {
-            return Err(error!(AuctionError::OutBidderMismatch));
+            return Err(error!(AuctionError::MissingRole));
         }
 
-        // Sets auction to run for 10 mins if the bidder bids in last 10 mins
-        if auction_data_info.end_time - MIN_DURATION_AFTER_BID_SECS < timestamp {
-            auction_data_info.end_time = timestamp + MIN_DURATION_AFTER_BID_SECS;
+        if recipients.is_empty()
+            || recipients.len() > MAX_FEE_RECIPIENTS
+            || recipients.len() != shares_bps.len()
+            || recipients.contains(&Pubkey::default())
+            || shares_bps.iter().map(|share| *share as u64).sum::<u64>() != PERMYRIAD
+        {
+            return Err(error!(AuctionError::InvalidFeeSplit));
         }
 
-        // Refund Last Bidder Escrow
-        if !Pubkey::default().eq(&auction_data_info.bidder) {
-            transfer_spl(
-                ctx.accounts.global_authority.to_account_info(),
-                ctx.accounts.auction_vault.to_account_info(),
-                ctx.accounts.out_bidder_account.to_account_info(),
-                auction_data_info.current_bid,
-                ctx.accounts.token_program.to_account_info(),
-                &[&[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]]],
-            )?;
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.recipients = [Pubkey::default(); MAX_FEE_RECIPIENTS];
+        treasury.shares_bps = [0; MAX_FEE_RECIPIENTS];
+        treasury.recipients[..recipients.len()].copy_from_slice(&recipients);
+        treasury.shares_bps[..shares_bps.len()].copy_from_slice(&shares_bps);
+        treasury.recipient_count = recipients.len() as u8;
+
+        Ok(())
+    }
+
+    /**
+     * @dev Pay out the treasury's fees in one quote mint along the split table
+     * Anyone can call this, as the fees only go to the configured recipients.
+     * Remaining accounts are the recipients' quote token ATAs in table order, or
+     * their wallets when treasury_vault is not passed to pay out native SOL
+     */
+    pub fn distribute_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributeFees<'info>>,
+    ) -> Result<()> {
+        let treasury = &ctx.accounts.treasury;
+        let count = treasury.recipient_count as usize;
+
+        if ctx.remaining_accounts.len() < count {
+            return Err(error!(AuctionError::AccountCountMismatch));
         }
+        // The quote mint's transfer hook accounts follow the recipients
+        let (recipient_accounts, hook_accounts) = ctx.remaining_accounts.split_at(count);
 
-        // Escrow New Bidder funds
-        transfer_spl(
-            ctx.accounts.bidder.to_account_info(),
-            ctx.accounts.new_bidder_account.to_account_info(),
-            ctx.accounts.auction_vault.to_account_info(),
-            bid,
-            ctx.accounts.token_program.to_account_info(),
-            &[],
-        )?;
+        // Native SOL fees are the treasury's lamports above its rent
+        let treasury_info = treasury.to_account_info();
+        let balance = match &ctx.accounts.treasury_vault {
+            Some(treasury_vault) => treasury_vault.amount,
+            None => treasury_info.lamports()
+                - Rent::get()?.minimum_balance(treasury_info.data_len()),
+        };
 
-        auction_data_info.bidder = ctx.accounts.bidder.key();
-        auction_data_info.current_bid = bid;
+        let seeds = &[TREASURY_SEED.as_bytes(), &[treasury.bump]];
+        let signer = &[&seeds[..]];
+
+        // Token-2022 fee mints withhold their fee from each recipient's payout
+        let quote = match &ctx.accounts.treasury_vault {
+            Some(treasury_vault) => {
+                let quote = quote_token(
+                    &ctx.accounts.quote_mint,
+                    &ctx.accounts.token_program,
+                    hook_accounts,
+                )
+                .ok_or(AuctionError::MissingSettlementAccount)?;
+                if quote.mint.key() != treasury_vault.mint {
+                    return Err(error!(AuctionError::InvalidQuoteMint));
+                }
+                Some(quote)
+            }
+            None => None,
+        };
+
+        // A lamport payout must leave a new recipient wallet rent-exempt
+        let min_wallet_balance = Rent::get()?.minimum_balance(0);
+        let mut paid: u64 = 0;
+        for (i, recipient_account) in recipient_accounts.iter().enumerate() {
+            // The last recipient also takes the rounding dust and any skipped share.
+            // If its own payout is skipped, the balance waits for the next distribution
+            let amount = if i == count - 1 {
+                balance - paid
+            } else {
+                (balance as u128 * treasury.shares_bps[i] as u128 / PERMYRIAD as u128) as u64
+            };
+
+            match (&ctx.accounts.treasury_vault, &quote) {
+                (Some(treasury_vault), Some(quote)) => {
+                    let recipient_ata =
+                        InterfaceAccount::<TokenAccount>::try_from(recipient_account)?;
+                    if recipient_ata.owner != treasury.recipients[i]
+                        || recipient_ata.mint != treasury_vault.mint
+                    {
+                        return Err(error!(AuctionError::InvalidFeeRecipient));
+                    }
+                    quote.transfer(
+                        treasury_info.clone(),
+                        treasury_vault.to_account_info(),
+                        recipient_account.clone(),
+                        amount,
+                        signer,
+                    )?;
+                }
+                _ => {
+                    if recipient_account.key() != treasury.recipients[i] {
+                        return Err(error!(AuctionError::InvalidFeeRecipient));
+                    }
+                    if recipient_account.lamports() + amount < min_wallet_balance {
+                        continue;
+                    }
+                    transfer_from_owned_account(
+                        &mut treasury_info.clone(),
+                        &mut recipient_account.clone(),
+                        amount,
+                    )?;
+                }
+            }
+            paid += amount;
+        }
 
         Ok(())
     }
 
-    pub fn claim_auction<'info>(
-        ctx: Context<'_, '_, '_, 'info, ClaimAuction<'info>>,
-        bump: u8,
-    ) -> Result<()> {
-        let auction_data_info = ctx.accounts.auction.load_mut()?;
-        let timestamp: u64 = Clock::get()?.unix_timestamp as u64;
+    /**
+     * @dev Whitelist a mint that auctions can be priced in
+     * Callable by the collection curator
+     */
+    pub fn add_quote_mint(ctx: Context<AddQuoteMint>) -> Result<()> {
+        if !ctx
+            .accounts
+            .global_authority
+            .has_role(&ctx.accounts.admin.key(), ROLE_CURATOR)
+        {
+            return Err(error!(AuctionError::MissingRole));
+        }
 
-        // The claimer should be Last bidder or Seller
-        if ctx.accounts.claimer.key() != auction_data_info.bidder
-            && ctx.accounts.claimer.key() != auction_data_info.seller
+        // Escrowed tokens must stay movable by the program and only by it
+        let quote_mint = ctx.accounts.quote_mint.to_account_info();
+        for extension in [EXTENSION_NON_TRANSFERABLE, EXTENSION_PERMANENT_DELEGATE] {
+            if mint_extension(&quote_mint, extension)?.is_some() {
+                return Err(error!(AuctionError::UnsupportedMintExtension));
+            }
+        }
+
+        let quote_mint_info = &mut ctx.accounts.quote_mint_info;
+        quote_mint_info.mint = ctx.accounts.quote_mint.key();
+        quote_mint_info.bump = *ctx.bumps.get("quote_mint_info").unwrap();
+
+        Ok(())
+    }
+
+    /**
+     * @dev Remove a mint from the whitelist
+     * Callable by the collection curator. Live auctions priced in the mint are not affected
+     */
+    pub fn remove_quote_mint(ctx: Context<RemoveQuoteMint>) -> Result<()> {
+        if !ctx
+            .accounts
+            .global_authority
+            .has_role(&ctx.accounts.admin.key(), ROLE_CURATOR)
         {
-            return Err(error!(AuctionError::InvalidClaimer));
+            return Err(error!(AuctionError::MissingRole));
         }
 
-        // The auction should be ended before
-        if timestamp < auction_data_info.end_time {
-            return Err(error!(AuctionError::NotEndedAuction));
+        Ok(())
+    }
+
+    /**
+     * @dev Register a collection with its listing policy
+     * Callable by the collection curator
+     */
+    pub fn add_collection(
+        ctx: Context<AddCollection>,
+        collection: Pubkey,
+        policy: CollectionPolicy,
+    ) -> Result<()> {
+        if !ctx
+            .accounts
+            .global_authority
+            .has_role(&ctx.accounts.admin.key(), ROLE_CURATOR)
+        {
+            return Err(error!(AuctionError::MissingRole));
         }
 
-        // Winner ATA's owner should be the last bidder
-        // The NFT should be sent to the winner's wallet so this check is necessary
-        if ctx.accounts.winner_ata.owner != auction_data_info.bidder {
-            return Err(error!(AuctionError::InvalidWinner));
+        policy.validate()?;
+
+        let collection_config = &mut ctx.accounts.collection_config;
+        collection_config.collection = collection;
+        collection_config.policy = policy;
+        collection_config.bump = *ctx.bumps.get("collection_config").unwrap();
+
+        Ok(())
+    }
+
+    /**
+     * @dev Replace the listing policy of a registered collection
+     * Callable by the collection curator. Live auctions keep the fee they were listed with
+     */
+    pub fn update_collection(ctx: Context<UpdateCollection>, policy: CollectionPolicy) -> Result<()> {
+        if !ctx
+            .accounts
+            .global_authority
+            .has_role(&ctx.accounts.admin.key(), ROLE_CURATOR)
+        {
+            return Err(error!(AuctionError::MissingRole));
         }
 
-        // Seller ATA's owner should be the auction creator
-        // The HTO token should be sent to the auction creator so this check is necessary
-        if ctx.accounts.seller_ata.owner != auction_data_info.seller {
-            return Err(error!(AuctionError::InvalidSeller));
+        policy.validate()?;
+        ctx.accounts.collection_config.policy = policy;
+
+        Ok(())
+    }
+
+    /**
+     * @dev Remove a collection from the registry
+     * Callable by the collection curator. Live auctions of the collection are not affected
+     */
+    pub fn remove_collection(ctx: Context<RemoveCollection>) -> Result<()> {
+        if !ctx
+            .accounts
+            .global_authority
+            .has_role(&ctx.accounts.admin.key(), ROLE_CURATOR)
+        {
+            return Err(error!(AuctionError::MissingRole));
         }
 
-        // Get Collection address from Metadata
-        let mint_metadata = &mut &ctx.accounts.mint_metadata;
-        msg!("Metadata Account: {:?}", ctx.accounts.mint_metadata.key());
-        let (metadata, _) = Pubkey::find_program_address(
-            &[
-                mpl_token_metadata::state::PREFIX.as_bytes(),
-                mpl_token_metadata::id().as_ref(),
-                ctx.accounts.nft_mint.key().as_ref(),
-            ],
-            &mpl_token_metadata::id(),
-        );
+        Ok(())
+    }
+
+    /**
+     * @dev Turn strict mode on or off, in which only registered collections can be listed
+     * Callable by the collection curator
+     */
+    pub fn set_strict_collections(ctx: Context<SetStrictCollections>, strict: bool) -> Result<()> {
+        let global_authority = &mut ctx.accounts.global_authority;
 
-        if metadata != mint_metadata.key() {
-            return Err(error!(AuctionError::InvalidMetadata));
+        if !global_authority.has_role(&ctx.accounts.admin.key(), ROLE_CURATOR) {
+            return Err(error!(AuctionError::MissingRole));
         }
 
-        // verify metadata is legit
-        let nft_metadata = Metadata::from_account_info(mint_metadata)?;
+        global_authority.strict_collections = strict;
 
-        let creators: &Vec<Creator>;
-        if let Some(crts) = &nft_metadata.data.creators {
-            creators = crts;
-        } else {
-            return Err(error!(AuctionError::MetadataCreatorParseError));
-        };
+        Ok(())
+    }
+
+    pub fn create_auction(
+        ctx: Context<CreateAuction>,
+        nonce: u64,
+        start_price: u64,
+        duration: u64,
+        terms: EnglishTerms,
+    ) -> Result<()> {
+        check_listing(&ctx.accounts.global_authority, duration)?;
+        let terms =
+            EnglishListing::new(&ctx.accounts.global_authority, start_price, duration, terms)?;
 
-        // Share Fee to distribute to creators
-        let total_share_fee = auction_data_info.current_bid
-            * (nft_metadata.data.seller_fee_basis_points as u64)
-            / PERMYRIAD;
+        let quote_mint = quote_mint_of(&ctx)?;
+        let (collection, metadata) = list_nft(&ctx)?;
+        let policy = collection_policy(
+            &ctx.accounts.global_authority,
+            &ctx.accounts.collection_config,
+            &collection,
+            start_price,
+            duration,
+            &quote_mint,
+        )?;
 
-        // Auction Fee to VAULT_WALLET
-        let auction_fee = auction_data_info.current_bid * FEE_PERCENT / 100;
+        let mut auction = ctx.accounts.auction.load_init()?;
+        Listing {
+            seller: ctx.accounts.owner.key(),
+            nft_mint: ctx.accounts.mint.key(),
+            nft_collection: collection,
+            quote_mint,
+            start_price,
+            duration,
+            nonce,
+            bump: *ctx.bumps.get("auction").unwrap(),
+            vault_bump: ctx.bumps.get("auction_vault").copied().unwrap_or_default(),
+        }
+        .init(&mut auction, &mut ctx.accounts.global_authority)?;
+        auction.snapshot_royalties(metadata.as_ref());
+        auction.snapshot_token_standard(metadata.as_ref());
+        auction.snapshot_policy(policy.as_ref());
+        terms.apply(&mut auction);
 
-        let token_program = &mut &ctx.accounts.token_program;
-        let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]];
-        let signer = &[&seeds[..]];
+        Ok(())
+    }
 
-        // Get remaining Accounts
-        let remaining_accounts: Vec<AccountInfo> = ctx.remaining_accounts.to_vec();
-        msg!(
-            "SC creators: {:?} Remaining Accouts {:?}",
-            creators.len(),
-            remaining_accounts.len()
-        );
-        if creators.len() != remaining_accounts.len() {
-            return Err(error!(AuctionError::AccountCountMismatch));
+    /**
+     * @dev Create Dutch Auction
+     * The price decays from start_price to floor_price over the duration,
+     * linearly or in steps of decay_interval seconds
+     */
+    pub fn create_dutch_auction(
+        ctx: Context<CreateAuction>,
+        nonce: u64,
+        start_price: u64,
+        floor_price: u64,
+        duration: u64,
+        decay_type: u8,
+        decay_interval: u64,
+    ) -> Result<()> {
+        check_listing(&ctx.accounts.global_authority, duration)?;
+
+        if floor_price == 0 {
+            return Err(error!(AuctionError::InvalidBidFloor));
         }
-        for i in 0..remaining_accounts.len() {
-            let creator_ata = spl_associated_token_account::get_associated_token_address(
-                &creators[i].address,
-                &HTO_TOKEN_MINT.parse::<Pubkey>().unwrap(),
-            );
-            if creator_ata == remaining_accounts[i].key() && creators[i].share != 0 {
-                let share_amount: u64 = total_share_fee * (creators[i].share as u64) / 100;
-                msg!("Share Amount: {:?}", share_amount);
-                // Distribute HTO to the Creator's wallets
-                let cpi_accounts = Transfer {
-                    from: ctx.accounts.auction_vault.to_account_info().clone(),
-                    to: remaining_accounts[i].clone(),
-                    authority: ctx.accounts.global_authority.to_account_info(),
-                };
-                token::transfer(
-                    CpiContext::new_with_signer(
-                        token_program.clone().to_account_info(),
-                        cpi_accounts,
-                        signer,
-                    ),
-                    share_amount,
-                )?;
-                continue;
+
+        if start_price <= floor_price {
+            return Err(error!(AuctionError::InvalidDutchPrice));
+        }
+
+        match decay_type {
+            DECAY_LINEAR => {}
+            DECAY_STEPPED => {
+                if decay_interval == 0 || decay_interval > duration {
+                    return Err(error!(AuctionError::InvalidDecaySchedule));
+                }
             }
-            sol_log_compute_units();
+            _ => return Err(error!(AuctionError::InvalidDecaySchedule)),
         }
 
-        msg!("Auction Fee : {:?}", auction_fee);
-        // Transfer HTO to the HL address as fee: 2%
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.auction_vault.to_account_info().clone(),
-            to: ctx.accounts.hl_vault.to_account_info().clone(),
-            authority: ctx.accounts.global_authority.to_account_info(),
-        };
-        token::transfer(
-            CpiContext::new_with_signer(
-                token_program.clone().to_account_info(),
-                cpi_accounts,
-                signer,
-            ),
-            auction_fee,
-        )?;
-        sol_log_compute_units();
-
-        msg!(
-            "HTO to Seller : {:?}",
-            auction_data_info.current_bid - total_share_fee - auction_fee
-        );
-        // Transfer HTO to the seller 100% - HL fee (2%) - royalties
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.auction_vault.to_account_info().clone(),
-            to: ctx.accounts.seller_ata.to_account_info().clone(),
-            authority: ctx.accounts.global_authority.to_account_info().clone(),
-        };
-        token::transfer(
-            CpiContext::new_with_signer(
-                token_program.clone().to_account_info(),
-                cpi_accounts,
-                signer,
-            ),
-            auction_data_info.current_bid - total_share_fee - auction_fee,
-        )?;
-        sol_log_compute_units();
-        // Close HTO account of the auction PDA
-        invoke_signed(
-            &spl_token::instruction::close_account(
-                token_program.key,
-                &ctx.accounts.auction_vault.key(),
-                ctx.accounts.claimer.key,
-                &ctx.accounts.global_authority.key(),
-                &[],
-            )?,
-            &[
-                token_program.clone().to_account_info(),
-                ctx.accounts.auction_vault.to_account_info().clone(),
-                ctx.accounts.claimer.to_account_info().clone(),
-                ctx.accounts.global_authority.to_account_info().clone(),
-            ],
-            &[&[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]]],
+        let quote_mint = quote_mint_of(&ctx)?;
+        let (collection, metadata) = list_nft(&ctx)?;
+        // The price can decay down to the floor, so the floor must meet the minimum
+        let policy = collection_policy(
+            &ctx.accounts.global_authority,
+            &ctx.accounts.collection_config,
+            &collection,
+            floor_price,
+            duration,
+            &quote_mint,
         )?;
 
-        // Transfer NFT to the winner
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.auction_ata.to_account_info().clone(),
-            to: ctx.accounts.winner_ata.to_account_info().clone(),
-            authority: ctx.accounts.global_authority.to_account_info().clone(),
-        };
-        token::transfer(
-            CpiContext::new_with_signer(
-                token_program.clone().to_account_info(),
-                cpi_accounts,
-                signer,
-            ),
-            1,
-        )?;
-        sol_log_compute_units();
-        // Close NFT account of the auction PDA
-        invoke_signed(
-            &spl_token::instruction::close_account(
-                token_program.key,
-                &ctx.accounts.auction_ata.key(),
-                ctx.accounts.claimer.key,
-                &ctx.accounts.global_authority.key(),
-                &[],
-            )?,
-            &[
-                token_program.clone().to_account_info(),
-                ctx.accounts.auction_ata.to_account_info().clone(),
-                ctx.accounts.claimer.to_account_info().clone(),
-                ctx.accounts.global_authority.to_account_info().clone(),
-            ],
-            &[&[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]]],
-        )?;
-        sol_log_compute_units();
+        let mut auction = ctx.accounts.auction.load_init()?;
+        Listing {
+            seller: ctx.accounts.owner.key(),
+            nft_mint: ctx.accounts.mint.key(),
+            nft_collection: collection,
+            quote_mint,
+            start_price,
+            duration,
+            nonce,
+            bump: *ctx.bumps.get("auction").unwrap(),
+            vault_bump: ctx.bumps.get("auction_vault").copied().unwrap_or_default(),
+        }
+        .init(&mut auction, &mut ctx.accounts.global_authority)?;
+        auction.snapshot_royalties(metadata.as_ref());
+        auction.snapshot_token_standard(metadata.as_ref());
+        auction.snapshot_policy(policy.as_ref());
+
+        auction.auction_type = AUCTION_TYPE_DUTCH;
+        auction.floor_price = floor_price;
+        auction.decay_type = decay_type;
+        auction.decay_interval = decay_interval;
+
+        Ok(())
+    }
+
+    /**
+     * @dev Create Sealed-Bid Auction
+     * Bidders commit hidden bids until end_time and reveal them until
+     * end_time + reveal_duration. The winner pays the highest bid or,
+     * with second-price settlement, the second-highest bid
+     */
+    pub fn create_sealed_auction(
+        ctx: Context<CreateAuction>,
+        nonce: u64,
+        start_price: u64,
+        duration: u64,
+        reveal_duration: u64,
+        settlement_type: u8,
+    ) -> Result<()> {
+        check_listing(&ctx.accounts.global_authority, duration)?;
+
+        let day = ctx.accounts.global_authority.day;
+        if !(day..=14 * day).contains(&reveal_duration) {
+            return Err(error!(AuctionError::InvalidDuration));
+        }
+
+        if start_price == 0 {
+            return Err(error!(AuctionError::InvalidBidFloor));
+        }
+
+        if settlement_type != SETTLE_FIRST_PRICE && settlement_type != SETTLE_SECOND_PRICE {
+            return Err(error!(AuctionError::InvalidSettlementType));
+        }
+
+        // Sealed deposits are escrowed in per-bid token vaults
+        let quote_mint = quote_mint_of(&ctx)?;
+        if quote_mint == Pubkey::default() {
+            return Err(error!(AuctionError::InvalidQuoteMint));
+        }
+
+        let (collection, metadata) = list_nft(&ctx)?;
+        let policy = collection_policy(
+            &ctx.accounts.global_authority,
+            &ctx.accounts.collection_config,
+            &collection,
+            start_price,
+            duration,
+            &quote_mint,
+        )?;
+
+        let mut auction = ctx.accounts.auction.load_init()?;
+        Listing {
+            seller: ctx.accounts.owner.key(),
+            nft_mint: ctx.accounts.mint.key(),
+            nft_collection: collection,
+            quote_mint,
+            start_price,
+            duration,
+            nonce,
+            bump: *ctx.bumps.get("auction").unwrap(),
+            vault_bump: ctx.bumps.get("auction_vault").copied().unwrap_or_default(),
+        }
+        .init(&mut auction, &mut ctx.accounts.global_authority)?;
+        auction.snapshot_royalties(metadata.as_ref());
+        auction.snapshot_token_standard(metadata.as_ref());
+        auction.snapshot_policy(policy.as_ref());
+
+        auction.auction_type = AUCTION_TYPE_SEALED;
+        auction.reveal_end_time = auction.end_time + reveal_duration;
+        auction.second_bid = 0;
+        auction.settlement_type = settlement_type;
+
+        Ok(())
+    }
+
+    /**
+     * @dev Create English Auction of a compressed NFT
+     * The seller passes the metadata hashed into the leaf and its merkle proof
+     * as remaining accounts. Bubblegum verifies the leaf while transferring it
+     * to the auction PDA, which keeps it until settlement
+     */
+    pub fn create_compressed_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateCompressedAuction<'info>>,
+        nonce: u64,
+        start_price: u64,
+        duration: u64,
+        terms: EnglishTerms,
+        leaf: CompressedLeaf,
+    ) -> Result<()> {
+        let CompressedLeaf {
+            metadata,
+            nonce: leaf_nonce,
+            index: leaf_index,
+        } = leaf;
+
+        check_listing(&ctx.accounts.global_authority, duration)?;
+        let terms =
+            EnglishListing::new(&ctx.accounts.global_authority, start_price, duration, terms)?;
+
+        let quote_mint = QuoteListing {
+            auction: ctx.accounts.auction.key(),
+            payer: ctx.accounts.owner.to_account_info(),
+            global_authority: ctx.accounts.global_authority.to_account_info(),
+            auction_vault: &ctx.accounts.auction_vault,
+            vault_bump: ctx.bumps.get("auction_vault").copied(),
+            quote_mint: &ctx.accounts.quote_mint,
+            quote_mint_info: &ctx.accounts.quote_mint_info,
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        }
+        .quote_mint()?;
+
+        // The verified collection, or the first verified creator as for SPL NFTs
+        let collection = metadata
+            .collection
+            .filter(|collection| collection.verified)
+            .map(|collection| collection.key)
+            .or_else(|| {
+                metadata
+                    .creators
+                    .iter()
+                    .find(|creator| creator.verified)
+                    .map(|creator| creator.address)
+            })
+            .ok_or(AuctionError::MetadataCreatorParseError)?;
+        let policy = collection_policy(
+            &ctx.accounts.global_authority,
+            &ctx.accounts.collection_config,
+            &collection,
+            start_price,
+            duration,
+            &quote_mint,
+        )?;
+
+        let (data_hash, creator_hash) = bubblegum::hash_metadata(&metadata)?;
+        let compressed_asset = &mut ctx.accounts.compressed_asset;
+        compressed_asset.auction = ctx.accounts.auction.key();
+        compressed_asset.seller = ctx.accounts.owner.key();
+        compressed_asset.auction_nonce = nonce;
+        compressed_asset.auction_bump = *ctx.bumps.get("auction").unwrap();
+        compressed_asset.merkle_tree = ctx.accounts.merkle_tree.key();
+        compressed_asset.data_hash = data_hash;
+        compressed_asset.creator_hash = creator_hash;
+        compressed_asset.nonce = leaf_nonce;
+        compressed_asset.index = leaf_index;
+        compressed_asset.proof_len = ctx.remaining_accounts.len() as u8;
+        compressed_asset.bump = *ctx.bumps.get("compressed_asset").unwrap();
+
+        // Escrow the leaf, which fails unless the metadata and proof match the tree
+        bubblegum::transfer(
+            bubblegum::TransferAccounts {
+                tree_authority: ctx.accounts.tree_authority.to_account_info(),
+                leaf_owner: ctx.accounts.owner.to_account_info(),
+                leaf_delegate: ctx.accounts.leaf_delegate.to_account_info(),
+                new_leaf_owner: ctx.accounts.auction.to_account_info(),
+                merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+                log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+                compression_program: ctx.accounts.compression_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                bubblegum_program: ctx.accounts.bubblegum_program.to_account_info(),
+            },
+            compressed_asset,
+            ctx.remaining_accounts,
+            &[],
+        )?;
+
+        let mut auction = ctx.accounts.auction.load_init()?;
+        Listing {
+            seller: ctx.accounts.owner.key(),
+            nft_mint: ctx.accounts.asset_id.key(),
+            nft_collection: collection,
+            quote_mint,
+            start_price,
+            duration,
+            nonce,
+            bump: *ctx.bumps.get("auction").unwrap(),
+            vault_bump: ctx.bumps.get("auction_vault").copied().unwrap_or_default(),
+        }
+        .init(&mut auction, &mut ctx.accounts.global_authority)?;
+        auction.snapshot_compressed(&metadata);
+        auction.snapshot_policy(policy.as_ref());
+        terms.apply(&mut auction);
+
+        Ok(())
+    }
+
+    /**
+     * @dev Create English Auction of a Metaplex Core asset
+     * The asset must belong to a Core collection, which becomes nft_collection.
+     * Royalties come from the asset's royalty plugin, else from the collection's
+     */
+    pub fn create_core_auction(
+        ctx: Context<CreateCoreAuction>,
+        nonce: u64,
+        start_price: u64,
+        duration: u64,
+        terms: EnglishTerms,
+    ) -> Result<()> {
+        check_listing(&ctx.accounts.global_authority, duration)?;
+        let terms =
+            EnglishListing::new(&ctx.accounts.global_authority, start_price, duration, terms)?;
+
+        let quote_mint = QuoteListing {
+            auction: ctx.accounts.auction.key(),
+            payer: ctx.accounts.owner.to_account_info(),
+            global_authority: ctx.accounts.global_authority.to_account_info(),
+            auction_vault: &ctx.accounts.auction_vault,
+            vault_bump: ctx.bumps.get("auction_vault").copied(),
+            quote_mint: &ctx.accounts.quote_mint,
+            quote_mint_info: &ctx.accounts.quote_mint_info,
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        }
+        .quote_mint()?;
+
+        // The asset must be the seller's and a member of the collection
+        let asset = mpl_core::parse_asset(&ctx.accounts.asset)?;
+        let collection = mpl_core::parse_collection(&ctx.accounts.collection)?;
+        if asset.owner != ctx.accounts.owner.key()
+            || asset.collection != Some(ctx.accounts.collection.key())
+        {
+            return Err(error!(AuctionError::InvalidCoreAsset));
+        }
+        let policy = collection_policy(
+            &ctx.accounts.global_authority,
+            &ctx.accounts.collection_config,
+            &ctx.accounts.collection.key(),
+            start_price,
+            duration,
+            &quote_mint,
+        )?;
+
+        // A permanent delegate could freeze, move or burn the asset in escrow
+        if asset.plugins.iter().chain(collection.plugins.iter()).any(|plugin| {
+            matches!(
+                *plugin,
+                mpl_core::PLUGIN_PERMANENT_FREEZE_DELEGATE
+                    | mpl_core::PLUGIN_PERMANENT_TRANSFER_DELEGATE
+                    | mpl_core::PLUGIN_PERMANENT_BURN_DELEGATE
+            )
+        }) {
+            return Err(error!(AuctionError::UnsupportedCorePlugin));
+        }
+
+        mpl_core::transfer(
+            mpl_core::TransferAccounts {
+                asset: ctx.accounts.asset.to_account_info(),
+                collection: ctx.accounts.collection.to_account_info(),
+                payer: ctx.accounts.owner.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+                new_owner: ctx.accounts.global_authority.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                core_program: ctx.accounts.core_program.to_account_info(),
+            },
+            &[],
+        )?;
+
+        let mut auction = ctx.accounts.auction.load_init()?;
+        Listing {
+            seller: ctx.accounts.owner.key(),
+            nft_mint: ctx.accounts.asset.key(),
+            nft_collection: ctx.accounts.collection.key(),
+            quote_mint,
+            start_price,
+            duration,
+            nonce,
+            bump: *ctx.bumps.get("auction").unwrap(),
+            vault_bump: ctx.bumps.get("auction_vault").copied().unwrap_or_default(),
+        }
+        .init(&mut auction, &mut ctx.accounts.global_authority)?;
+        auction.snapshot_core(asset.royalties.as_ref().or(collection.royalties.as_ref()));
+        auction.snapshot_policy(policy.as_ref());
+        terms.apply(&mut auction);
+
+        Ok(())
+    }
+
+    /**
+     * @dev Set the bid increment schedule of an English auction before its first bid
+     * Callable by the seller, unless the collection's policy sets the schedule.
+     * An empty schedule falls back to the global increments
+     */
+    pub fn set_increment_schedule(
+        ctx: Context<SetIncrementSchedule>,
+        tiers: Vec<IncrementTier>,
+    ) -> Result<()> {
+        let mut auction = ctx.accounts.auction.load_mut()?;
+
+        if auction.seller != ctx.accounts.seller.key() {
+            return Err(error!(AuctionError::InvalidSeller));
+        }
+        if auction.auction_type != AUCTION_TYPE_ENGLISH {
+            return Err(error!(AuctionError::InvalidAuctionType));
+        }
+        if auction.bidder != Pubkey::default() || auction.collection_increments != 0 {
+            return Err(error!(AuctionError::IncrementScheduleLocked));
+        }
+
+        if !tiers.is_empty() {
+            IncrementTier::validate_schedule(&tiers)?;
+        }
+        auction.set_increment_tiers(&tiers);
+
+        Ok(())
+    }
+
+    /**
+     * @dev View the lowest bid place_bid accepts right now, returned as u64
+     * The start price before the first bid of an English auction, the current
+     * price of a Dutch auction and the minimum deposit of a sealed one
+     */
+    pub fn next_min_bid(ctx: Context<NextMinBid>) -> Result<u64> {
+        let auction = ctx.accounts.auction.load()?;
+
+        match auction.auction_type {
+            AUCTION_TYPE_DUTCH => Ok(auction.current_price(Clock::get()?.unix_timestamp as u64)),
+            AUCTION_TYPE_SEALED => Ok(auction.start_price),
+            _ => auction.min_next_bid(&ctx.accounts.global_authority),
+        }
+    }
+
+    /**
+     * @dev Cancel Auction
+     * In this function the owner of the auction can cancel his auction
+     */
+    pub fn cancel_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelAuction<'info>>,
+        bump: u8,
+    ) -> Result<()> {
+        let auction = ctx.accounts.auction.load()?;
+
+        if auction.current_bid != 0 || auction.commit_count != 0 {
+            return Err(error!(AuctionError::InvalidCancel));
+        }
+        if auction.seller != ctx.accounts.seller.key() {
+            return Err(error!(AuctionError::InvalidCancel));
+        }
+
+        // Transfer Back nft to the seller
+        if auction.is_compressed() {
+            LeafTransfer {
+                leaf_owner: ctx.accounts.auction.to_account_info(),
+                new_owner: auction.seller,
+                receiver: ctx.accounts.seller.to_account_info(),
+                cnft: &ctx.accounts.cnft,
+                remaining_accounts: ctx.remaining_accounts,
+                        }
+            .transfer()?;
+        } else if auction.is_core() {
+            CoreTransfer {
+                asset: auction.nft_mint,
+                owner: ctx.accounts.global_authority.to_account_info(),
+                new_owner: auction.seller,
+                payer: ctx.accounts.seller.to_account_info(),
+                core: &ctx.accounts.core,
+            }
+            .transfer(&[&[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]]])?;
+        } else {
+            NftTransfer {
+                programmable: auction.is_programmable(),
+                source: required_account(&ctx.accounts.auction_ata)?.to_account_info(),
+                source_owner: ctx.accounts.global_authority.to_account_info(),
+                destination: ctx
+                    .accounts
+                    .owner_ata
+                    .clone()
+                    .ok_or(AuctionError::MissingSettlementAccount)?,
+                payer: ctx.accounts.seller.to_account_info(),
+                token_program: ctx.accounts.nft_token_program.to_account_info(),
+                pnft: &ctx.accounts.pnft,
+            }
+            .transfer(&[&[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]]])?;
+        }
+
+        // Close the empty quote vault of the auction
+        if !auction.is_native() {
+            close_spl_account(
+                ctx.accounts.global_authority.to_account_info(),
+                required_account(&ctx.accounts.auction_vault)?.to_account_info(),
+                ctx.accounts.seller.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                &[&[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]]],
+            )?;
+        }
+
+        // Close the auction PDA
+        let owner = &mut ctx.accounts.seller;
+        let origin_lamports: u64 = owner.lamports();
+        **owner.lamports.borrow_mut() = origin_lamports + ctx.accounts.auction.as_ref().lamports();
+        **ctx.accounts.auction.as_ref().lamports.borrow_mut() = 0;
+
+        Ok(())
+    }
+
+    /**
+     * @dev Cancel a disputed auction, e.g. a stolen NFT or a counterfeit collection
+     * Callable by the super admin even while paused or frozen. The escrowed bid is
+     * refunded to the bidder, and the NFT is either returned to the seller or kept
+     * quarantined in the program's ATA until release_quarantined_nft.
+     * The reason code is kept in a ForceCancelRecord that outlives the auction
+     */
+    pub fn force_cancel<'info>(
+        ctx: Context<'_, '_, '_, 'info, ForceCancel<'info>>,
+        bump: u8,
+        reason: u8,
+        quarantine: bool,
+    ) -> Result<()> {
+        let auction = ctx.accounts.auction.load()?;
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        if ctx.accounts.global_authority.super_admin != ctx.accounts.admin.key() {
+            return Err(error!(AuctionError::InvalidAdmin));
+        }
+        if reason > CANCEL_REASON_OTHER {
+            return Err(error!(AuctionError::InvalidCancelReason));
+        }
+
+        let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]];
+        let signer = &[&seeds[..]];
+
+        // Sealed bids stay in their own vaults and are withdrawn without forfeit,
+        // so only English auctions hold an escrowed bid here
+        let refund = if auction.auction_type == AUCTION_TYPE_SEALED {
+            0
+        } else {
+            auction.escrowed_bid
+        };
+        if refund != 0 {
+            if auction.is_native() {
+                transfer_from_owned_account(
+                    &mut ctx.accounts.auction.to_account_info(),
+                    &mut ctx
+                        .accounts
+                        .bidder
+                        .as_ref()
+                        .ok_or(AuctionError::MissingSettlementAccount)?
+                        .to_account_info(),
+                    refund,
+                )?;
+            } else {
+                quote_token(
+                    &ctx.accounts.quote_mint,
+                    &ctx.accounts.token_program,
+                    ctx.remaining_accounts,
+                )
+                .ok_or(AuctionError::MissingSettlementAccount)?
+                .transfer(
+                    ctx.accounts.global_authority.to_account_info(),
+                    required_account(&ctx.accounts.auction_vault)?.to_account_info(),
+                    required_account(&ctx.accounts.bidder_account)?.to_account_info(),
+                    refund,
+                    signer,
+                )?;
+            }
+        }
+
+        if !quarantine && auction.is_compressed() {
+            LeafTransfer {
+                leaf_owner: ctx.accounts.auction.to_account_info(),
+                new_owner: auction.seller,
+                receiver: ctx.accounts.seller.to_account_info(),
+                cnft: &ctx.accounts.cnft,
+                remaining_accounts: ctx.remaining_accounts,
+                        }
+            .transfer()?;
+        } else if !quarantine && auction.is_core() {
+            CoreTransfer {
+                asset: auction.nft_mint,
+                owner: ctx.accounts.global_authority.to_account_info(),
+                new_owner: auction.seller,
+                payer: ctx.accounts.admin.to_account_info(),
+                core: &ctx.accounts.core,
+            }
+            .transfer(signer)?;
+        } else if !quarantine {
+            return_nft_to_seller(
+                NftTransfer {
+                    programmable: auction.is_programmable(),
+                    source: required_account(&ctx.accounts.auction_ata)?.to_account_info(),
+                    source_owner: ctx.accounts.global_authority.to_account_info(),
+                    destination: required_account(&ctx.accounts.seller_nft_ata)?
+                        .to_account_info(),
+                    payer: ctx.accounts.admin.to_account_info(),
+                    token_program: ctx.accounts.nft_token_program.to_account_info(),
+                    pnft: &ctx.accounts.pnft,
+                },
+                ctx.accounts.seller.to_account_info(),
+                signer,
+            )?;
+        }
+
+        let record = &mut ctx.accounts.force_cancel_record;
+        record.auction = ctx.accounts.auction.key();
+        record.auction_id = auction.auction_id;
+        record.seller = auction.seller;
+        record.nft_mint = auction.nft_mint;
+        record.bidder = auction.bidder;
+        record.refund = refund;
+        record.admin = ctx.accounts.admin.key();
+        // A quarantined cNFT stays owned by the auction PDA and a Core asset by the
+        // global authority, while any other NFT is released from the recorded ATA
+        record.quarantine_ata = if auction.is_compressed() || auction.is_core() {
+            Pubkey::default()
+        } else {
+            required_account(&ctx.accounts.auction_ata)?.key()
+        };
+        record.timestamp = timestamp;
+        record.reason = reason;
+        record.quarantined = quarantine;
+        record.token_standard = auction.token_standard;
+        record.asset_type = auction.asset_type;
+        record.bump = *ctx.bumps.get("force_cancel_record").unwrap();
+
+        // Close the empty quote vault of the auction
+        if !auction.is_native() {
+            quote_token(
+                &ctx.accounts.quote_mint,
+                &ctx.accounts.token_program,
+                ctx.remaining_accounts,
+            )
+            .ok_or(AuctionError::MissingSettlementAccount)?
+            .close(
+                ctx.accounts.global_authority.to_account_info(),
+                required_account(&ctx.accounts.auction_vault)?.to_account_info(),
+                ctx.accounts.seller.to_account_info(),
+                signer,
+            )?;
+        }
+        drop(auction);
+
+        // Close the auction PDA
+        let owner = &mut ctx.accounts.seller;
+        let origin_lamports: u64 = owner.lamports();
+        **owner.lamports.borrow_mut() = origin_lamports + ctx.accounts.auction.as_ref().lamports();
+        **ctx.accounts.auction.as_ref().lamports.borrow_mut() = 0;
+
+        Ok(())
+    }
+
+    /**
+     * @dev Release an NFT quarantined by force_cancel to the ATA the super admin picks
+     */
+    pub fn release_quarantined_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReleaseQuarantinedNft<'info>>,
+        bump: u8,
+    ) -> Result<()> {
+        if ctx.accounts.global_authority.super_admin != ctx.accounts.admin.key() {
+            return Err(error!(AuctionError::InvalidAdmin));
+        }
+
+        let record = &mut ctx.accounts.force_cancel_record;
+        if !record.quarantined {
+            return Err(error!(AuctionError::NotQuarantined));
+        }
+
+        let signer: &[&[&[u8]]] = &[&[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]]];
+        if record.asset_type == ASSET_TYPE_COMPRESSED {
+            // The cNFT goes to whichever wallet the super admin passes
+            LeafTransfer {
+                leaf_owner: ctx.accounts.auction.to_account_info(),
+                new_owner: cnft_account(&ctx.accounts.cnft.new_leaf_owner)?.key(),
+                receiver: ctx.accounts.admin.to_account_info(),
+                cnft: &ctx.accounts.cnft,
+                remaining_accounts: ctx.remaining_accounts,
+            }
+            .transfer()?;
+        } else if record.asset_type == ASSET_TYPE_CORE {
+            CoreTransfer {
+                asset: record.nft_mint,
+                owner: ctx.accounts.global_authority.to_account_info(),
+                new_owner: core_account(&ctx.accounts.core.new_owner)?.key(),
+                payer: ctx.accounts.admin.to_account_info(),
+                core: &ctx.accounts.core,
+            }
+            .transfer(signer)?;
+        } else {
+            return_nft_to_seller(
+                NftTransfer {
+                    programmable: record.token_standard
+                        == TokenStandard::ProgrammableNonFungible as u8,
+                    source: required_account(&ctx.accounts.quarantine_ata)?.to_account_info(),
+                    source_owner: ctx.accounts.global_authority.to_account_info(),
+                    destination: required_account(&ctx.accounts.destination_ata)?
+                        .to_account_info(),
+                    payer: ctx.accounts.admin.to_account_info(),
+                    token_program: ctx.accounts.nft_token_program.to_account_info(),
+                    pnft: &ctx.accounts.pnft,
+                },
+                ctx.accounts.admin.to_account_info(),
+                signer,
+            )?;
+        }
+        record.quarantined = false;
+
+        Ok(())
+    }
+
+    /**
+     * @dev Open the bid book of an English auction, enabling proxy bids
+     * Anyone can open it and gets its rent back through close_bid_book once the
+     * auction is closed. From then on every bid goes through the book
+     */
+    pub fn open_bid_book(ctx: Context<OpenBidBook>) -> Result<()> {
+        let mut auction = ctx.accounts.auction.load_mut()?;
+        let timestamp: u64 = Clock::get()?.unix_timestamp as u64;
+
+        if auction.auction_type != AUCTION_TYPE_ENGLISH || auction.end_time < timestamp {
+            return Err(error!(AuctionError::ProxyBiddingUnavailable));
+        }
+        auction.has_bid_book = 1;
+
+        let bid_book = &mut ctx.accounts.bid_book;
+        bid_book.auction = ctx.accounts.auction.key();
+        bid_book.auction_id = auction.auction_id;
+        bid_book.payer = ctx.accounts.payer.key();
+        bid_book.leader = auction.bidder;
+        bid_book.max_bid = auction.escrowed_bid;
+        bid_book.bump = *ctx.bumps.get("bid_book").unwrap();
+
+        Ok(())
+    }
+
+    /**
+     * @dev Close the bid book of a closed auction, refunding its rent to whoever opened it
+     * A relisting at the same address is a new auction, leaving the book closable
+     */
+    pub fn close_bid_book(ctx: Context<CloseBidBook>) -> Result<()> {
+        let auction_info = ctx.accounts.auction.to_account_info();
+        if auction_info.owner == &ID && !auction_info.data_is_empty() {
+            let auction = AccountLoader::<AuctionPool>::try_from(&auction_info)?;
+            if auction.load()?.auction_id == ctx.accounts.bid_book.auction_id {
+                return Err(error!(AuctionError::AuctionNotClosed));
+            }
+        }
+
+        Ok(())
+    }
+
+    /**
+     * @dev Uers can palce bid for the auction with this function
+     * In this function, users can place bid by quote token amount
+     * With max_bid the bid is a proxy: max_bid is escrowed and the auction only
+     * records what it takes to lead, bid being the least it opens with. Later
+     * bids push the proxy up to max_bid on their own. A proxy needs the bid book
+     */
+    pub fn place_bid<'info>(
+        ctx: Context<'_, '_, '_, 'info, PlaceBid<'info>>,
+        bid: u64,
+        bump: u8,
+        max_bid: Option<u64>,
+    ) -> Result<()> {
+        let mut auction_data_info = ctx.accounts.auction.load_mut()?;
+        let timestamp: u64 = Clock::get()?.unix_timestamp as u64;
+
+        assert_not_paused(&ctx.accounts.global_authority, &auction_data_info)?;
+
+        if auction_data_info.auction_type == AUCTION_TYPE_SEALED {
+            return Err(error!(AuctionError::InvalidAuctionType));
+        }
+
+        if auction_data_info.auction_type == AUCTION_TYPE_DUTCH {
+            // Assert Auction Already Ended
+            if auction_data_info.end_time < timestamp {
+                return Err(error!(AuctionError::EndedAuction));
+            }
+
+            // The first bid at or above the current price buys the NFT at that price
+            let price = auction_data_info.current_price(timestamp);
+            if bid < price {
+                return Err(error!(AuctionError::InsufficientBid));
+            }
+
+            if auction_data_info.is_native() {
+                // The system transfer needs the auction account unborrowed
+                drop(auction_data_info);
+                transfer_sol(
+                    ctx.accounts.bidder.to_account_info(),
+                    ctx.accounts.auction.to_account_info(),
+                    price,
+                    ctx.accounts.system_program.to_account_info(),
+                )?;
+                auction_data_info = ctx.accounts.auction.load_mut()?;
+            } else {
+                // The vault must receive the full price after any transfer fee
+                let quote = quote_token(
+                    &ctx.accounts.quote_mint,
+                    &ctx.accounts.token_program,
+                    ctx.remaining_accounts,
+                )
+                .ok_or(AuctionError::MissingSettlementAccount)?;
+                quote.transfer(
+                    ctx.accounts.bidder.to_account_info(),
+                    required_account(&ctx.accounts.new_bidder_account)?.to_account_info(),
+                    required_account(&ctx.accounts.auction_vault)?.to_account_info(),
+                    quote.gross_amount(price)?,
+                    &[],
+                )?;
+            }
+
+            auction_data_info.bidder = ctx.accounts.bidder.key();
+            auction_data_info.current_bid = price;
+            auction_data_info.escrowed_bid = price;
+
+            // An escrowed cNFT is moved by its owner, the auction PDA, which Bubblegum
+            // can only borrow while the auction is read-only
+            drop(auction_data_info);
+            let auction_data_info = ctx.accounts.auction.load()?;
+
+            let payment = PaymentAccounts {
+                auction: ctx.accounts.auction.to_account_info(),
+                auction_vault: &ctx.accounts.auction_vault,
+                treasury: ctx.accounts.treasury.to_account_info(),
+                treasury_vault: &ctx.accounts.treasury_vault,
+                seller: &ctx.accounts.seller,
+                seller_ata: &ctx.accounts.seller_ata,
+                quote: quote_token(
+                    &ctx.accounts.quote_mint,
+                    &ctx.accounts.token_program,
+                    ctx.remaining_accounts,
+                ),
+            }
+            .payment(&auction_data_info)?;
+            settle_auction(
+                &auction_data_info,
+                Settlement {
+                    claimer: ctx.accounts.bidder.to_account_info(),
+                    vault_rent_receiver: ctx.accounts.bidder.to_account_info(),
+                    global_authority: ctx.accounts.global_authority.to_account_info(),
+                    fee_percent: auction_data_info.fee_percent(&ctx.accounts.global_authority),
+                    royalty_fallback: ctx.accounts.global_authority.royalty_fallback,
+                    auction: ctx.accounts.auction.to_account_info(),
+                    auction_ata: ctx.accounts.auction_ata.as_deref(),
+                    winner_ata: ctx.accounts.winner_ata.as_deref(),
+                    payment,
+                    nft_token_program: ctx.accounts.nft_token_program.to_account_info(),
+                    creator_accounts: ctx.remaining_accounts,
+                    ata_program: ctx.accounts.ata_program.clone(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    pnft: &ctx.accounts.pnft,
+                    cnft: Some(&ctx.accounts.cnft),
+                    core: Some(&ctx.accounts.core),
+                },
+                bump,
+            )?;
+
+            // Close the auction PDA
+            let bidder = &mut ctx.accounts.bidder;
+            let origin_lamports: u64 = bidder.lamports();
+            **bidder.lamports.borrow_mut() =
+                origin_lamports + ctx.accounts.auction.as_ref().lamports();
+            **ctx.accounts.auction.as_ref().lamports.borrow_mut() = 0;
+
+            return Ok(());
+        }
+
+        // Assert Auction Already Ended
+        if auction_data_info.end_time < timestamp {
+            return Err(error!(AuctionError::EndedAuction));
+        }
+
+        // A plain bid is a proxy capped at the bid itself
+        let plain_bid = max_bid.is_none();
+        let max_bid = max_bid.unwrap_or(bid);
+        if max_bid < bid {
+            return Err(error!(AuctionError::InvalidMaxBid));
+        }
+        if auction_data_info.has_bid_book != 0 && ctx.accounts.bid_book.is_none() {
+            return Err(error!(AuctionError::MissingBidBook));
+        }
+
+        // The first bid must meet the start price, later ones the increment schedule
+        let min_bid = auction_data_info.min_next_bid(&ctx.accounts.global_authority)?;
+        if max_bid < min_bid {
+            if auction_data_info.current_bid == 0 {
+                return Err(error!(AuctionError::InsufficientFirstBid));
+            }
+            return Err(error!(AuctionError::InsufficientBid));
+        }
+
+        // Assert OutBidder Address with the Last Bidder
+        if Pubkey::default() != auction_data_info.bidder
+            && ctx.accounts.out_bidder.key() != auction_data_info.bidder
+        {
+            return Err(error!(AuctionError::OutBidderMismatch));
+        }
+
+        // Late bids extend the auction as the seller chose at listing
+        auction_data_info.extend_for_bid(timestamp);
+
+        let has_leader = auction_data_info.bidder != Pubkey::default();
+        let is_leader = ctx.accounts.bidder.key() == auction_data_info.bidder;
+        let leader_max = auction_data_info.escrowed_bid;
+
+        // The leader can only raise their ceiling
+        if is_leader && max_bid <= leader_max {
+            return Err(error!(AuctionError::InsufficientBid));
+        }
+
+        // The leading proxy answers bids up to its ceiling, winning ties as the
+        // earlier bid. Nothing is escrowed from the outbid bidder
+        if has_leader && !is_leader && max_bid <= leader_max {
+            let counter_bid = auction_data_info
+                .min_bid_over(&ctx.accounts.global_authority, max_bid)?
+                .min(leader_max);
+            auction_data_info.current_bid = counter_bid;
+
+            let bid_book = ctx
+                .accounts
+                .bid_book
+                .as_mut()
+                .ok_or(AuctionError::MissingBidBook)?;
+            bid_book.bid_count += 1;
+            bid_book.counter_bid_count += 1;
+
+            return Ok(());
+        }
+
+        // Otherwise the bidder leads, at what beats the last ceiling up to their
+        // own. Raising one's own ceiling doesn't raise the price, while a plain
+        // bid of the leader becomes the price
+        let lead_bid = if is_leader && plain_bid {
+            bid
+        } else if is_leader {
+            auction_data_info.current_bid
+        } else if has_leader {
+            auction_data_info
+                .min_bid_over(&ctx.accounts.global_authority, leader_max)?
+                .min(max_bid)
+                .max(min_bid)
+                .max(bid)
+        } else {
+            min_bid.max(bid)
+        };
+
+        // A ceiling above the price stays hidden, which only the bid book tracks
+        if max_bid > lead_bid && ctx.accounts.bid_book.is_none() {
+            return Err(error!(AuctionError::MissingBidBook));
+        }
+
+        // Refund the whole escrow of the last bidder, unused proxy ceiling included.
+        // A leader raising their ceiling only escrows the difference
+        let escrow = if is_leader {
+            max_bid - leader_max
+        } else {
+            max_bid
+        };
+        if has_leader && !is_leader {
+            if auction_data_info.is_native() {
+                transfer_from_owned_account(
+                    &mut ctx.accounts.auction.to_account_info(),
+                    &mut ctx.accounts.out_bidder.to_account_info(),
+                    leader_max,
+                )?;
+            } else {
+                quote_token(
+                    &ctx.accounts.quote_mint,
+                    &ctx.accounts.token_program,
+                    ctx.remaining_accounts,
+                )
+                .ok_or(AuctionError::MissingSettlementAccount)?
+                .transfer(
+                    ctx.accounts.global_authority.to_account_info(),
+                    required_account(&ctx.accounts.auction_vault)?.to_account_info(),
+                    required_account(&ctx.accounts.out_bidder_account)?.to_account_info(),
+                    leader_max,
+                    &[&[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]]],
+                )?;
+            }
+        }
+
+        // Escrow New Bidder funds, up to the proxy ceiling
+        if auction_data_info.is_native() {
+            // The system transfer needs the auction account unborrowed
+            drop(auction_data_info);
+            transfer_sol(
+                ctx.accounts.bidder.to_account_info(),
+                ctx.accounts.auction.to_account_info(),
+                escrow,
+                ctx.accounts.system_program.to_account_info(),
+            )?;
+            auction_data_info = ctx.accounts.auction.load_mut()?;
+        } else {
+            // The vault must hold the full bid after any transfer fee
+            let quote = quote_token(
+                &ctx.accounts.quote_mint,
+                &ctx.accounts.token_program,
+                ctx.remaining_accounts,
+            )
+            .ok_or(AuctionError::MissingSettlementAccount)?;
+            quote.transfer(
+                ctx.accounts.bidder.to_account_info(),
+                required_account(&ctx.accounts.new_bidder_account)?.to_account_info(),
+                required_account(&ctx.accounts.auction_vault)?.to_account_info(),
+                quote.gross_amount(escrow)?,
+                &[],
+            )?;
+        }
+
+        auction_data_info.bidder = ctx.accounts.bidder.key();
+        auction_data_info.current_bid = lead_bid;
+        auction_data_info.escrowed_bid = max_bid;
+
+        if let Some(bid_book) = ctx.accounts.bid_book.as_mut() {
+            bid_book.leader = ctx.accounts.bidder.key();
+            bid_book.max_bid = max_bid;
+            bid_book.bid_count += 1;
+        }
+
+        Ok(())
+    }
+
+    /**
+     * @dev Settle an ended auction
+     * With a hidden reserve the seller reveals reserve_price and reserve_salt here.
+     * If the winner's proxy ceiling is below the reserve, or the seller doesn't reveal
+     * it within a day of the end, the bidder is refunded and the NFT goes back to the seller
+     */
+    pub fn claim_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimAuction<'info>>,
+        bump: u8,
+        reserve_price: u64,
+        reserve_salt: [u8; 32],
+    ) -> Result<()> {
+        let mut auction_data_info = ctx.accounts.auction.load()?;
+        let timestamp: u64 = Clock::get()?.unix_timestamp as u64;
+
+        // Sealed-bid auctions settle through claim_sealed_auction
+        if auction_data_info.auction_type == AUCTION_TYPE_SEALED {
+            return Err(error!(AuctionError::InvalidAuctionType));
+        }
+
+        // The claimer should be Last bidder or Seller
+        if ctx.accounts.claimer.key() != auction_data_info.bidder
+            && ctx.accounts.claimer.key() != auction_data_info.seller
+        {
+            return Err(error!(AuctionError::InvalidClaimer));
+        }
+
+        // The auction should be ended before
+        if timestamp < auction_data_info.end_time {
+            return Err(error!(AuctionError::NotEndedAuction));
+        }
+
+        let mut reserve_not_met = false;
+        if auction_data_info.reserve_hash != [0; 32] {
+            if ctx.accounts.claimer.key() == auction_data_info.seller {
+                let hash = hashv(&[
+                    &reserve_price.to_le_bytes(),
+                    &reserve_salt,
+                    auction_data_info.seller.as_ref(),
+                ]);
+                if hash.to_bytes() != auction_data_info.reserve_hash {
+                    return Err(error!(AuctionError::ReserveMismatch));
+                }
+
+                // The leader's proxy would have bid up to the reserve, so the
+                // price rises to it as far as their ceiling allows
+                let price = auction_data_info
+                    .current_bid
+                    .max(auction_data_info.escrowed_bid.min(reserve_price));
+                reserve_not_met = price < reserve_price;
+                if !reserve_not_met && price != auction_data_info.current_bid {
+                    drop(auction_data_info);
+                    ctx.accounts.auction.load_mut()?.current_bid = price;
+                    auction_data_info = ctx.accounts.auction.load()?;
+                }
+            } else if timestamp < auction_data_info.end_time + ctx.accounts.global_authority.day {
+                return Err(error!(AuctionError::ReserveNotRevealed));
+            } else {
+                // The seller missed the reveal window, so the sale is called off
+                reserve_not_met = true;
+            }
+        }
+
+        if reserve_not_met {
+            let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]];
+            let signer = &[&seeds[..]];
+
+            // Refund Last Bidder Escrow
+            if auction_data_info.is_native() {
+                transfer_from_owned_account(
+                    &mut ctx.accounts.auction.to_account_info(),
+                    &mut ctx
+                        .accounts
+                        .bidder
+                        .as_ref()
+                        .ok_or(AuctionError::MissingSettlementAccount)?
+                        .to_account_info(),
+                    auction_data_info.escrowed_bid,
+                )?;
+            } else {
+                let quote = quote_token(
+                    &ctx.accounts.quote_mint,
+                    &ctx.accounts.token_program,
+                    ctx.remaining_accounts,
+                )
+                .ok_or(AuctionError::MissingSettlementAccount)?;
+                quote.transfer(
+                    ctx.accounts.global_authority.to_account_info(),
+                    required_account(&ctx.accounts.auction_vault)?.to_account_info(),
+                    required_account(&ctx.accounts.bidder_account)?.to_account_info(),
+                    auction_data_info.escrowed_bid,
+                    signer,
+                )?;
+                quote.close(
+                    ctx.accounts.global_authority.to_account_info(),
+                    required_account(&ctx.accounts.auction_vault)?.to_account_info(),
+                    ctx.accounts.claimer.to_account_info(),
+                    signer,
+                )?;
+            }
+
+            if auction_data_info.is_compressed() {
+                LeafTransfer {
+                    leaf_owner: ctx.accounts.auction.to_account_info(),
+                    new_owner: auction_data_info.seller,
+                    receiver: ctx.accounts.claimer.to_account_info(),
+                    cnft: &ctx.accounts.cnft,
+                    remaining_accounts: ctx.remaining_accounts,
+                                }
+                .transfer()?;
+            } else if auction_data_info.is_core() {
+                CoreTransfer {
+                    asset: auction_data_info.nft_mint,
+                    owner: ctx.accounts.global_authority.to_account_info(),
+                    new_owner: auction_data_info.seller,
+                    payer: ctx.accounts.claimer.to_account_info(),
+                    core: &ctx.accounts.core,
+                }
+                .transfer(signer)?;
+            } else {
+                return_nft_to_seller(
+                    NftTransfer {
+                        programmable: auction_data_info.is_programmable(),
+                        source: required_account(&ctx.accounts.auction_ata)?.to_account_info(),
+                        source_owner: ctx.accounts.global_authority.to_account_info(),
+                        destination: required_account(&ctx.accounts.seller_nft_ata)?
+                            .to_account_info(),
+                        payer: ctx.accounts.claimer.to_account_info(),
+                        token_program: ctx.accounts.nft_token_program.to_account_info(),
+                        pnft: &ctx.accounts.pnft,
+                    },
+                    ctx.accounts.claimer.to_account_info(),
+                    signer,
+                )?;
+            }
+
+            // Close the auction PDA
+            let owner = &mut ctx.accounts.claimer;
+            let origin_lamports: u64 = owner.lamports();
+            **owner.lamports.borrow_mut() =
+                origin_lamports + ctx.accounts.auction.as_ref().lamports();
+            **ctx.accounts.auction.as_ref().lamports.borrow_mut() = 0;
+
+            return Ok(());
+        }
+
+        // Refunds stay available while paused, settling a sale doesn't
+        assert_not_paused(&ctx.accounts.global_authority, &auction_data_info)?;
+
+        // The winner gets back the escrow their proxy didn't need
+        let unused_escrow = auction_data_info.unused_escrow();
+        if unused_escrow != 0 {
+            if auction_data_info.is_native() {
+                transfer_from_owned_account(
+                    &mut ctx.accounts.auction.to_account_info(),
+                    &mut ctx
+                        .accounts
+                        .bidder
+                        .as_ref()
+                        .ok_or(AuctionError::MissingSettlementAccount)?
+                        .to_account_info(),
+                    unused_escrow,
+                )?;
+            } else {
+                quote_token(
+                    &ctx.accounts.quote_mint,
+                    &ctx.accounts.token_program,
+                    ctx.remaining_accounts,
+                )
+                .ok_or(AuctionError::MissingSettlementAccount)?
+                .transfer(
+                    ctx.accounts.global_authority.to_account_info(),
+                    required_account(&ctx.accounts.auction_vault)?.to_account_info(),
+                    required_account(&ctx.accounts.bidder_account)?.to_account_info(),
+                    unused_escrow,
+                    &[&[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]]],
+                )?;
+            }
+        }
+
+        let payment = PaymentAccounts {
+            auction: ctx.accounts.auction.to_account_info(),
+            auction_vault: &ctx.accounts.auction_vault,
+            treasury: ctx.accounts.treasury.to_account_info(),
+            treasury_vault: &ctx.accounts.treasury_vault,
+            seller: &ctx.accounts.seller,
+            seller_ata: &ctx.accounts.seller_ata,
+            quote: quote_token(
+                &ctx.accounts.quote_mint,
+                &ctx.accounts.token_program,
+                ctx.remaining_accounts,
+            ),
+        }
+        .payment(&auction_data_info)?;
+        settle_auction(
+            &auction_data_info,
+            Settlement {
+                claimer: ctx.accounts.claimer.to_account_info(),
+                vault_rent_receiver: ctx.accounts.claimer.to_account_info(),
+                global_authority: ctx.accounts.global_authority.to_account_info(),
+                fee_percent: auction_data_info.fee_percent(&ctx.accounts.global_authority),
+                royalty_fallback: ctx.accounts.global_authority.royalty_fallback,
+                auction: ctx.accounts.auction.to_account_info(),
+                auction_ata: ctx.accounts.auction_ata.as_deref(),
+                winner_ata: ctx.accounts.winner_ata.as_deref(),
+                payment,
+                nft_token_program: ctx.accounts.nft_token_program.to_account_info(),
+                creator_accounts: ctx.remaining_accounts,
+                ata_program: ctx.accounts.ata_program.clone(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                pnft: &ctx.accounts.pnft,
+                cnft: Some(&ctx.accounts.cnft),
+                core: Some(&ctx.accounts.core),
+            },
+            bump,
+        )?;
+
+        // Close the auction PDA
+        let owner = &mut ctx.accounts.claimer;
+        let origin_lamports: u64 = owner.lamports();
+        **owner.lamports.borrow_mut() = origin_lamports + ctx.accounts.auction.as_ref().lamports();
+        **ctx.accounts.auction.as_ref().lamports.borrow_mut() = 0;
+
+        Ok(())
+    }
+
+    /**
+     * @dev Settle an auction that ended without a sale
+     * Returns the NFT to the seller and closes the escrow accounts, refunding
+     * their rent to the seller. Callable by the seller or any crank
+     */
+    pub fn settle_expired_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, SettleExpiredAuction<'info>>,
+        bump: u8,
+    ) -> Result<()> {
+        let auction = ctx.accounts.auction.load()?;
+        let timestamp: u64 = Clock::get()?.unix_timestamp as u64;
+
+        let end_time = if auction.auction_type == AUCTION_TYPE_SEALED {
+            auction.reveal_end_time
+        } else {
+            auction.end_time
+        };
+        if timestamp < end_time {
+            return Err(error!(AuctionError::NotEndedAuction));
+        }
+
+        if auction.bidder != Pubkey::default() {
+            return Err(error!(AuctionError::AuctionHasBid));
+        }
+
+        let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]];
+        let signer = &[&seeds[..]];
+
+        if auction.is_compressed() {
+            LeafTransfer {
+                leaf_owner: ctx.accounts.auction.to_account_info(),
+                new_owner: auction.seller,
+                receiver: ctx.accounts.seller.to_account_info(),
+                cnft: &ctx.accounts.cnft,
+                remaining_accounts: ctx.remaining_accounts,
+                        }
+            .transfer()?;
+        } else if auction.is_core() {
+            CoreTransfer {
+                asset: auction.nft_mint,
+                owner: ctx.accounts.global_authority.to_account_info(),
+                new_owner: auction.seller,
+                payer: ctx.accounts.payer.to_account_info(),
+                core: &ctx.accounts.core,
+            }
+            .transfer(signer)?;
+        } else {
+            return_nft_to_seller(
+                NftTransfer {
+                    programmable: auction.is_programmable(),
+                    source: required_account(&ctx.accounts.auction_ata)?.to_account_info(),
+                    source_owner: ctx.accounts.global_authority.to_account_info(),
+                    destination: required_account(&ctx.accounts.seller_nft_ata)?
+                        .to_account_info(),
+                    payer: ctx.accounts.payer.to_account_info(),
+                    token_program: ctx.accounts.nft_token_program.to_account_info(),
+                    pnft: &ctx.accounts.pnft,
+                },
+                ctx.accounts.seller.to_account_info(),
+                signer,
+            )?;
+        }
+
+        // Close the empty quote vault of the auction
+        if !auction.is_native() {
+            close_spl_account(
+                ctx.accounts.global_authority.to_account_info(),
+                required_account(&ctx.accounts.auction_vault)?.to_account_info(),
+                ctx.accounts.seller.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                signer,
+            )?;
+        }
+
+        // Close the auction PDA
+        let seller = &mut ctx.accounts.seller;
+        let origin_lamports: u64 = seller.lamports();
+        **seller.lamports.borrow_mut() = origin_lamports + ctx.accounts.auction.as_ref().lamports();
+        **ctx.accounts.auction.as_ref().lamports.borrow_mut() = 0;
+
+        Ok(())
+    }
+
+    /**
+     * @dev Buy the NFT instantly at the buy-now price of an English auction
+     * The current bidder is refunded and the sale settles in the same transaction
+     */
+    pub fn buy_now<'info>(ctx: Context<'_, '_, '_, 'info, BuyNow<'info>>, bump: u8) -> Result<()> {
+        let mut auction_data_info = ctx.accounts.auction.load_mut()?;
+        let timestamp: u64 = Clock::get()?.unix_timestamp as u64;
+
+        assert_not_paused(&ctx.accounts.global_authority, &auction_data_info)?;
+
+        if auction_data_info.auction_type != AUCTION_TYPE_ENGLISH
+            || auction_data_info.buy_now_price == 0
+        {
+            return Err(error!(AuctionError::BuyNowUnavailable));
+        }
+
+        // Assert Auction Already Ended
+        if auction_data_info.end_time < timestamp {
+            return Err(error!(AuctionError::EndedAuction));
+        }
+
+        // Buy-now closes once bids exceed the cutoff fraction of its price
+        if auction_data_info.current_bid as u128 * PERMYRIAD as u128
+            > auction_data_info.buy_now_price as u128
+                * auction_data_info.buy_now_cutoff_bps as u128
+        {
+            return Err(error!(AuctionError::BuyNowUnavailable));
+        }
+
+        // Assert OutBidder Address with the Last Bidder
+        if Pubkey::default() != auction_data_info.bidder
+            && ctx.accounts.out_bidder.key() != auction_data_info.bidder
+        {
+            return Err(error!(AuctionError::OutBidderMismatch));
+        }
+        if auction_data_info.has_bid_book != 0 && ctx.accounts.bid_book.is_none() {
+            return Err(error!(AuctionError::MissingBidBook));
+        }
+
+        // Refund Last Bidder Escrow
+        if !Pubkey::default().eq(&auction_data_info.bidder) {
+            if auction_data_info.is_native() {
+                transfer_from_owned_account(
+                    &mut ctx.accounts.auction.to_account_info(),
+                    &mut ctx.accounts.out_bidder.to_account_info(),
+                    auction_data_info.escrowed_bid,
+                )?;
+            } else {
+                quote_token(
+                    &ctx.accounts.quote_mint,
+                    &ctx.accounts.token_program,
+                    ctx.remaining_accounts,
+                )
+                .ok_or(AuctionError::MissingSettlementAccount)?
+                .transfer(
+                    ctx.accounts.global_authority.to_account_info(),
+                    required_account(&ctx.accounts.auction_vault)?.to_account_info(),
+                    required_account(&ctx.accounts.out_bidder_account)?.to_account_info(),
+                    auction_data_info.escrowed_bid,
+                    &[&[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]]],
+                )?;
+            }
+        }
+
+        // Escrow the buy-now price
+        if auction_data_info.is_native() {
+            // The system transfer needs the auction account unborrowed
+            let price = auction_data_info.buy_now_price;
+            drop(auction_data_info);
+            transfer_sol(
+                ctx.accounts.buyer.to_account_info(),
+                ctx.accounts.auction.to_account_info(),
+                price,
+                ctx.accounts.system_program.to_account_info(),
+            )?;
+            auction_data_info = ctx.accounts.auction.load_mut()?;
+        } else {
+            // The vault must hold the full price after any transfer fee
+            let quote = quote_token(
+                &ctx.accounts.quote_mint,
+                &ctx.accounts.token_program,
+                ctx.remaining_accounts,
+            )
+            .ok_or(AuctionError::MissingSettlementAccount)?;
+            quote.transfer(
+                ctx.accounts.buyer.to_account_info(),
+                required_account(&ctx.accounts.buyer_account)?.to_account_info(),
+                required_account(&ctx.accounts.auction_vault)?.to_account_info(),
+                quote.gross_amount(auction_data_info.buy_now_price)?,
+                &[],
+            )?;
+        }
+
+        auction_data_info.bidder = ctx.accounts.buyer.key();
+        auction_data_info.current_bid = auction_data_info.buy_now_price;
+        auction_data_info.escrowed_bid = auction_data_info.buy_now_price;
+
+        // The buyer ends the book as its leader, capped at the buy-now price
+        if let Some(bid_book) = ctx.accounts.bid_book.as_mut() {
+            bid_book.leader = ctx.accounts.buyer.key();
+            bid_book.max_bid = auction_data_info.buy_now_price;
+            bid_book.bid_count += 1;
+        }
+
+        // An escrowed cNFT is moved by its owner, the auction PDA, which Bubblegum
+        // can only borrow while the auction is read-only
+        drop(auction_data_info);
+        let auction_data_info = ctx.accounts.auction.load()?;
+
+        let payment = PaymentAccounts {
+            auction: ctx.accounts.auction.to_account_info(),
+            auction_vault: &ctx.accounts.auction_vault,
+            treasury: ctx.accounts.treasury.to_account_info(),
+            treasury_vault: &ctx.accounts.treasury_vault,
+            seller: &ctx.accounts.seller,
+            seller_ata: &ctx.accounts.seller_ata,
+            quote: quote_token(
+                &ctx.accounts.quote_mint,
+                &ctx.accounts.token_program,
+                ctx.remaining_accounts,
+            ),
+        }
+        .payment(&auction_data_info)?;
+        settle_auction(
+            &auction_data_info,
+            Settlement {
+                claimer: ctx.accounts.buyer.to_account_info(),
+                vault_rent_receiver: ctx.accounts.buyer.to_account_info(),
+                global_authority: ctx.accounts.global_authority.to_account_info(),
+                fee_percent: auction_data_info.fee_percent(&ctx.accounts.global_authority),
+                royalty_fallback: ctx.accounts.global_authority.royalty_fallback,
+                auction: ctx.accounts.auction.to_account_info(),
+                auction_ata: ctx.accounts.auction_ata.as_deref(),
+                winner_ata: ctx.accounts.winner_ata.as_deref(),
+                payment,
+                nft_token_program: ctx.accounts.nft_token_program.to_account_info(),
+                creator_accounts: ctx.remaining_accounts,
+                ata_program: ctx.accounts.ata_program.clone(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                pnft: &ctx.accounts.pnft,
+                cnft: Some(&ctx.accounts.cnft),
+                core: Some(&ctx.accounts.core),
+            },
+            bump,
+        )?;
+
+        // Close the auction PDA
+        let buyer = &mut ctx.accounts.buyer;
+        let origin_lamports: u64 = buyer.lamports();
+        **buyer.lamports.borrow_mut() = origin_lamports + ctx.accounts.auction.as_ref().lamports();
+        **ctx.accounts.auction.as_ref().lamports.borrow_mut() = 0;
+
+        Ok(())
+    }
+
+    /**
+     * @dev Commit a sealed bid
+     * The commitment is sha256(amount as u64 LE || salt || bidder) and the
+     * deposit is escrowed in a vault owned by the bid
+     */
+    pub fn commit_bid<'info>(
+        ctx: Context<'_, '_, '_, 'info, CommitBid<'info>>,
+        commitment: [u8; 32],
+        deposit: u64,
+    ) -> Result<()> {
+        let mut auction = ctx.accounts.auction.load_mut()?;
+        let timestamp: u64 = Clock::get()?.unix_timestamp as u64;
+
+        assert_not_paused(&ctx.accounts.global_authority, &auction)?;
+
+        if auction.auction_type != AUCTION_TYPE_SEALED {
+            return Err(error!(AuctionError::InvalidAuctionType));
+        }
+
+        // Assert Commit Phase Already Ended
+        if auction.end_time < timestamp {
+            return Err(error!(AuctionError::EndedAuction));
+        }
+
+        if deposit < auction.start_price {
+            return Err(error!(AuctionError::InsufficientDeposit));
+        }
+
+        let sealed_bid_key = ctx.accounts.sealed_bid.key();
+        let vault_bump = *ctx.bumps.get("bid_vault").unwrap();
+        create_token_vault(
+            ctx.accounts.bidder.to_account_info(),
+            ctx.accounts.bid_vault.to_account_info(),
+            ctx.accounts.quote_mint.to_account_info(),
+            ctx.accounts.global_authority.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            &[&[SEALED_BID_VAULT_SEED.as_bytes(), sealed_bid_key.as_ref(), &[vault_bump]]],
+        )?;
+
+        // Escrow the Bidder deposit, in full after any transfer fee
+        let quote = QuoteToken {
+            mint: &ctx.accounts.quote_mint,
+            token_program: ctx.accounts.token_program.to_account_info(),
+            hook_accounts: ctx.remaining_accounts,
+        };
+        quote.transfer(
+            ctx.accounts.bidder.to_account_info(),
+            ctx.accounts.bidder_account.to_account_info(),
+            ctx.accounts.bid_vault.to_account_info(),
+            quote.gross_amount(deposit)?,
+            &[],
+        )?;
+
+        let sealed_bid = &mut ctx.accounts.sealed_bid;
+        sealed_bid.auction = ctx.accounts.auction.key();
+        sealed_bid.auction_id = auction.auction_id;
+        sealed_bid.bidder = ctx.accounts.bidder.key();
+        sealed_bid.commitment = commitment;
+        sealed_bid.deposit = deposit;
+        sealed_bid.amount = 0;
+        sealed_bid.reveal_end_time = auction.reveal_end_time;
+        sealed_bid.revealed = false;
+        sealed_bid.bump = *ctx.bumps.get("sealed_bid").unwrap();
+        sealed_bid.vault_bump = vault_bump;
+
+        auction.commit_count += 1;
+
+        Ok(())
+    }
+
+    /**
+     * @dev Reveal a sealed bid
+     * Only bids covered by their deposit can lead the auction
+     */
+    pub fn reveal_bid(ctx: Context<RevealBid>, amount: u64, salt: [u8; 32]) -> Result<()> {
+        let mut auction = ctx.accounts.auction.load_mut()?;
+        let sealed_bid = &mut ctx.accounts.sealed_bid;
+        let timestamp: u64 = Clock::get()?.unix_timestamp as u64;
+
+        if auction.auction_type != AUCTION_TYPE_SEALED {
+            return Err(error!(AuctionError::InvalidAuctionType));
+        }
+
+        if timestamp < auction.end_time || auction.reveal_end_time <= timestamp {
+            return Err(error!(AuctionError::NotRevealPhase));
+        }
+
+        if sealed_bid.revealed {
+            return Err(error!(AuctionError::AlreadyRevealed));
+        }
+
+        let hash = hashv(&[
+            &amount.to_le_bytes(),
+            &salt,
+            ctx.accounts.bidder.key().as_ref(),
+        ]);
+        if hash.to_bytes() != sealed_bid.commitment {
+            return Err(error!(AuctionError::CommitmentMismatch));
+        }
+
+        sealed_bid.revealed = true;
+        sealed_bid.amount = amount;
+
+        // Underfunded bids stay out of the ranking and forfeit on withdrawal
+        if amount > sealed_bid.deposit || amount < auction.start_price {
+            return Ok(());
+        }
+
+        if amount > auction.current_bid {
+            auction.second_bid = auction.current_bid;
+            auction.current_bid = amount;
+            auction.bidder = ctx.accounts.bidder.key();
+        } else if amount > auction.second_bid {
+            auction.second_bid = amount;
+        }
+
+        Ok(())
+    }
+
+    /**
+     * @dev Settle a sealed-bid auction after the reveal phase
+     * The clearing price is taken from the winner's deposit, the rest of the
+     * deposit is refunded to the winner
+     */
+    pub fn claim_sealed_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimSealedAuction<'info>>,
+        bump: u8,
+    ) -> Result<()> {
+        let mut auction_data_info = ctx.accounts.auction.load_mut()?;
+        let timestamp: u64 = Clock::get()?.unix_timestamp as u64;
+
+        assert_not_paused(&ctx.accounts.global_authority, &auction_data_info)?;
+
+        if auction_data_info.auction_type != AUCTION_TYPE_SEALED {
+            return Err(error!(AuctionError::InvalidAuctionType));
+        }
+
+        // The claimer should be Last bidder or Seller
+        if ctx.accounts.claimer.key() != auction_data_info.bidder
+            && ctx.accounts.claimer.key() != auction_data_info.seller
+        {
+            return Err(error!(AuctionError::InvalidClaimer));
+        }
+
+        // The reveal phase should be ended before
+        if timestamp < auction_data_info.reveal_end_time {
+            return Err(error!(AuctionError::NotEndedAuction));
+        }
+
+        if auction_data_info.bidder == Pubkey::default() {
+            return Err(error!(AuctionError::InvalidWinner));
+        }
+
+        let price = if auction_data_info.settlement_type == SETTLE_SECOND_PRICE {
+            auction_data_info.second_bid.max(auction_data_info.start_price)
+        } else {
+            auction_data_info.current_bid
+        };
+
+        let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]];
+        let signer = &[&seeds[..]];
+        let quote = QuoteToken {
+            mint: &ctx.accounts.quote_mint,
+            token_program: ctx.accounts.token_program.to_account_info(),
+            hook_accounts: ctx.remaining_accounts,
+        };
+
+        // Refund the rest of the winner's deposit
+        let refund = ctx.accounts.winner_bid.deposit - price;
+        if refund != 0 {
+            quote.transfer(
+                ctx.accounts.global_authority.to_account_info(),
+                ctx.accounts.winner_bid_vault.to_account_info(),
+                ctx.accounts.winner_account.to_account_info(),
+                refund,
+                signer,
+            )?;
+        }
+
+        // The clearing price is paid out straight from the winner's bid vault,
+        // sparing it a transfer fee, so the unused auction vault is closed.
+        // Each vault's rent goes back to whoever paid it
+        close_spl_account(
+            ctx.accounts.global_authority.to_account_info(),
+            ctx.accounts.auction_vault.to_account_info(),
+            ctx.accounts.seller.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            signer,
+        )?;
+
+        auction_data_info.current_bid = price;
+
+        settle_auction(
+            &auction_data_info,
+            Settlement {
+                claimer: ctx.accounts.claimer.to_account_info(),
+                // The winner paid for their bid vault
+                vault_rent_receiver: ctx.accounts.winner.to_account_info(),
+                global_authority: ctx.accounts.global_authority.to_account_info(),
+                fee_percent: auction_data_info.fee_percent(&ctx.accounts.global_authority),
+                royalty_fallback: ctx.accounts.global_authority.royalty_fallback,
+                auction: ctx.accounts.auction.to_account_info(),
+                auction_ata: Some(&ctx.accounts.auction_ata),
+                winner_ata: Some(&ctx.accounts.winner_ata),
+                payment: Payment::Token {
+                    auction_vault: &ctx.accounts.winner_bid_vault,
+                    treasury_vault: &ctx.accounts.treasury_vault,
+                    seller_ata: &ctx.accounts.seller_ata,
+                    quote,
+                },
+                nft_token_program: ctx.accounts.nft_token_program.to_account_info(),
+                creator_accounts: ctx.remaining_accounts,
+                ata_program: ctx.accounts.ata_program.clone(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                pnft: &ctx.accounts.pnft,
+                cnft: None,
+                core: None,
+            },
+            bump,
+        )?;
+
+        // Close the auction PDA
+        let owner = &mut ctx.accounts.claimer;
+        let origin_lamports: u64 = owner.lamports();
+        **owner.lamports.borrow_mut() = origin_lamports + ctx.accounts.auction.as_ref().lamports();
+        **ctx.accounts.auction.as_ref().lamports.borrow_mut() = 0;
+
+        Ok(())
+    }
+
+    /**
+     * @dev Withdraw a losing sealed bid after the reveal phase
+     * Unrevealed and underfunded bids forfeit SEALED_FORFEIT_PERCENT of the deposit
+     */
+    pub fn withdraw_sealed_bid<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawSealedBid<'info>>,
+        bump: u8,
+    ) -> Result<()> {
+        let sealed_bid = &ctx.accounts.sealed_bid;
+        let timestamp: u64 = Clock::get()?.unix_timestamp as u64;
+
+        // Bids of a force cancelled auction are returned in full right away
+        let force_cancelled = ctx.accounts.force_cancel_record.is_some();
+        if !force_cancelled && timestamp < sealed_bid.reveal_end_time {
+            return Err(error!(AuctionError::NotEndedAuction));
+        }
+
+        // The winning deposit is released by claim_sealed_auction.
+        // Once the auction is closed or relisted every remaining bid is a losing one
+        let auction_info = ctx.accounts.auction.to_account_info();
+        if auction_info.lamports() != 0 && auction_info.owner == ctx.program_id {
+            let auction = AccountLoader::<AuctionPool>::try_from(&auction_info)?;
+            let auction = auction.load()?;
+            if auction.auction_id == sealed_bid.auction_id && auction.bidder == sealed_bid.bidder {
+                return Err(error!(AuctionError::WinnerCannotWithdraw));
+            }
+        }
+
+        let forfeit = if force_cancelled {
+            0
+        } else if !sealed_bid.revealed || sealed_bid.amount > sealed_bid.deposit {
+            sealed_bid.deposit * SEALED_FORFEIT_PERCENT / 100
+        } else {
+            0
+        };
+
+        let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]];
+        let signer = &[&seeds[..]];
+
+        let quote = QuoteToken {
+            mint: &ctx.accounts.quote_mint,
+            token_program: ctx.accounts.token_program.to_account_info(),
+            hook_accounts: ctx.remaining_accounts,
+        };
+
+        if forfeit != 0 {
+            quote.transfer(
+                ctx.accounts.global_authority.to_account_info(),
+                ctx.accounts.bid_vault.to_account_info(),
+                ctx.accounts.treasury_vault.to_account_info(),
+                forfeit,
+                signer,
+            )?;
+        }
+
+        quote.transfer(
+            ctx.accounts.global_authority.to_account_info(),
+            ctx.accounts.bid_vault.to_account_info(),
+            ctx.accounts.bidder_account.to_account_info(),
+            sealed_bid.deposit - forfeit,
+            signer,
+        )?;
+
+        quote.close(
+            ctx.accounts.global_authority.to_account_info(),
+            ctx.accounts.bid_vault.to_account_info(),
+            ctx.accounts.bidder.to_account_info(),
+            signer,
+        )?;
+
+        Ok(())
+    }
+}
+
+/**
+ * @dev Verifies the NFT metadata and escrows the NFT into the auction ATA
+ * Returns the verified collection (or first verified creator) of the NFT and
+ * its metadata. A Token-2022 NFT listed without Token Metadata has none, and
+ * its token group stands for the collection, or no collection outside a group
+ */
+fn list_nft(ctx: &Context<CreateAuction>) -> Result<(Pubkey, Option<Metadata>)> {
+    let auction_ata = &ctx.accounts.auction_ata;
+    let owner = &ctx.accounts.owner;
+    let owner_ata = &ctx.accounts.owner_ata;
+    let mint = &ctx.accounts.mint;
+    let nft_token_program = &ctx.accounts.nft_token_program;
+    let ata_program = &ctx.accounts.ata_program;
+    let system_program = &ctx.accounts.system_program;
+
+    let mint_info = mint.to_account_info();
+    let (_collection, nft_metadata) = match &ctx.accounts.mint_metadata {
+        Some(mint_metadata) => {
+            let (metadata, _) = Pubkey::find_program_address(
+                &[
+                    mpl_token_metadata::state::PREFIX.as_bytes(),
+                    mpl_token_metadata::id().as_ref(),
+                    mint.key().as_ref(),
+                ],
+                &mpl_token_metadata::id(),
+            );
+
+            if metadata != mint_metadata.key() {
+                return Err(error!(AuctionError::InvalidMetadata));
+            }
+            // verify metadata is legit
+            let nft_metadata = Metadata::from_account_info(mint_metadata)?;
+            let collection = verified_collection(&nft_metadata);
+            if collection == Pubkey::default() {
+                return Err(error!(AuctionError::MetadataCreatorParseError));
+            }
+            (collection, Some(nft_metadata))
+        }
+        None => {
+            if !has_token_metadata(&mint_info)? {
+                return Err(error!(AuctionError::MetadataCreatorParseError));
+            }
+            (token_group_of(&mint_info, ctx.accounts.nft_group.as_deref())?, None)
+        }
+    };
+
+    // The NFT must move 1:1 through plain transfers
+    for extension in [
+        EXTENSION_TRANSFER_FEE_CONFIG,
+        EXTENSION_NON_TRANSFERABLE,
+        EXTENSION_PERMANENT_DELEGATE,
+        EXTENSION_TRANSFER_HOOK,
+    ] {
+        if mint_extension(&mint_info, extension)?.is_some() {
+            return Err(error!(AuctionError::UnsupportedMintExtension));
+        }
+    }
+
+    check_eligibility(mint, owner_ata, nft_metadata.as_ref())?;
+
+    if auction_ata.to_account_info().data_is_empty() {
+        create_ata(
+            owner.to_account_info(),
+            ctx.accounts.global_authority.to_account_info(),
+            mint.to_account_info(),
+            auction_ata.to_account_info(),
+            nft_token_program.to_account_info(),
+            ata_program.to_account_info(),
+            system_program.to_account_info(),
+        )?;
+    } else {
+        // The ATA is shared by every auction of this mint, so a balance here
+        // means the NFT is still escrowed by another live auction
+        let escrowed =
+            TokenAccount::try_deserialize(&mut &auction_ata.to_account_info().data.borrow()[..])?;
+        if escrowed.amount != 0 {
+            return Err(error!(AuctionError::DuplicateAuction));
+        }
+    }
+    NftTransfer {
+        programmable: is_programmable(nft_metadata.as_ref()),
+        source: owner_ata.to_account_info(),
+        source_owner: owner.to_account_info(),
+        destination: auction_ata.to_account_info(),
+        payer: owner.to_account_info(),
+        token_program: nft_token_program.to_account_info(),
+        pnft: &ctx.accounts.pnft,
+    }
+    .transfer(&[])?;
+
+    Ok((_collection, nft_metadata))
+}
+
+/**
+ * @dev The token group a Token-2022 NFT is a member of, checked against the
+ * passed group mint. Token-2022 only adds members with the group's update
+ * authority signing, unlike the freely set metadata authorities.
+ * The default pubkey, for no collection, if the NFT isn't in a group
+ */
+fn token_group_of(mint: &AccountInfo, group: Option<&AccountInfo>) -> Result<Pubkey> {
+    let (group_address, member_number) = match token_group_member(mint)? {
+        Some(member) => member,
+        None => return Ok(Pubkey::default()),
+    };
+    let group = match group {
+        Some(group) if group.key() == group_address => group,
+        _ => return Err(error!(AuctionError::InvalidTokenGroup)),
+    };
+    // Members are numbered from 1 as the group grows
+    match token_group_size(group)? {
+        Some(size) if member_number != 0 && member_number <= size => Ok(group_address),
+        _ => Err(error!(AuctionError::InvalidTokenGroup)),
+    }
+}
+
+// The verified collection of the NFT, or its first verified creator
+fn verified_collection(metadata: &Metadata) -> Pubkey {
+    if let Some(collection) = &metadata.collection {
+        if collection.verified {
+            return collection.key;
+        }
+    }
+    if let Some(creators) = &metadata.data.creators {
+        for creator in creators.iter() {
+            if creator.verified {
+                return creator.address;
+            }
+        }
+    }
+    Pubkey::default()
+}
+
+// Whether the NFT is a pNFT, moved through Token Metadata
+fn is_programmable(metadata: Option<&Metadata>) -> bool {
+    metadata.is_some_and(|metadata| {
+        metadata.token_standard == Some(TokenStandard::ProgrammableNonFungible)
+    })
+}
+
+/**
+ * @dev Rejects mints that aren't a 1-of-1 NFT and owner ATAs the seller
+ * doesn't fully control. Token Metadata freezes pNFT ATAs with the edition,
+ * which is the only freeze authority allowed
+ */
+fn check_eligibility(
+    mint: &InterfaceAccount<Mint>,
+    owner_ata: &InterfaceAccount<TokenAccount>,
+    metadata: Option<&Metadata>,
+) -> Result<()> {
+    if mint.supply != 1 {
+        return Err(error!(AuctionError::InvalidNftSupply));
+    }
+    if mint.decimals != 0 {
+        return Err(error!(AuctionError::InvalidNftDecimals));
+    }
+
+    // Legacy NFTs were minted before token standards and have none
+    if matches!(
+        metadata.and_then(|metadata| metadata.token_standard),
+        Some(TokenStandard::Fungible) | Some(TokenStandard::FungibleAsset)
+    ) {
+        return Err(error!(AuctionError::NotNonFungible));
+    }
+
+    let (edition, _) = mpl_token_metadata::pda::find_master_edition_account(&mint.key());
+    if let Some(freeze_authority) = Option::<Pubkey>::from(mint.freeze_authority) {
+        if freeze_authority != edition {
+            return Err(error!(AuctionError::RiskyFreezeAuthority));
+        }
+    }
+
+    if owner_ata.is_frozen() && !is_programmable(metadata) {
+        return Err(error!(AuctionError::FrozenOwnerAta));
+    }
+    if owner_ata.delegate.is_some() {
+        return Err(error!(AuctionError::DelegatedOwnerAta));
+    }
+
+    Ok(())
+}
+
+/// Quote mint accounts of a new auction, left out for native SOL auctions
+pub struct QuoteListing<'a, 'info> {
+    pub auction: Pubkey,
+    // Pays the rent of the quote vault
+    pub payer: AccountInfo<'info>,
+    pub global_authority: AccountInfo<'info>,
+    pub auction_vault: &'a Option<UncheckedAccount<'info>>,
+    pub vault_bump: Option<u8>,
+    pub quote_mint: &'a Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub quote_mint_info: &'a Option<Box<Account<'info, QuoteMint>>>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'a, 'info> QuoteListing<'a, 'info> {
+    /**
+     * @dev Returns the whitelisted quote mint of a new auction, creating its
+     * quote vault, or the default pubkey for a native SOL auction listed
+     * without quote mint accounts
+     */
+    fn quote_mint(&self) -> Result<Pubkey> {
+        match (
+            self.quote_mint,
+            self.quote_mint_info,
+            self.auction_vault,
+            self.vault_bump,
+        ) {
+            (Some(quote_mint), Some(quote_mint_info), Some(auction_vault), Some(vault_bump))
+                if quote_mint_info.mint == quote_mint.key() =>
+            {
+                create_token_vault(
+                    self.payer.clone(),
+                    auction_vault.to_account_info(),
+                    quote_mint.to_account_info(),
+                    self.global_authority.clone(),
+                    self.token_program.clone(),
+                    self.system_program.clone(),
+                    &[&[AUCTION_VAULT_SEED.as_bytes(), self.auction.as_ref(), &[vault_bump]]],
+                )?;
+
+                Ok(quote_mint.key())
+            }
+            (None, None, None, _) => Ok(Pubkey::default()),
+            _ => Err(error!(AuctionError::InvalidQuoteMint)),
+        }
+    }
+}
+
+/**
+ * @dev Checks a new listing against its collection's registry entry and
+ * returns the collection's policy. The entry's PDA is always passed, so
+ * a seller can't skip the policy by leaving it out. Unregistered collections,
+ * and NFTs outside any collection, are only rejected in strict mode
+ */
+fn collection_policy(
+    global_authority: &GlobalPool,
+    collection_config: &AccountInfo,
+    collection: &Pubkey,
+    min_price: u64,
+    duration: u64,
+    quote_mint: &Pubkey,
+) -> Result<Option<CollectionPolicy>> {
+    // An NFT outside any collection has no config to follow
+    if *collection == Pubkey::default() {
+        if global_authority.strict_collections {
+            return Err(error!(AuctionError::CollectionNotRegistered));
+        }
+        return Ok(None);
+    }
+
+    let (config_address, _) = Pubkey::find_program_address(
+        &[COLLECTION_CONFIG_SEED.as_bytes(), collection.as_ref()],
+        &crate::ID,
+    );
+    if collection_config.key() != config_address {
+        return Err(error!(AuctionError::InvalidCollectionConfig));
+    }
+
+    if collection_config.data_is_empty() {
+        if global_authority.strict_collections {
+            return Err(error!(AuctionError::CollectionNotRegistered));
+        }
+        return Ok(None);
+    }
+
+    let config = Account::<CollectionConfig>::try_from(collection_config)?;
+    config.policy.check_listing(min_price, duration, quote_mint)?;
+
+    Ok(Some(config.policy.clone()))
+}
+
+/**
+ * @dev Anti-sniping rules of a new English auction. Without a choice from the
+ * seller, bids in the last min_duration_after_bid_secs extend the auction by
+ * as much, uncapped
+ */
+fn soft_close_of(
+    global_authority: &GlobalPool,
+    soft_close: Option<SoftClose>,
+    duration: u64,
+) -> Result<SoftClose> {
+    let soft_close = match soft_close {
+        Some(soft_close) => soft_close,
+        None => {
+            return Ok(SoftClose {
+                window_secs: global_authority.min_duration_after_bid_secs,
+                extension_secs: global_authority.min_duration_after_bid_secs,
+                max_extension_secs: 0,
+            })
+        }
+    };
+
+    let bounds = global_authority.min_soft_close_secs..=global_authority.max_soft_close_secs;
+    if !bounds.contains(&soft_close.window_secs)
+        || !bounds.contains(&soft_close.extension_secs)
+        || soft_close.window_secs > duration
+    {
+        return Err(error!(AuctionError::InvalidSoftClose));
+    }
+
+    Ok(soft_close)
+}
+
+// Rejects a new listing while paused or lasting outside one to 14 days
+fn check_listing(global_authority: &GlobalPool, duration: u64) -> Result<()> {
+    if global_authority.paused {
+        return Err(error!(AuctionError::ProgramPaused));
+    }
+
+    let day = global_authority.day;
+    if !(day..=14 * day).contains(&duration) {
+        return Err(error!(AuctionError::InvalidDuration));
+    }
+
+    Ok(())
+}
+
+/// What every new auction records of its listing
+struct Listing {
+    seller: Pubkey,
+    nft_mint: Pubkey,
+    nft_collection: Pubkey,
+    quote_mint: Pubkey,
+    start_price: u64,
+    duration: u64,
+    nonce: u64,
+    bump: u8,
+    vault_bump: u8,
+}
+
+impl Listing {
+    /**
+     * @dev Fills the fields every new auction shares under the next auction id,
+     * starting it now without a bid
+     */
+    fn init(&self, auction: &mut AuctionPool, global_authority: &mut GlobalPool) -> Result<()> {
+        let cur_time: u64 = Clock::get()?.unix_timestamp as u64;
+
+        auction.auction_id = global_authority.next_auction_id();
+        auction.seller = self.seller;
+        auction.nft_mint = self.nft_mint;
+        auction.nft_collection = self.nft_collection;
+        auction.quote_mint = self.quote_mint;
+
+        auction.start_time = cur_time;
+        auction.set_end_time(cur_time + self.duration);
+        auction.start_price = self.start_price;
+
+        auction.bidder = Pubkey::default();
+        auction.current_bid = 0;
+
+        auction.nonce = self.nonce;
+        auction.bump = self.bump;
+        auction.vault_bump = self.vault_bump;
+
+        Ok(())
+    }
+}
+
+/// The checked terms of a new English auction
+struct EnglishListing {
+    buy_now_price: u64,
+    buy_now_cutoff_bps: u16,
+    reserve_hash: [u8; 32],
+    soft_close: SoftClose,
+}
+
+impl EnglishListing {
+    /**
+     * @dev Checks the terms of a new English auction. Buy-now must be above
+     * the start price and stays open until bids reach buy_now_cutoff_bps of it
+     */
+    fn new(
+        global_authority: &GlobalPool,
+        start_price: u64,
+        duration: u64,
+        terms: EnglishTerms,
+    ) -> Result<Self> {
+        if start_price == 0 {
+            return Err(error!(AuctionError::InvalidBidFloor));
+        }
+
+        let buy_now_price = terms.buy_now_price.unwrap_or(0);
+        if buy_now_price != 0
+            && (buy_now_price <= start_price || terms.buy_now_cutoff_bps as u64 > PERMYRIAD)
+        {
+            return Err(error!(AuctionError::InvalidBuyNowPrice));
+        }
+
+        Ok(Self {
+            buy_now_price,
+            buy_now_cutoff_bps: terms.buy_now_cutoff_bps,
+            reserve_hash: terms.reserve_hash.unwrap_or_default(),
+            soft_close: soft_close_of(global_authority, terms.soft_close, duration)?,
+        })
+    }
+
+    // Makes the new auction an English one on these terms
+    fn apply(&self, auction: &mut AuctionPool) {
+        auction.auction_type = AUCTION_TYPE_ENGLISH;
+        auction.set_soft_close(&self.soft_close);
+        auction.buy_now_price = self.buy_now_price;
+        auction.buy_now_cutoff_bps = self.buy_now_cutoff_bps;
+        // sha256(reserve_price as u64 LE || salt || seller), revealed in claim_auction
+        auction.reserve_hash = self.reserve_hash;
+    }
+}
+
+// The quote mint of an auction listing an SPL NFT
+fn quote_mint_of(ctx: &Context<CreateAuction>) -> Result<Pubkey> {
+    QuoteListing {
+        auction: ctx.accounts.auction.key(),
+        payer: ctx.accounts.owner.to_account_info(),
+        global_authority: ctx.accounts.global_authority.to_account_info(),
+        auction_vault: &ctx.accounts.auction_vault,
+        vault_bump: ctx.bumps.get("auction_vault").copied(),
+        quote_mint: &ctx.accounts.quote_mint,
+        quote_mint_info: &ctx.accounts.quote_mint_info,
+        token_program: ctx.accounts.token_program.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    }
+    .quote_mint()
+}
+
+/// Accounts needed to pay out a sale and release the NFT to the winner
+pub struct Settlement<'a, 'info> {
+    // Receives the rent of the closed escrow accounts
+    pub claimer: AccountInfo<'info>,
+    // Receives the rent of the closed quote vault
+    pub vault_rent_receiver: AccountInfo<'info>,
+    pub global_authority: AccountInfo<'info>,
+    // Percentage of the sale paid to the treasury
+    pub fee_percent: u64,
+    // Who takes unpaid royalties and rounding dust: seller or treasury
+    pub royalty_fallback: u8,
+    // The auction PDA, owning an escrowed cNFT
+    pub auction: AccountInfo<'info>,
+    // The NFT ATAs of the auction and the winner, unused by cNFT auctions
+    pub auction_ata: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub winner_ata: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub payment: Payment<'a, 'info>,
+    pub nft_token_program: AccountInfo<'info>,
+    // The Creators' quote token ATAs, or their wallets in native SOL auctions,
+    // in any order. A missing ATA is created when its creator's wallet is passed too
+    pub creator_accounts: &'a [AccountInfo<'info>],
+    // Only required to create missing creator ATAs
+    pub ata_program: Option<AccountInfo<'info>>,
+    pub system_program: AccountInfo<'info>,
+    // Token Metadata accounts moving a pNFT to the winner
+    pub pnft: &'a PnftAccounts<'info>,
+    // Bubblegum accounts moving a cNFT to the winner
+    pub cnft: Option<&'a CnftAccounts<'info>>,
+    // Core accounts moving a Core asset to the winner
+    pub core: Option<&'a CoreAccounts<'info>>,
+}
+
+/// Where the winning bid is escrowed and where the fee and the seller's share go
+pub enum Payment<'a, 'info> {
+    // Quote tokens held by the auction vault
+    Token {
+        auction_vault: &'a InterfaceAccount<'info, TokenAccount>,
+        treasury_vault: &'a InterfaceAccount<'info, TokenAccount>,
+        seller_ata: &'a InterfaceAccount<'info, TokenAccount>,
+        quote: QuoteToken<'a, 'info>,
+    },
+    // Lamports held by the auction PDA on top of its rent
+    Native {
+        auction: AccountInfo<'info>,
+        treasury: AccountInfo<'info>,
+        seller: AccountInfo<'info>,
+    },
+}
+
+impl<'a, 'info> Payment<'a, 'info> {
+    /**
+     * @dev Pays amount out of the escrow to a quote token account, or to a
+     * wallet in native SOL auctions
+     */
+    fn pay(
+        &self,
+        to: AccountInfo<'info>,
+        amount: u64,
+        global_authority: AccountInfo<'info>,
+        signer: &[&[&[u8]]],
+    ) -> Result<()> {
+        match self {
+            Payment::Token {
+                auction_vault,
+                quote,
+                ..
+            } => quote.transfer(
+                global_authority,
+                auction_vault.to_account_info(),
+                to,
+                amount,
+                signer,
+            ),
+            Payment::Native { auction, .. } => {
+                transfer_from_owned_account(&mut auction.clone(), &mut to.clone(), amount)
+            }
+        }
+    }
+}
+
+/// Escrow and payout accounts of a sale, each only required by its payment path
+pub struct PaymentAccounts<'a, 'info> {
+    pub auction: AccountInfo<'info>,
+    pub auction_vault: &'a Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub treasury: AccountInfo<'info>,
+    pub treasury_vault: &'a Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub seller: &'a Option<SystemAccount<'info>>,
+    pub seller_ata: &'a Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub quote: Option<QuoteToken<'a, 'info>>,
+}
+
+impl<'a, 'info> PaymentAccounts<'a, 'info> {
+    /**
+     * @dev Picks the escrow of the auction, checking the accounts its payment
+     * path requires were passed
+     */
+    fn payment(self, auction: &AuctionPool) -> Result<Payment<'a, 'info>> {
+        if auction.is_native() {
+            let seller = self
+                .seller
+                .as_ref()
+                .ok_or(AuctionError::MissingSettlementAccount)?;
+            return Ok(Payment::Native {
+                auction: self.auction,
+                treasury: self.treasury,
+                seller: seller.to_account_info(),
+            });
+        }
+
+        Ok(Payment::Token {
+            auction_vault: required_account(self.auction_vault)?,
+            treasury_vault: required_account(self.treasury_vault)?,
+            seller_ata: required_account(self.seller_ata)?,
+            quote: self.quote.ok_or(AuctionError::MissingSettlementAccount)?,
+        })
+    }
+}
+
+/// A quote token, moved with transfer_checked so Token-2022 mints work too
+pub struct QuoteToken<'a, 'info> {
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub token_program: AccountInfo<'info>,
+    // Extra accounts of the mint's transfer hook, from the remaining accounts
+    pub hook_accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> QuoteToken<'a, 'info> {
+    /**
+     * @dev Transfers amount of the quote token, forwarding the accounts of a
+     * transfer hook. Any transfer fee is withheld from what the receiver gets
+     */
+    fn transfer(
+        &self,
+        authority: AccountInfo<'info>,
+        from: AccountInfo<'info>,
+        to: AccountInfo<'info>,
+        amount: u64,
+        signer: &[&[&[u8]]],
+    ) -> Result<()> {
+        let mint = self.mint.to_account_info();
+        let mut transfer_ix = spl_token_2022::instruction::transfer_checked(
+            &self.token_program.key(),
+            &from.key(),
+            &mint.key(),
+            &to.key(),
+            &authority.key(),
+            &[],
+            amount,
+            self.mint.decimals,
+        )?;
+        let mut account_infos = vec![from, mint.clone(), to, authority, self.token_program.clone()];
+        if mint_extension(&mint, EXTENSION_TRANSFER_HOOK)?.is_some() {
+            for account in self.hook_accounts {
+                transfer_ix.accounts.push(if account.is_writable {
+                    AccountMeta::new(account.key(), false)
+                } else {
+                    AccountMeta::new_readonly(account.key(), false)
+                });
+                account_infos.push(account.clone());
+            }
+        }
+
+        invoke_signed(&transfer_ix, &account_infos, signer)?;
+
+        Ok(())
+    }
+
+    /**
+     * @dev Amount to send so that net arrives after the mint's transfer fee
+     */
+    fn gross_amount(&self, net: u64) -> Result<u64> {
+        let Some(data) =
+            mint_extension(&self.mint.to_account_info(), EXTENSION_TRANSFER_FEE_CONFIG)?
+        else {
+            return Ok(net);
+        };
+        let config = bytemuck::try_from_bytes::<TransferFeeConfig>(&data)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        let fee = config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, net)
+            .ok_or(ProgramError::InvalidArgument)?;
+
+        Ok(net + fee)
+    }
+
+    /**
+     * @dev Closes an empty quote token account, first harvesting the fees
+     * withheld in it to the mint as Token-2022 requires
+     */
+    fn close(
+        &self,
+        authority: AccountInfo<'info>,
+        account: AccountInfo<'info>,
+        destination: AccountInfo<'info>,
+        signer: &[&[&[u8]]],
+    ) -> Result<()> {
+        let mint = self.mint.to_account_info();
+        if mint_extension(&mint, EXTENSION_TRANSFER_FEE_CONFIG)?.is_some() {
+            invoke(
+                &harvest_withheld_tokens_to_mint(
+                    &self.token_program.key(),
+                    &mint.key(),
+                    &[&account.key()],
+                )?,
+                &[mint, account.clone(), self.token_program.clone()],
+            )?;
+        }
+
+        close_spl_account(authority, account, destination, self.token_program.clone(), signer)
+    }
+}
+
+// Builds the quote token of a token auction, None without a quote mint
+fn quote_token<'a, 'info>(
+    quote_mint: &'a Option<Box<InterfaceAccount<'info, Mint>>>,
+    token_program: &Interface<'info, TokenInterface>,
+    hook_accounts: &'a [AccountInfo<'info>],
+) -> Option<QuoteToken<'a, 'info>> {
+    quote_mint.as_ref().map(|mint| QuoteToken {
+        mint,
+        token_program: token_program.to_account_info(),
+        hook_accounts,
+    })
+}
+
+/**
+ * @dev Pays the fee, the royalties and the seller from the auction escrow,
+ * sends the NFT to the bidder and closes the escrow token accounts
+ */
+fn settle_auction(auction: &AuctionPool, accounts: Settlement, bump: u8) -> Result<()> {
+    // Winner ATA's owner should be the last bidder
+    // The NFT should be sent to the winner's wallet so this check is necessary
+    // cNFTs and Core assets are checked to go to the bidder when transferred
+    if !auction.is_compressed()
+        && !auction.is_core()
+        && settlement_account(accounts.winner_ata)?.owner != auction.bidder
+    {
+        return Err(error!(AuctionError::InvalidWinner));
+    }
+
+    // Seller ATA's owner should be the auction creator
+    // The quote token should be sent to the auction creator so this check is necessary
+    let seller = match &accounts.payment {
+        Payment::Token { seller_ata, .. } => seller_ata.owner,
+        Payment::Native { seller, .. } => seller.key(),
+    };
+    if seller != auction.seller {
+        return Err(error!(AuctionError::InvalidSeller));
+    }
+
+    // Royalties come from the snapshot taken at listing, not the live metadata
+    let creator_count = auction.creator_count as usize;
+    let creators = &auction.creators[..creator_count];
+    let creator_shares = &auction.creator_shares[..creator_count];
+
+    // Share Fee to distribute to creators
+    let total_share_fee = portion(
+        auction.current_bid,
+        auction.seller_fee_basis_points as u64,
+        PERMYRIAD,
+    )?;
+
+    // Auction Fee to the treasury
+    let auction_fee = portion(auction.current_bid, accounts.fee_percent, 100)?;
+
+    let nft_token_program = &accounts.nft_token_program;
+    let global_authority = &accounts.global_authority;
+    let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]];
+    let signer = &[&seeds[..]];
+
+    // Creator accounts are matched by address, so their order doesn't matter.
+    // Every creator with a share must be passed, and the wallet too when its ATA is missing
+    let remaining_accounts = accounts.creator_accounts;
+    // A lamport payout must leave a new creator wallet rent-exempt
+    let min_wallet_balance = Rent::get()?.minimum_balance(0);
+    let mut paid_share_fee: u64 = 0;
+    for (creator, share) in creators.iter().zip(creator_shares.iter()) {
+        if *share == 0 {
+            continue;
+        }
+        let share_amount = portion(total_share_fee, *share as u64, 100)?;
+
+        let creator_account = match &accounts.payment {
+            Payment::Native { .. } => {
+                let creator_wallet = find_account(remaining_accounts, creator)?;
+                // Too small a payout can't fund a new wallet, so it goes to the fallback
+                if creator_wallet.lamports().saturating_add(share_amount) < min_wallet_balance {
+                    continue;
+                }
+                creator_wallet
+            }
+            Payment::Token { quote, .. } => {
+                let creator_ata = find_account(
+                    remaining_accounts,
+                    &spl_associated_token_account::get_associated_token_address_with_program_id(
+                        creator,
+                        &auction.quote_mint,
+                        &quote.token_program.key(),
+                    ),
+                )?;
+                if creator_ata.data_is_empty() {
+                    let creator_wallet = find_account(remaining_accounts, creator)?;
+                    create_ata_idempotent(
+                        accounts.claimer.clone(),
+                        creator_wallet.clone(),
+                        quote.mint.to_account_info(),
+                        creator_ata.clone(),
+                        quote.token_program.clone(),
+                        accounts
+                            .ata_program
+                            .clone()
+                            .ok_or(AuctionError::MissingSettlementAccount)?,
+                        accounts.system_program.clone(),
+                    )?;
+                }
+                creator_ata
+            }
+        };
+
+        // Distribute quote tokens to the Creator's wallets
+        accounts.payment.pay(
+            creator_account.clone(),
+            share_amount,
+            global_authority.clone(),
+            signer,
+        )?;
+        paid_share_fee = paid_share_fee
+            .checked_add(share_amount)
+            .ok_or(AuctionError::MathOverflow)?;
+    }
+
+    // The escrow only holds this auction's bid, so the seller takes everything
+    // left after the fee and the royalties
+    let seller_amount = auction
+        .current_bid
+        .checked_sub(total_share_fee)
+        .and_then(|amount| amount.checked_sub(auction_fee))
+        .ok_or(AuctionError::MathOverflow)?;
+
+    // Rounding dust and lamport payouts below rent go to the fallback
+    let royalty_remainder = total_share_fee
+        .checked_sub(paid_share_fee)
+        .ok_or(AuctionError::MathOverflow)?;
+    let (auction_fee, seller_amount) =
+        if accounts.royalty_fallback == ROYALTY_FALLBACK_TREASURY {
+            (auction_fee.checked_add(royalty_remainder), Some(seller_amount))
+        } else {
+            (Some(auction_fee), seller_amount.checked_add(royalty_remainder))
+        };
+    let auction_fee = auction_fee.ok_or(AuctionError::MathOverflow)?;
+    let seller_amount = seller_amount.ok_or(AuctionError::MathOverflow)?;
+
+    let (fee_account, seller_account) = match &accounts.payment {
+        Payment::Token {
+            treasury_vault,
+            seller_ata,
+            ..
+        } => (treasury_vault.to_account_info(), seller_ata.to_account_info()),
+        Payment::Native {
+            treasury,
+            seller,
+            ..
+        } => (treasury.clone(), seller.clone()),
+    };
+
+    // Transfer the fee to the treasury
+    accounts.payment.pay(
+        fee_account,
+        auction_fee,
+        global_authority.clone(),
+        signer,
+    )?;
+
+    // Transfer quote tokens to the seller 100% - fee - royalties
+    accounts.payment.pay(
+        seller_account,
+        seller_amount,
+        global_authority.clone(),
+        signer,
+    )?;
+    // Close the quote vault of the auction
+    if let Payment::Token {
+        auction_vault,
+        quote,
+        ..
+    } = &accounts.payment
+    {
+        quote.close(
+            global_authority.clone(),
+            auction_vault.to_account_info(),
+            accounts.vault_rent_receiver.clone(),
+            signer,
+        )?;
+    }
+
+    // Transfer a cNFT to the winner, closing its compressed asset
+    if auction.is_compressed() {
+        LeafTransfer {
+            leaf_owner: accounts.auction,
+            new_owner: auction.bidder,
+            receiver: accounts.claimer.clone(),
+            cnft: accounts.cnft.ok_or(AuctionError::MissingCnftAccount)?,
+            remaining_accounts,
+        }
+        .transfer()?;
+
+        return Ok(());
+    }
+
+    // Transfer a Core asset to the winner
+    if auction.is_core() {
+        CoreTransfer {
+            asset: auction.nft_mint,
+            owner: global_authority.clone(),
+            new_owner: auction.bidder,
+            payer: accounts.claimer.clone(),
+            core: accounts.core.ok_or(AuctionError::MissingCoreAccount)?,
+        }
+        .transfer(signer)?;
+
+        return Ok(());
+    }
+
+    // Transfer NFT to the winner
+    let auction_ata = settlement_account(accounts.auction_ata)?;
+    NftTransfer {
+        programmable: auction.is_programmable(),
+        source: auction_ata.to_account_info(),
+        source_owner: global_authority.clone(),
+        destination: settlement_account(accounts.winner_ata)?.to_account_info(),
+        payer: accounts.claimer.clone(),
+        token_program: nft_token_program.clone(),
+        pnft: accounts.pnft,
+    }
+    .transfer(signer)?;
+    // Close NFT account of the auction PDA
+    close_spl_account(
+        accounts.global_authority.to_account_info(),
+        auction_ata.to_account_info(),
+        accounts.claimer.to_account_info(),
+        nft_token_program.clone(),
+        &[&[GLOBAL_AUTHORITY_SEED.as_bytes(), &[bump]]],
+    )?;
+
+    Ok(())
+}
+
+/// An NFT transfer, through Token Metadata for programmable NFTs
+pub struct NftTransfer<'a, 'info> {
+    pub programmable: bool,
+    pub source: AccountInfo<'info>,
+    // Owner of the source, signing the transfer
+    pub source_owner: AccountInfo<'info>,
+    pub destination: AccountInfo<'info>,
+    // Pays for the destination token record of a pNFT
+    pub payer: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub pnft: &'a PnftAccounts<'info>,
+}
+
+impl<'a, 'info> NftTransfer<'a, 'info> {
+    /**
+     * @dev Moves the NFT with a plain token transfer, or with Token Metadata's
+     * Transfer for pNFTs so their token records and rule set are enforced
+     */
+    fn transfer(&self, signer: &[&[&[u8]]]) -> Result<()> {
+        if !self.programmable {
+            return transfer_spl(
+                self.source_owner.clone(),
+                self.source.clone(),
+                self.destination.clone(),
+                1,
+                self.token_program.clone(),
+                signer,
+            );
+        }
+
+        let pnft = self.pnft;
+        let destination_owner = pnft_account(&pnft.destination_owner)?;
+        let mint = pnft_account(&pnft.mint)?;
+        let metadata = pnft_account(&pnft.metadata)?;
+        let edition = pnft_account(&pnft.edition)?;
+        let owner_token_record = pnft_account(&pnft.owner_token_record)?;
+        let destination_token_record = pnft_account(&pnft.destination_token_record)?;
+        let sysvar_instructions = pnft_account(&pnft.sysvar_instructions)?;
+        let token_metadata_program = pnft_account(&pnft.token_metadata_program)?;
+        let ata_program = pnft_account(&pnft.ata_program)?;
+        let system_program = pnft
+            .system_program
+            .as_ref()
+            .ok_or(AuctionError::MissingPnftAccount)?
+            .to_account_info();
+
+        let mut builder = TransferBuilder::new();
+        builder
+            .token(self.source.key())
+            .token_owner(self.source_owner.key())
+            .destination(self.destination.key())
+            .destination_owner(destination_owner.key())
+            .mint(mint.key())
+            .metadata(metadata.key())
+            .edition(edition.key())
+            .owner_token_record(owner_token_record.key())
+            .destination_token_record(destination_token_record.key())
+            .authority(self.source_owner.key())
+            .payer(self.payer.key())
+            .system_program(system_program.key())
+            .sysvar_instructions(sysvar_instructions.key())
+            .spl_token_program(self.token_program.key())
+            .spl_ata_program(ata_program.key());
+        let mut account_infos = vec![
+            self.source.clone(),
+            self.source_owner.clone(),
+            self.destination.clone(),
+            destination_owner,
+            mint,
+            metadata,
+            edition,
+            owner_token_record,
+            destination_token_record,
+            self.payer.clone(),
+            system_program,
+            sysvar_instructions,
+            self.token_program.clone(),
+            ata_program,
+            token_metadata_program,
+        ];
+        // pNFTs without a rule set are transferred without one
+        if let (Some(auth_rules), Some(auth_rules_program)) =
+            (&pnft.auth_rules, &pnft.auth_rules_program)
+        {
+            builder
+                .authorization_rules(auth_rules.key())
+                .authorization_rules_program(auth_rules_program.key());
+            account_infos.push(auth_rules.to_account_info());
+            account_infos.push(auth_rules_program.to_account_info());
+        }
+        let transfer_ix = builder
+            .build(TransferArgs::V1 {
+                amount: 1,
+                authorization_data: None,
+            })
+            .map_err(|_| error!(AuctionError::MissingPnftAccount))?
+            .instruction();
+
+        invoke_signed(&transfer_ix, &account_infos, signer)?;
+
+        Ok(())
+    }
+}
+
+/**
+ * @dev Sends the escrowed NFT back to the seller and closes the auction ATA
+ */
+fn return_nft_to_seller<'info>(
+    nft: NftTransfer<'_, 'info>,
+    receiver: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    nft.transfer(signer_seeds)?;
+    // Token Metadata leaves the source of a pNFT thawed, so it can be closed too
+    close_spl_account(
+        nft.source_owner,
+        nft.source,
+        receiver,
+        nft.token_program,
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
+
+/// A cNFT transfer through Bubblegum, out of the auction PDA's custody
+pub struct LeafTransfer<'a, 'info> {
+    // The auction PDA owning the leaf, signing the transfer
+    pub leaf_owner: AccountInfo<'info>,
+    // The wallet the leaf must go to
+    pub new_owner: Pubkey,
+    // Receives the rent of the closed compressed asset
+    pub receiver: AccountInfo<'info>,
+    pub cnft: &'a CnftAccounts<'info>,
+    // The remaining accounts, ending with the merkle proof of the leaf
+    pub remaining_accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> LeafTransfer<'a, 'info> {
+    /**
+     * @dev Transfers the escrowed leaf to new_owner and closes its compressed
+     * asset. Bubblegum rejects a proof that doesn't match the tree's current root
+     */
+    fn transfer(&self) -> Result<()> {
+        let cnft = self.cnft;
+        let compressed_asset = cnft
+            .compressed_asset
+            .as_ref()
+            .ok_or(AuctionError::MissingCnftAccount)?;
+        let merkle_tree = cnft_account(&cnft.merkle_tree)?;
+        let new_leaf_owner = cnft_account(&cnft.new_leaf_owner)?;
+        if compressed_asset.auction != self.leaf_owner.key()
+            || compressed_asset.merkle_tree != merkle_tree.key()
+            || new_leaf_owner.key() != self.new_owner
+        {
+            return Err(error!(AuctionError::InvalidCompressedNft));
+        }
+
+        let proof_len = compressed_asset.proof_len as usize;
+        if self.remaining_accounts.len() < proof_len {
+            return Err(error!(AuctionError::MissingCnftAccount));
+        }
+        let proof = &self.remaining_accounts[self.remaining_accounts.len() - proof_len..];
+
+        // The auction may be closed by a force cancel, but its PDA still signs
+        let asset_id = bubblegum::get_asset_id(&compressed_asset.merkle_tree, compressed_asset.nonce);
+        let auction_nonce = compressed_asset.auction_nonce.to_le_bytes();
+        let seeds = &[
+            AUCTION_SEED.as_bytes(),
+            compressed_asset.seller.as_ref(),
+            asset_id.as_ref(),
+            auction_nonce.as_ref(),
+            &[compressed_asset.auction_bump],
+        ];
+
+        bubblegum::transfer(
+            bubblegum::TransferAccounts {
+                tree_authority: cnft_account(&cnft.tree_authority)?,
+                leaf_owner: self.leaf_owner.clone(),
+                // Bubblegum clears the delegate when the leaf is escrowed
+                leaf_delegate: self.leaf_owner.clone(),
+                new_leaf_owner,
+                merkle_tree,
+                log_wrapper: cnft_account(&cnft.log_wrapper)?,
+                compression_program: cnft_account(&cnft.compression_program)?,
+                system_program: cnft
+                    .system_program
+                    .as_ref()
+                    .ok_or(AuctionError::MissingCnftAccount)?
+                    .to_account_info(),
+                bubblegum_program: cnft_account(&cnft.bubblegum_program)?,
+            },
+            compressed_asset,
+            proof,
+            &[&seeds[..]],
+        )?;
+
+        compressed_asset.close(self.receiver.clone())
+    }
+}
+
+/// A Core asset transfer through Metaplex Core, out of the global authority's custody
+pub struct CoreTransfer<'a, 'info> {
+    // The escrowed asset
+    pub asset: Pubkey,
+    // The global authority owning the asset, signing the transfer
+    pub owner: AccountInfo<'info>,
+    // The wallet the asset must go to
+    pub new_owner: Pubkey,
+    // Signer paying for the transfer
+    pub payer: AccountInfo<'info>,
+    pub core: &'a CoreAccounts<'info>,
+}
+
+impl<'a, 'info> CoreTransfer<'a, 'info> {
+    /**
+     * @dev Transfers the escrowed asset to new_owner. Core rejects a collection
+     * that isn't the asset's, and its plugins may still block the transfer
+     */
+    fn transfer(&self, signer: &[&[&[u8]]]) -> Result<()> {
+        let core = self.core;
+        let asset = core_account(&core.asset)?;
+        let new_owner = core_account(&core.new_owner)?;
+        if asset.key() != self.asset || new_owner.key() != self.new_owner {
+            return Err(error!(AuctionError::InvalidCoreAsset));
+        }
+
+        mpl_core::transfer(
+            mpl_core::TransferAccounts {
+                asset,
+                collection: core_account(&core.collection)?,
+                payer: self.payer.clone(),
+                authority: self.owner.clone(),
+                new_owner,
+                system_program: core
+                    .system_program
+                    .as_ref()
+                    .ok_or(AuctionError::MissingCoreAccount)?
+                    .to_account_info(),
+                core_program: core_account(&core.core_program)?,
+            },
+            signer,
+        )
+    }
+}
+
+// Rejects bids and settlements while the program is paused or the auction frozen
+fn assert_not_paused(global_authority: &GlobalPool, auction: &AuctionPool) -> Result<()> {
+    if global_authority.paused {
+        return Err(error!(AuctionError::ProgramPaused));
+    }
+    if auction.frozen != 0 {
+        return Err(error!(AuctionError::AuctionFrozen));
+    }
+
+    Ok(())
+}
+
+// amount * numerator / denominator, computed in u128 so the product can't overflow
+fn portion(amount: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    u64::try_from(amount as u128 * numerator as u128 / denominator as u128)
+        .map_err(|_| error!(AuctionError::MathOverflow))
+}
+
+// Finds the remaining account of a creator with the given address
+fn find_account<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+    key: &Pubkey,
+) -> Result<&'a AccountInfo<'info>> {
+    accounts
+        .iter()
+        .find(|account| account.key == key)
+        .ok_or_else(|| error!(AuctionError::MissingCreatorAccount))
+}
+
+// Unwraps an optional account that a pNFT transfer requires
+fn pnft_account<'info>(account: &Option<UncheckedAccount<'info>>) -> Result<AccountInfo<'info>> {
+    match account {
+        Some(account) => Ok(account.to_account_info()),
+        None => Err(error!(AuctionError::MissingPnftAccount)),
+    }
+}
+
+// Unwraps an optional account that a cNFT transfer requires
+fn cnft_account<'info>(account: &Option<UncheckedAccount<'info>>) -> Result<AccountInfo<'info>> {
+    match account {
+        Some(account) => Ok(account.to_account_info()),
+        None => Err(error!(AuctionError::MissingCnftAccount)),
+    }
+}
+
+// Unwraps an optional account that a Core transfer requires
+fn core_account<'info>(account: &Option<UncheckedAccount<'info>>) -> Result<AccountInfo<'info>> {
+    match account {
+        Some(account) => Ok(account.to_account_info()),
+        None => Err(error!(AuctionError::MissingCoreAccount)),
+    }
+}
+
+// Unwraps an NFT ATA of a settlement, only left out in cNFT and Core auctions
+fn settlement_account<'a, 'info>(
+    account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+) -> Result<&'a InterfaceAccount<'info, TokenAccount>> {
+    account.ok_or_else(|| error!(AuctionError::MissingSettlementAccount))
+}
+
+// Unwraps an optional account that the current path requires
+fn required_account<'a, 'info>(
+    account: &'a Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+) -> Result<&'a InterfaceAccount<'info, TokenAccount>> {
+    match account {
+        Some(account) => Ok(account),
+        None => Err(error!(AuctionError::MissingSettlementAccount)),
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        init,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        space = GLOBAL_SIZE,
+        payer = admin
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+
+    // The fee treasury and its split table
+    #[account(
+        init,
+        seeds = [TREASURY_SEED.as_ref()],
+        bump,
+        space = 8 + 172,
+        payer = admin
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateGlobalAuthority<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    // The global authority in the layout of the first release
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        owner = ID,
+    )]
+    /// CHECK: The super admin is read from the legacy layout and the account rewritten
+    pub global_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        seeds = [TREASURY_SEED.as_ref()],
+        bump,
+        space = 8 + 172,
+        payer = admin
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+}
+
+#[derive(Accounts)]
+pub struct SetRole<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+}
+
+#[derive(Accounts)]
+pub struct FreezeAuction<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+
+    #[account(mut)]
+    pub auction: AccountLoader<'info, AuctionPool>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeSplit<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED.as_ref()],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeFees<'info> {
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED.as_ref()],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    // The treasury's quote token ATA, not passed to pay out native SOL
+    #[account(
+        mut,
+        constraint = treasury_vault.owner == treasury.key()
+    )]
+    pub treasury_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The quote mint of the treasury vault, passed with it
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    // The quote mint's token program, Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct AddQuoteMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+
+    // The whitelist entry of the quote mint
+    #[account(
+        init,
+        seeds = [QUOTE_MINT_SEED.as_ref(), quote_mint.key().as_ref()],
+        bump,
+        space = 8 + 33,
+        payer = admin
+    )]
+    pub quote_mint_info: Account<'info, QuoteMint>,
+
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveQuoteMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+
+    // The whitelist entry of the quote mint
+    #[account(
+        mut,
+        seeds = [QUOTE_MINT_SEED.as_ref(), quote_mint_info.mint.as_ref()],
+        bump = quote_mint_info.bump,
+        close = admin
+    )]
+    pub quote_mint_info: Account<'info, QuoteMint>,
+}
+
+#[derive(Accounts)]
+#[instruction(collection: Pubkey)]
+pub struct AddCollection<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+
+    // The registry entry of the collection
+    #[account(
+        init,
+        seeds = [COLLECTION_CONFIG_SEED.as_ref(), collection.as_ref()],
+        bump,
+        space = 8 + 307,
+        payer = admin
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCollection<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+
+    // The registry entry of the collection
+    #[account(
+        mut,
+        seeds = [COLLECTION_CONFIG_SEED.as_ref(), collection_config.collection.as_ref()],
+        bump = collection_config.bump,
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveCollection<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+
+    // The registry entry of the collection
+    #[account(
+        mut,
+        seeds = [COLLECTION_CONFIG_SEED.as_ref(), collection_config.collection.as_ref()],
+        bump = collection_config.bump,
+        close = admin
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetStrictCollections<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+}
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CreateAuction<'info> {
+    // Main Auction PDA to store the data
+    #[account(
+        init,
+        seeds = [
+            AUCTION_SEED.as_ref(),
+            owner.key().as_ref(),
+            mint.key().as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<AuctionPool>(),
+        payer = owner
+    )]
+    pub auction: AccountLoader<'info, AuctionPool>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+
+    // The NFT's Auction ATA, which must be the global authority's so claims can move the NFT
+    #[account(
+        mut,
+        address = spl_associated_token_account::get_associated_token_address_with_program_id(
+            &global_authority.key(),
+            &mint.key(),
+            &nft_token_program.key(),
+        )
+    )]
+    /// CHECK: This is not dangerous because it is the global authority's ATA, created if missing
+    pub auction_ata: AccountInfo<'info>,
+
+    // The Auction's quote escrow vault, left out for native SOL auctions.
+    // Created in the instruction so it fits the quote mint's extensions
+    #[account(
+        mut,
+        seeds = [AUCTION_VAULT_SEED.as_ref(), auction.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is not dangerous because it is created as a token account
+    pub auction_vault: Option<UncheckedAccount<'info>>,
+
+    // The mint bids are paid in, chosen by the seller
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    // The whitelist entry of the quote mint
+    #[account(
+        seeds = [QUOTE_MINT_SEED.as_ref(), quote_mint_info.mint.as_ref()],
+        bump = quote_mint_info.bump,
+    )]
+    pub quote_mint_info: Option<Box<Account<'info, QuoteMint>>>,
+
+    // The auction creator
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    // The NFT's owner ATA
+    #[account(
+        mut,
+        constraint = owner_ata.mint == *mint.to_account_info().key,
+        constraint = owner_ata.owner == *owner.key,
+    )]
+    pub owner_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // The NFT mint address
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // The NFT's Token Metadata account, left out for a Token-2022 NFT
+    // described by its own metadata extension
+    #[account(
+        mut,
+        constraint = mint_metadata.owner == &mpl_token_metadata::ID
+    )]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub mint_metadata: Option<UncheckedAccount<'info>>,
+
+    // The token group mint of a Token-2022 NFT listed without Token Metadata,
+    // left out when the NFT isn't a group member
+    /// CHECK: Checked against the NFT's group member extension in the instruction
+    pub nft_group: Option<UncheckedAccount<'info>>,
+
+    // The registry entry of the NFT's collection, which may not exist
+    /// CHECK: Checked to be the collection's config PDA in the instruction
+    pub collection_config: UncheckedAccount<'info>,
+
+    #[account(address = spl_associated_token_account::ID)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub ata_program: AccountInfo<'info>,
+
+    // Token Metadata accounts, only required for programmable NFTs
+    pub pnft: PnftAccounts<'info>,
+
+    // The quote mint's token program, Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    // The NFT mint's token program
+    pub nft_token_program: Interface<'info, TokenInterface>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    nonce: u64,
+    start_price: u64,
+    duration: u64,
+    buy_now_price: Option<u64>,
+    buy_now_cutoff_bps: u16,
+    reserve_hash: Option<[u8; 32]>,
+    metadata: MetadataArgs,
+    leaf_nonce: u64,
+)]
+pub struct CreateCompressedAuction<'info> {
+    // Main Auction PDA to store the data
+    #[account(
+        init,
+        seeds = [
+            AUCTION_SEED.as_ref(),
+            owner.key().as_ref(),
+            asset_id.key().as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<AuctionPool>(),
+        payer = owner
+    )]
+    pub auction: AccountLoader<'info, AuctionPool>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+
+    // The Auction's quote escrow vault, left out for native SOL auctions.
+    // Created in the instruction so it fits the quote mint's extensions
+    #[account(
+        mut,
+        seeds = [AUCTION_VAULT_SEED.as_ref(), auction.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is not dangerous because it is created as a token account
+    pub auction_vault: Option<UncheckedAccount<'info>>,
+
+    // The mint bids are paid in, chosen by the seller
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    // The whitelist entry of the quote mint
+    #[account(
+        seeds = [QUOTE_MINT_SEED.as_ref(), quote_mint_info.mint.as_ref()],
+        bump = quote_mint_info.bump,
+    )]
+    pub quote_mint_info: Option<Box<Account<'info, QuoteMint>>>,
+
+    // The auction creator, owning the leaf
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    // The leaf's delegate, the owner itself if it has none
+    /// CHECK: Bubblegum checks it against the leaf
+    pub leaf_delegate: UncheckedAccount<'info>,
+
+    // The asset id of the leaf, which the auction is keyed by
+    #[account(
+        constraint = asset_id.key() == get_asset_id(&merkle_tree.key(), leaf_nonce) @ AuctionError::InvalidCompressedNft
+    )]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub asset_id: UncheckedAccount<'info>,
+
+    // The registry entry of the cNFT's collection, which may not exist
+    /// CHECK: Checked to be the collection's config PDA in the instruction
+    pub collection_config: UncheckedAccount<'info>,
+
+    // The leaf of the escrowed cNFT
+    #[account(
+        init,
+        seeds = [COMPRESSED_ASSET_SEED.as_ref(), auction.key().as_ref()],
+        bump,
+        space = 8 + 183,
+        payer = owner
+    )]
+    pub compressed_asset: Box<Account<'info, CompressedAsset>>,
+
+    /// CHECK: Bubblegum checks it's the tree config PDA of the merkle tree
+    pub tree_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Bubblegum verifies the leaf and its proof against the tree
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    #[account(address = bubblegum::SPL_NOOP_ID)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    #[account(address = bubblegum::SPL_ACCOUNT_COMPRESSION_ID)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub compression_program: UncheckedAccount<'info>,
+
+    #[account(address = bubblegum::BUBBLEGUM_ID)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    // The quote mint's token program, Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CreateCoreAuction<'info> {
+    // Main Auction PDA to store the data
+    #[account(
+        init,
+        seeds = [
+            AUCTION_SEED.as_ref(),
+            owner.key().as_ref(),
+            asset.key().as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<AuctionPool>(),
+        payer = owner
+    )]
+    pub auction: AccountLoader<'info, AuctionPool>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+
+    // The Auction's quote escrow vault, left out for native SOL auctions.
+    // Created in the instruction so it fits the quote mint's extensions
+    #[account(
+        mut,
+        seeds = [AUCTION_VAULT_SEED.as_ref(), auction.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is not dangerous because it is created as a token account
+    pub auction_vault: Option<UncheckedAccount<'info>>,
+
+    // The mint bids are paid in, chosen by the seller
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    // The whitelist entry of the quote mint
+    #[account(
+        seeds = [QUOTE_MINT_SEED.as_ref(), quote_mint_info.mint.as_ref()],
+        bump = quote_mint_info.bump,
+    )]
+    pub quote_mint_info: Option<Box<Account<'info, QuoteMint>>>,
+
+    // The auction creator, owning the asset
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    // The Core asset
+    #[account(mut)]
+    /// CHECK: Parsed as a Core asset owned by the seller
+    pub asset: UncheckedAccount<'info>,
+
+    // The Core collection of the asset
+    /// CHECK: Parsed as a Core collection the asset belongs to
+    pub collection: UncheckedAccount<'info>,
+
+    // The registry entry of the asset's collection, which may not exist
+    /// CHECK: Checked to be the collection's config PDA in the instruction
+    pub collection_config: UncheckedAccount<'info>,
+
+    #[account(address = mpl_core::MPL_CORE_ID)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub core_program: UncheckedAccount<'info>,
+
+    // The quote mint's token program, Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetIncrementSchedule<'info> {
+    pub seller: Signer<'info>,
+
+    #[account(mut)]
+    pub auction: AccountLoader<'info, AuctionPool>,
+}
+
+#[derive(Accounts)]
+pub struct NextMinBid<'info> {
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+
+    pub auction: AccountLoader<'info, AuctionPool>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuction<'info> {
+    #[account(mut)]
+    pub auction: AccountLoader<'info, AuctionPool>,
+
+    // The seller's NFT ATA, unused by cNFT auctions
+    #[account(mut)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub owner_ata: Option<AccountInfo<'info>>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+
+    // The NFT's Auction ATA, unused by cNFT auctions
+    #[account(
+        mut,
+        constraint = auction_ata.mint == auction.load()?.nft_mint,
+        constraint = auction_ata.owner == *global_authority.to_account_info().key
+    )]
+    pub auction_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The Auction's quote escrow vault, unused by native SOL auctions
+    #[account(
+        mut,
+        seeds = [AUCTION_VAULT_SEED.as_ref(), auction.key().as_ref()],
+        bump = auction.load()?.vault_bump,
+    )]
+    pub auction_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The NFT mint address, unused by cNFT auctions
+    #[account(address = auction.load()?.nft_mint)]
+    pub nft_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    // The seller address who created auction
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    // Token Metadata accounts, only required for programmable NFTs
+    pub pnft: PnftAccounts<'info>,
+
+    // Bubblegum accounts, only required for compressed NFTs
+    pub cnft: CnftAccounts<'info>,
+
+    // Core accounts, only required for Core assets
+    pub core: CoreAccounts<'info>,
+
+    // The quote mint's token program, Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    // The NFT mint's token program
+    pub nft_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ForceCancel<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Box<Account<'info, GlobalPool>>,
+
+    #[account(mut)]
+    pub auction: AccountLoader<'info, AuctionPool>,
+
+    // The audit record of the cancellation
+    #[account(
+        init,
+        seeds = [
+            FORCE_CANCEL_SEED.as_ref(),
+            auction.key().as_ref(),
+            auction.load()?.auction_id.to_le_bytes().as_ref(),
+        ],
+        bump,
+        space = 8 + 221,
+        payer = admin
+    )]
+    pub force_cancel_record: Box<Account<'info, ForceCancelRecord>>,
+
+    // The NFT's Auction ATA, which keeps a quarantined NFT. Unused by cNFT auctions
+    #[account(
+        mut,
+        constraint = auction_ata.mint == auction.load()?.nft_mint,
+        constraint = auction_ata.owner == *global_authority.to_account_info().key
+    )]
+    pub auction_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The Auction's quote escrow vault, unused by native SOL auctions
+    #[account(
+        mut,
+        seeds = [AUCTION_VAULT_SEED.as_ref(), auction.key().as_ref()],
+        bump = auction.load()?.vault_bump,
+    )]
+    pub auction_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The seller receiving the rent of the closed accounts
+    #[account(
+        mut,
+        address = auction.load()?.seller
+    )]
+    pub seller: SystemAccount<'info>,
+
+    // The seller's NFT ATA, only required when the NFT is returned
+    #[account(
+        mut,
+        constraint = seller_nft_ata.mint == auction.load()?.nft_mint,
+        constraint = seller_nft_ata.owner == auction.load()?.seller
+    )]
+    pub seller_nft_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The last bidder's quote token ATA for the refund
+    #[account(
+        mut,
+        constraint = bidder_account.mint == auction.load()?.quote_mint,
+        constraint = bidder_account.owner == auction.load()?.bidder
+    )]
+    pub bidder_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The last bidder receiving the refund of a native SOL auction
+    #[account(
+        mut,
+        address = auction.load()?.bidder
+    )]
+    pub bidder: Option<SystemAccount<'info>>,
+
+    // The quote mint, required to refund a token auction
+    #[account(
+        mut,
+        address = auction.load()?.quote_mint
+    )]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    // Token Metadata accounts, only required for programmable NFTs
+    pub pnft: PnftAccounts<'info>,
+
+    // Bubblegum accounts, only required for compressed NFTs
+    pub cnft: CnftAccounts<'info>,
+
+    // Core accounts, only required for Core assets
+    pub core: CoreAccounts<'info>,
+
+    // The quote mint's token program, Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    // The NFT mint's token program
+    pub nft_token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseQuarantinedNft<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+
+    // The force cancel record of the auction
+    #[account(
+        mut,
+        seeds = [
+            FORCE_CANCEL_SEED.as_ref(),
+            force_cancel_record.auction.as_ref(),
+            force_cancel_record.auction_id.to_le_bytes().as_ref(),
+        ],
+        bump = force_cancel_record.bump,
+    )]
+    pub force_cancel_record: Account<'info, ForceCancelRecord>,
+
+    // The closed auction, whose PDA still owns a quarantined cNFT
+    #[account(address = force_cancel_record.auction)]
+    /// CHECK: This is not dangerous because it only signs for the cNFT it owns
+    pub auction: UncheckedAccount<'info>,
+
+    // The program's ATA keeping the quarantined NFT, unused by cNFTs
+    #[account(
+        mut,
+        address = force_cancel_record.quarantine_ata
+    )]
+    pub quarantine_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The NFT ATA receiving the released NFT, unused by cNFTs
+    #[account(
+        mut,
+        constraint = destination_ata.mint == force_cancel_record.nft_mint,
+    )]
+    pub destination_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Token Metadata accounts, only required for programmable NFTs
+    pub pnft: PnftAccounts<'info>,
+
+    // Bubblegum accounts, only required for compressed NFTs
+    pub cnft: CnftAccounts<'info>,
+
+    // Core accounts, only required for Core assets
+    pub core: CoreAccounts<'info>,
+
+    // The NFT mint's token program
+    pub nft_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(mut)]
+    pub auction: AccountLoader<'info, AuctionPool>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+
+    // The Auction's quote escrow vault, unused by native SOL auctions
+    #[account(
+        mut,
+        seeds = [AUCTION_VAULT_SEED.as_ref(), auction.key().as_ref()],
+        bump = auction.load()?.vault_bump,
+    )]
+    pub auction_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The Out_bidder's quote token ATA
+    #[account(
+        mut,
+        constraint = out_bidder_account.mint == auction.load()?.quote_mint,
+        constraint = out_bidder_account.owner == *out_bidder.to_account_info().key
+    )]
+    pub out_bidder_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub out_bidder: SystemAccount<'info>,
+
+    // The Auction's quote token ATA to store them
+    #[account(
+        mut,
+        constraint = new_bidder_account.mint == auction.load()?.quote_mint,
+        constraint = new_bidder_account.owner == *bidder.to_account_info().key
+    )]
+    pub new_bidder_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The auction's bid book, required for proxy bids and once it is opened
+    #[account(
+        mut,
+        seeds = [
+            BID_BOOK_SEED.as_ref(),
+            auction.key().as_ref(),
+            auction.load()?.auction_id.to_le_bytes().as_ref(),
+        ],
+        bump = bid_book.bump,
+    )]
+    pub bid_book: Option<Box<Account<'info, BidBook>>>,
+
+    // Accounts below are only required to settle a Dutch auction
+
+    // The NFT's Auction ATA
+    #[account(
+        mut,
+        constraint = auction_ata.mint == auction.load()?.nft_mint,
+        constraint = auction_ata.owner == *global_authority.to_account_info().key
+    )]
+    pub auction_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The treasury, which also keeps the fees of native SOL auctions
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED.as_ref()],
+        bump = treasury.bump,
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    // The treasury's quote token ATA collecting the fee
+    #[account(
+        mut,
+        constraint = treasury_vault.mint == auction.load()?.quote_mint,
+        constraint = treasury_vault.owner == treasury.key()
+    )]
+    pub treasury_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The NFT's winner ATA
+    #[account(
+        mut,
+        constraint = winner_ata.mint == auction.load()?.nft_mint,
+    )]
+    pub winner_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The Seller's quote token ATA
+    #[account(
+        mut,
+        constraint = seller_ata.mint == auction.load()?.quote_mint,
+    )]
+    pub seller_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The seller receiving the proceeds of a native SOL auction
+    #[account(
+        mut,
+        address = auction.load()?.seller
+    )]
+    pub seller: Option<SystemAccount<'info>>,
+
+    // The quote mint, required in token auctions
+    #[account(
+        mut,
+        address = auction.load()?.quote_mint
+    )]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    // Only required to create a missing creator ATA
+    #[account(address = spl_associated_token_account::ID)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub ata_program: Option<AccountInfo<'info>>,
+
+    // Token Metadata accounts, only required for programmable NFTs
+    pub pnft: PnftAccounts<'info>,
+
+    // Bubblegum accounts, only required for compressed NFTs
+    pub cnft: CnftAccounts<'info>,
+
+    // Core accounts, only required for Core assets
+    pub core: CoreAccounts<'info>,
+
+    // The quote mint's token program, Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    // The NFT mint's token program
+    pub nft_token_program: Interface<'info, TokenInterface>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenBidBook<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub auction: AccountLoader<'info, AuctionPool>,
+
+    #[account(
+        init,
+        seeds = [
+            BID_BOOK_SEED.as_ref(),
+            auction.key().as_ref(),
+            auction.load()?.auction_id.to_le_bytes().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = 8 + 129,
+    )]
+    pub bid_book: Account<'info, BidBook>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBidBook<'info> {
+    // Opened the bid book and gets its rent back
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address = bid_book.auction)]
+    /// CHECK: This is not dangerous because it is only read to be closed
+    pub auction: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            BID_BOOK_SEED.as_ref(),
+            auction.key().as_ref(),
+            bid_book.auction_id.to_le_bytes().as_ref(),
+        ],
+        bump = bid_book.bump,
+        has_one = payer,
+        close = payer,
+    )]
+    pub bid_book: Account<'info, BidBook>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAuction<'info> {
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    #[account(mut)]
+    pub auction: AccountLoader<'info, AuctionPool>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+
+    // The NFT's owner ATA, unused by cNFT auctions
+    #[account(
+        mut,
+        constraint = auction_ata.mint == auction.load()?.nft_mint,
+        constraint = auction_ata.owner == *global_authority.to_account_info().key
+    )]
+    pub auction_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The Auction's quote escrow vault, unused by native SOL auctions
+    #[account(
+        mut,
+        seeds = [AUCTION_VAULT_SEED.as_ref(), auction.key().as_ref()],
+        bump = auction.load()?.vault_bump,
+    )]
+    pub auction_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The treasury, which also keeps the fees of native SOL auctions
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED.as_ref()],
+        bump = treasury.bump,
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    // The treasury's quote token ATA collecting the fee
+    #[account(
+        mut,
+        constraint = treasury_vault.mint == auction.load()?.quote_mint,
+        constraint = treasury_vault.owner == treasury.key()
+    )]
+    pub treasury_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The NFT mint address
+    #[account(address = auction.load()?.nft_mint)]
+    pub nft_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    // The NFT's winner ATA
+    #[account(
+        mut,
+        constraint = winner_ata.mint == auction.load()?.nft_mint,
+    )]
+    pub winner_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The Creator's quote token ATA
+    #[account(
+        mut,
+        constraint = seller_ata.mint == auction.load()?.quote_mint,
+    )]
+    pub seller_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Accounts below are only required when the reserve price is not met,
+    // except the refund accounts of the last bidder which a winning proxy's
+    // unused escrow needs too
+
+    // The last bidder's quote token ATA for the refund
+    #[account(
+        mut,
+        constraint = bidder_account.mint == auction.load()?.quote_mint,
+        constraint = bidder_account.owner == auction.load()?.bidder
+    )]
+    pub bidder_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The seller's NFT ATA
+    #[account(
+        mut,
+        constraint = seller_nft_ata.mint == auction.load()?.nft_mint,
+        constraint = seller_nft_ata.owner == auction.load()?.seller
+    )]
+    pub seller_nft_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The last bidder receiving the refund of a native SOL auction
+    #[account(
+        mut,
+        address = auction.load()?.bidder
+    )]
+    pub bidder: Option<SystemAccount<'info>>,
+
+    // Accounts below are only required to settle a native SOL auction
+
+    // The seller receiving the proceeds
+    #[account(
+        mut,
+        address = auction.load()?.seller
+    )]
+    pub seller: Option<SystemAccount<'info>>,
+
+    // The quote mint, required in token auctions
+    #[account(
+        mut,
+        address = auction.load()?.quote_mint
+    )]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    // Only required to create a missing creator ATA
+    #[account(address = spl_associated_token_account::ID)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub ata_program: Option<AccountInfo<'info>>,
 
-        // Close the auction PDA
-        let owner = &mut ctx.accounts.claimer;
-        let origin_lamports: u64 = owner.lamports();
-        **owner.lamports.borrow_mut() = origin_lamports + ctx.accounts.auction.as_ref().lamports();
-        **ctx.accounts.auction.as_ref().lamports.borrow_mut() = 0;
+    // Token Metadata accounts, only required for programmable NFTs
+    pub pnft: PnftAccounts<'info>,
 
-        Ok(())
-    }
+    // Bubblegum accounts, only required for compressed NFTs
+    pub cnft: CnftAccounts<'info>,
+
+    // Core accounts, only required for Core assets
+    pub core: CoreAccounts<'info>,
+
+    // The quote mint's token program, Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    // The NFT mint's token program
+    pub nft_token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct Initialize<'info> {
+pub struct SettleExpiredAuction<'info> {
+    // The seller or any crank, paying the token record of a returned pNFT
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub auction: AccountLoader<'info, AuctionPool>,
+
     #[account(
-        init,
         seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
         bump,
-        space = 8 + 32,
-        payer = admin
     )]
     pub global_authority: Account<'info, GlobalPool>,
 
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+    // The seller address who created auction, receives the rent
+    #[account(
+        mut,
+        address = auction.load()?.seller
+    )]
+    pub seller: SystemAccount<'info>,
+
+    // The NFT's Auction ATA, unused by cNFT auctions
+    #[account(
+        mut,
+        constraint = auction_ata.mint == auction.load()?.nft_mint,
+        constraint = auction_ata.owner == *global_authority.to_account_info().key
+    )]
+    pub auction_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The seller's NFT ATA, unused by cNFT auctions
+    #[account(
+        mut,
+        constraint = seller_nft_ata.mint == auction.load()?.nft_mint,
+        constraint = seller_nft_ata.owner == *seller.to_account_info().key
+    )]
+    pub seller_nft_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The Auction's quote escrow vault, unused by native SOL auctions
+    #[account(
+        mut,
+        seeds = [AUCTION_VAULT_SEED.as_ref(), auction.key().as_ref()],
+        bump = auction.load()?.vault_bump,
+    )]
+    pub auction_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Token Metadata accounts, only required for programmable NFTs
+    pub pnft: PnftAccounts<'info>,
+
+    // Bubblegum accounts, only required for compressed NFTs
+    pub cnft: CnftAccounts<'info>,
+
+    // Core accounts, only required for Core assets
+    pub core: CoreAccounts<'info>,
+
+    // The quote mint's token program, Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    // The NFT mint's token program
+    pub nft_token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-pub struct CreateAuction<'info> {
-    // Main Auction PDA to store the data
-    #[account(zero)]
+pub struct BuyNow<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(mut)]
     pub auction: AccountLoader<'info, AuctionPool>,
 
     #[account(
@@ -461,87 +4666,200 @@ pub struct CreateAuction<'info> {
     )]
     pub global_authority: Account<'info, GlobalPool>,
 
-    // The NFT's Auction ATA
+    // The Auction's quote escrow vault, unused by native SOL auctions
+    #[account(
+        mut,
+        seeds = [AUCTION_VAULT_SEED.as_ref(), auction.key().as_ref()],
+        bump = auction.load()?.vault_bump,
+    )]
+    pub auction_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The Out_bidder's quote token ATA
+    #[account(
+        mut,
+        constraint = out_bidder_account.mint == auction.load()?.quote_mint,
+        constraint = out_bidder_account.owner == *out_bidder.to_account_info().key
+    )]
+    pub out_bidder_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
     #[account(mut)]
     /// CHECK: This is not dangerous because we don't read or write from this account
-    pub auction_ata: AccountInfo<'info>,
+    pub out_bidder: SystemAccount<'info>,
 
-    // The auction creator
-    #[account(mut)]
-    pub owner: Signer<'info>,
+    // The Buyer's quote token ATA
+    #[account(
+        mut,
+        constraint = buyer_account.mint == auction.load()?.quote_mint,
+        constraint = buyer_account.owner == *buyer.to_account_info().key
+    )]
+    pub buyer_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    // The NFT's owner ATA
+    // The auction's bid book, required once it is opened
     #[account(
         mut,
-        constraint = owner_ata.mint == *mint.to_account_info().key,
-        constraint = owner_ata.owner == *owner.key,
+        seeds = [
+            BID_BOOK_SEED.as_ref(),
+            auction.key().as_ref(),
+            auction.load()?.auction_id.to_le_bytes().as_ref(),
+        ],
+        bump = bid_book.bump,
+    )]
+    pub bid_book: Option<Box<Account<'info, BidBook>>>,
+
+    // The NFT's owner ATA, unused by cNFT auctions
+    #[account(
+        mut,
+        constraint = auction_ata.mint == auction.load()?.nft_mint,
+        constraint = auction_ata.owner == *global_authority.to_account_info().key
+    )]
+    pub auction_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The treasury, which also keeps the fees of native SOL auctions
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED.as_ref()],
+        bump = treasury.bump,
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    // The treasury's quote token ATA collecting the fee
+    #[account(
+        mut,
+        constraint = treasury_vault.mint == auction.load()?.quote_mint,
+        constraint = treasury_vault.owner == treasury.key()
     )]
-    pub owner_ata: Account<'info, TokenAccount>,
+    pub treasury_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     // The NFT mint address
-    pub mint: Account<'info, Mint>,
+    #[account(address = auction.load()?.nft_mint)]
+    pub nft_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
 
+    // The NFT's buyer ATA
     #[account(
         mut,
-        constraint = mint_metadata.owner == &mpl_token_metadata::ID
+        constraint = winner_ata.mint == auction.load()?.nft_mint,
     )]
-    /// CHECK: This is not dangerous because we don't read or write from this account
-    pub mint_metadata: AccountInfo<'info>,
+    pub winner_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // The Seller's quote token ATA
+    #[account(
+        mut,
+        constraint = seller_ata.mint == auction.load()?.quote_mint,
+    )]
+    pub seller_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
+    // The seller receiving the proceeds of a native SOL auction
+    #[account(
+        mut,
+        address = auction.load()?.seller
+    )]
+    pub seller: Option<SystemAccount<'info>>,
+
+    // The quote mint, required in token auctions
+    #[account(
+        mut,
+        address = auction.load()?.quote_mint
+    )]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    // Only required to create a missing creator ATA
     #[account(address = spl_associated_token_account::ID)]
     /// CHECK: This is not dangerous because we don't read or write from this account
-    pub ata_program: AccountInfo<'info>,
+    pub ata_program: Option<AccountInfo<'info>>,
+
+    // Token Metadata accounts, only required for programmable NFTs
+    pub pnft: PnftAccounts<'info>,
+
+    // Bubblegum accounts, only required for compressed NFTs
+    pub cnft: CnftAccounts<'info>,
+
+    // Core accounts, only required for Core assets
+    pub core: CoreAccounts<'info>,
 
-    #[account(address = anchor_spl::token::ID)]
-    pub token_program: Program<'info, Token>,
+    // The quote mint's token program, Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    // The NFT mint's token program
+    pub nft_token_program: Interface<'info, TokenInterface>,
 
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
-
-    #[account(address = sysvar::rent::ID)]
-    pub rent_sysvar: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct CancelAuction<'info> {
+pub struct CommitBid<'info> {
     #[account(mut)]
-    pub auction: AccountLoader<'info, AuctionPool>,
+    pub bidder: Signer<'info>,
 
-    // The NFT's Auction ATA
     #[account(mut)]
-    /// CHECK: This is not dangerous because we don't read or write from this account
-    pub owner_ata: AccountInfo<'info>,
+    pub auction: AccountLoader<'info, AuctionPool>,
 
     #[account(
-        mut,
         seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
         bump,
     )]
     pub global_authority: Account<'info, GlobalPool>,
 
-    // The NFT's owner ATA
+    // The Bidder's sealed bid
+    #[account(
+        init,
+        seeds = [SEALED_BID_SEED.as_ref(), auction.key().as_ref(), bidder.key().as_ref()],
+        bump,
+        space = 8 + 131,
+        payer = bidder
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+
+    // The sealed bid's quote deposit vault.
+    // Created in the instruction so it fits the quote mint's extensions
     #[account(
         mut,
-        constraint = auction_ata.mint == *nft_mint.to_account_info().key,
-        constraint = auction_ata.owner == *global_authority.to_account_info().key
+        seeds = [SEALED_BID_VAULT_SEED.as_ref(), sealed_bid.key().as_ref()],
+        bump,
     )]
-    pub auction_ata: Account<'info, TokenAccount>,
+    /// CHECK: This is not dangerous because it is created as a token account
+    pub bid_vault: UncheckedAccount<'info>,
 
-    // The NFT mint address
-    pub nft_mint: Account<'info, Mint>,
+    // The quote mint of the auction
+    #[account(address = auction.load()?.quote_mint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // The Bidder's quote token ATA
+    #[account(
+        mut,
+        constraint = bidder_account.mint == auction.load()?.quote_mint,
+        constraint = bidder_account.owner == *bidder.to_account_info().key
+    )]
+    pub bidder_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // The quote mint's token program, Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealBid<'info> {
+    pub bidder: Signer<'info>,
 
-    // The seller address who created auction
     #[account(mut)]
-    pub seller: Signer<'info>,
+    pub auction: AccountLoader<'info, AuctionPool>,
 
-    #[account(address = anchor_spl::token::ID)]
-    pub token_program: Program<'info, Token>,
+    // The Bidder's sealed bid
+    #[account(
+        mut,
+        seeds = [SEALED_BID_SEED.as_ref(), auction.key().as_ref(), bidder.key().as_ref()],
+        bump = sealed_bid.bump,
+        constraint = sealed_bid.auction_id == auction.load()?.auction_id @ AuctionError::StaleSealedBid,
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
 }
 
 #[derive(Accounts)]
-pub struct PlaceBid<'info> {
+pub struct ClaimSealedAuction<'info> {
     #[account(mut)]
-    pub bidder: Signer<'info>,
+    pub claimer: Signer<'info>,
 
     #[account(mut)]
     pub auction: AccountLoader<'info, AuctionPool>,
@@ -553,101 +4871,301 @@ pub struct PlaceBid<'info> {
     )]
     pub global_authority: Account<'info, GlobalPool>,
 
-    // The Auction's HTO token ATA to store them
+    // The winner's sealed bid
     #[account(
         mut,
-        constraint = auction_vault.mint == HTO_TOKEN_MINT.parse::<Pubkey>().unwrap(),
-        constraint = auction_vault.owner == *global_authority.to_account_info().key
+        seeds = [SEALED_BID_SEED.as_ref(), auction.key().as_ref(), winner.key().as_ref()],
+        bump = winner_bid.bump,
+        constraint = winner_bid.auction_id == auction.load()?.auction_id @ AuctionError::StaleSealedBid,
+        close = winner
     )]
-    pub auction_vault: Account<'info, TokenAccount>,
+    pub winner_bid: Box<Account<'info, SealedBid>>,
 
-    // The Out_bidder's HTO token ATA
+    // The winner's quote deposit vault
     #[account(
         mut,
-        constraint = out_bidder_account.mint == HTO_TOKEN_MINT.parse::<Pubkey>().unwrap(),
-        constraint = out_bidder_account.owner == *out_bidder.to_account_info().key
+        seeds = [SEALED_BID_VAULT_SEED.as_ref(), winner_bid.key().as_ref()],
+        bump = winner_bid.vault_bump,
     )]
-    pub out_bidder_account: Account<'info, TokenAccount>,
+    pub winner_bid_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    #[account(mut)]
-    /// CHECK: This is not dangerous because we don't read or write from this account
-    pub out_bidder: SystemAccount<'info>,
+    // The winning bidder
+    #[account(
+        mut,
+        address = auction.load()?.bidder
+    )]
+    pub winner: SystemAccount<'info>,
 
-    // The Auction's HTO token ATA to store them
+    // The Seller, who gets back the rent of the unused auction vault
     #[account(
         mut,
-        constraint = new_bidder_account.mint == HTO_TOKEN_MINT.parse::<Pubkey>().unwrap(),
-        constraint = new_bidder_account.owner == *bidder.to_account_info().key
+        address = auction.load()?.seller
+    )]
+    pub seller: SystemAccount<'info>,
+
+    // The winner's quote token ATA for the deposit refund
+    #[account(
+        mut,
+        constraint = winner_account.mint == auction.load()?.quote_mint,
+        constraint = winner_account.owner == *winner.to_account_info().key
+    )]
+    pub winner_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // The NFT's owner ATA
+    #[account(
+        mut,
+        constraint = auction_ata.mint == auction.load()?.nft_mint,
+        constraint = auction_ata.owner == *global_authority.to_account_info().key
+    )]
+    pub auction_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // The Auction's quote escrow vault
+    #[account(
+        mut,
+        seeds = [AUCTION_VAULT_SEED.as_ref(), auction.key().as_ref()],
+        bump = auction.load()?.vault_bump,
+    )]
+    pub auction_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // The treasury
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED.as_ref()],
+        bump = treasury.bump,
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    // The treasury's quote token ATA collecting the fee
+    #[account(
+        mut,
+        constraint = treasury_vault.mint == auction.load()?.quote_mint,
+        constraint = treasury_vault.owner == treasury.key()
+    )]
+    pub treasury_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // The NFT mint address
+    #[account(address = auction.load()?.nft_mint)]
+    pub nft_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // The NFT's winner ATA
+    #[account(
+        mut,
+        constraint = winner_ata.mint == auction.load()?.nft_mint,
+    )]
+    pub winner_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // The Seller's quote token ATA
+    #[account(
+        mut,
+        constraint = seller_ata.mint == auction.load()?.quote_mint,
+    )]
+    pub seller_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // The quote mint of the auction
+    #[account(
+        mut,
+        address = auction.load()?.quote_mint
     )]
-    pub new_bidder_account: Account<'info, TokenAccount>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    #[account(address = anchor_spl::token::ID)]
-    pub token_program: Program<'info, Token>,
+    // Only required to create a missing creator ATA
+    #[account(address = spl_associated_token_account::ID)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub ata_program: Option<AccountInfo<'info>>,
+
+    // Token Metadata accounts, only required for programmable NFTs
+    pub pnft: PnftAccounts<'info>,
+
+    // The quote mint's token program, Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    // The NFT mint's token program
+    pub nft_token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ClaimAuction<'info> {
+pub struct WithdrawSealedBid<'info> {
     #[account(mut)]
-    pub claimer: Signer<'info>,
+    pub bidder: Signer<'info>,
 
-    #[account(mut)]
-    pub auction: AccountLoader<'info, AuctionPool>,
+    // The auction may already be closed after settlement
+    /// CHECK: This is not dangerous because it is only read when still owned by this program
+    pub auction: UncheckedAccount<'info>,
 
     #[account(
-        mut,
         seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
         bump,
     )]
     pub global_authority: Account<'info, GlobalPool>,
 
-    // The NFT's owner ATA
+    // The Bidder's sealed bid
     #[account(
         mut,
-        constraint = auction_ata.mint == *nft_mint.to_account_info().key,
-        constraint = auction_ata.owner == *global_authority.to_account_info().key
+        seeds = [SEALED_BID_SEED.as_ref(), auction.key().as_ref(), bidder.key().as_ref()],
+        bump = sealed_bid.bump,
+        close = bidder
     )]
-    pub auction_ata: Box<Account<'info, TokenAccount>>,
+    pub sealed_bid: Account<'info, SealedBid>,
 
-    // The Auction's HTO token ATA to store them
+    // The sealed bid's quote deposit vault
     #[account(
         mut,
-        constraint = auction_vault.mint == HTO_TOKEN_MINT.parse::<Pubkey>().unwrap(),
-        constraint = auction_vault.owner == *global_authority.to_account_info().key
+        seeds = [SEALED_BID_VAULT_SEED.as_ref(), sealed_bid.key().as_ref()],
+        bump = sealed_bid.vault_bump,
     )]
-    pub auction_vault: Box<Account<'info, TokenAccount>>,
+    pub bid_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    // The VAULT_WALLET's HTO token ATA to store them
+    // The quote mint of the deposit
     #[account(
         mut,
-        constraint = hl_vault.mint == HTO_TOKEN_MINT.parse::<Pubkey>().unwrap(),
-        constraint = hl_vault.owner == VAULT_WALLET.parse::<Pubkey>().unwrap()
+        address = bid_vault.mint
     )]
-    pub hl_vault: Box<Account<'info, TokenAccount>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    // The NFT mint address
-    pub nft_mint: Box<Account<'info, Mint>>,
+    // The force cancel record, only passed when the bid's auction was force cancelled
+    #[account(
+        seeds = [
+            FORCE_CANCEL_SEED.as_ref(),
+            auction.key().as_ref(),
+            sealed_bid.auction_id.to_le_bytes().as_ref(),
+        ],
+        bump = force_cancel_record.bump,
+    )]
+    pub force_cancel_record: Option<Account<'info, ForceCancelRecord>>,
 
-    // The NFT's winner ATA
+    // The Bidder's quote token ATA
     #[account(
         mut,
-        constraint = winner_ata.mint == *nft_mint.to_account_info().key,
+        constraint = bidder_account.mint == bid_vault.mint,
+        constraint = bidder_account.owner == *bidder.to_account_info().key
     )]
-    pub winner_ata: Box<Account<'info, TokenAccount>>,
+    pub bidder_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    // The Creator's HTO token ATA
+    // The treasury
     #[account(
         mut,
-        constraint = seller_ata.mint == HTO_TOKEN_MINT.parse::<Pubkey>().unwrap(),
+        seeds = [TREASURY_SEED.as_ref()],
+        bump = treasury.bump,
     )]
-    pub seller_ata: Box<Account<'info, TokenAccount>>,
+    pub treasury: Box<Account<'info, Treasury>>,
 
+    // The treasury's quote token ATA receiving forfeits
     #[account(
         mut,
-        constraint = mint_metadata.owner == &mpl_token_metadata::ID
+        constraint = treasury_vault.mint == bid_vault.mint,
+        constraint = treasury_vault.owner == treasury.key()
     )]
+    pub treasury_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // The quote mint's token program, Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Token Metadata accounts needed to transfer a programmable NFT, left out for
+/// legacy NFTs. Token Metadata checks them against the mint and the token accounts
+#[derive(Accounts)]
+pub struct PnftAccounts<'info> {
+    // The wallet owning the destination NFT ATA
+    /// CHECK: Token Metadata checks it owns the destination
+    pub destination_owner: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Token Metadata checks it against the token accounts
+    pub mint: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: Token Metadata checks it's the metadata PDA of the mint
+    pub metadata: Option<UncheckedAccount<'info>>,
+
+    // The master edition of the mint, freezing and thawing the pNFT
+    /// CHECK: Token Metadata checks it's the edition PDA of the mint
+    pub edition: Option<UncheckedAccount<'info>>,
+
+    // The token records of the source and the destination NFT ATAs
+    #[account(mut)]
+    /// CHECK: Token Metadata checks it's the token record PDA of the source
+    pub owner_token_record: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: Token Metadata checks it's the token record PDA of the destination
+    pub destination_token_record: Option<UncheckedAccount<'info>>,
+
+    // The rule set of the pNFT, left out if it has none
+    /// CHECK: Token Metadata checks it's the rule set of the metadata
+    pub auth_rules: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Token Metadata checks it's the Token Auth Rules program
+    pub auth_rules_program: Option<UncheckedAccount<'info>>,
+
+    #[account(address = sysvar::instructions::ID)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub sysvar_instructions: Option<UncheckedAccount<'info>>,
+
+    #[account(address = mpl_token_metadata::ID)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub token_metadata_program: Option<UncheckedAccount<'info>>,
+
+    #[account(address = spl_associated_token_account::ID)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub ata_program: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Option<Program<'info, System>>,
+}
+
+/// Bubblegum accounts needed to transfer a compressed NFT out of the auction,
+/// left out for SPL NFTs. The merkle proof follows the remaining accounts
+#[derive(Accounts)]
+pub struct CnftAccounts<'info> {
+    // The leaf of the escrowed cNFT
+    #[account(mut)]
+    pub compressed_asset: Option<Box<Account<'info, CompressedAsset>>>,
+
+    /// CHECK: Bubblegum checks it's the tree config PDA of the merkle tree
+    pub tree_authority: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: Checked against the compressed asset, Bubblegum verifies the proof
+    pub merkle_tree: Option<UncheckedAccount<'info>>,
+
+    // The wallet receiving the cNFT
+    /// CHECK: Checked against the winner or the seller of the auction
+    pub new_leaf_owner: Option<UncheckedAccount<'info>>,
+
+    #[account(address = bubblegum::SPL_NOOP_ID)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub log_wrapper: Option<UncheckedAccount<'info>>,
+
+    #[account(address = bubblegum::SPL_ACCOUNT_COMPRESSION_ID)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub compression_program: Option<UncheckedAccount<'info>>,
+
+    #[account(address = bubblegum::BUBBLEGUM_ID)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub bubblegum_program: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Option<Program<'info, System>>,
+}
+
+/// Metaplex Core accounts needed to transfer a Core asset out of the auction,
+/// left out for token NFTs and cNFTs
+#[derive(Accounts)]
+pub struct CoreAccounts<'info> {
+    #[account(mut)]
+    /// CHECK: Checked against the auction, Core checks its owner
+    pub asset: Option<UncheckedAccount<'info>>,
+
+    // The collection of the asset
+    /// CHECK: Core checks it's the asset's collection
+    pub collection: Option<UncheckedAccount<'info>>,
+
+    // The wallet receiving the asset
+    /// CHECK: Checked against the winner or the seller of the auction
+    pub new_owner: Option<UncheckedAccount<'info>>,
+
+    #[account(address = mpl_core::MPL_CORE_ID)]
     /// CHECK: This is not dangerous because we don't read or write from this account
-    pub mint_metadata: AccountInfo<'info>,
+    pub core_program: Option<UncheckedAccount<'info>>,
 
-    #[account(address = anchor_spl::token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub system_program: Option<Program<'info, System>>,
 }
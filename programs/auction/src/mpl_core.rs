@@ -0,0 +1,264 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+    pubkey,
+};
+
+use crate::error::AuctionError;
+
+pub const MPL_CORE_ID: Pubkey = pubkey!("CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d");
+
+// Index of TransferV1 in Core's instruction enum
+const TRANSFER_V1: u8 = 14;
+
+// Core's account keys, the first byte of its accounts
+const KEY_ASSET_V1: u8 = 1;
+const KEY_PLUGIN_HEADER_V1: u8 = 3;
+const KEY_PLUGIN_REGISTRY_V1: u8 = 4;
+const KEY_COLLECTION_V1: u8 = 5;
+
+// UpdateAuthority variants of an asset
+const UPDATE_AUTHORITY_NONE: u8 = 0;
+const UPDATE_AUTHORITY_COLLECTION: u8 = 2;
+
+// Authority variant holding an address in a plugin registry record
+const AUTHORITY_ADDRESS: u8 = 3;
+
+// Core's plugin types
+pub const PLUGIN_ROYALTIES: u8 = 0;
+pub const PLUGIN_PERMANENT_FREEZE_DELEGATE: u8 = 5;
+pub const PLUGIN_PERMANENT_TRANSFER_DELEGATE: u8 = 7;
+pub const PLUGIN_PERMANENT_BURN_DELEGATE: u8 = 8;
+
+// A creator of Core's royalty plugin
+pub struct Creator {
+    pub address: Pubkey,
+    pub percentage: u8,
+}
+
+// Core's royalty plugin, the rule set is left to Core to enforce
+pub struct Royalties {
+    pub basis_points: u16,
+    pub creators: Vec<Creator>,
+}
+
+// The fields of a Core asset or collection the auction relies on
+pub struct CoreAccount {
+    // The owner of an asset, the update authority of a collection
+    pub owner: Pubkey,
+    // The collection an asset belongs to
+    pub collection: Option<Pubkey>,
+    pub plugins: Vec<u8>,
+    pub royalties: Option<Royalties>,
+}
+
+/// Accounts of Core's TransferV1 instruction
+pub struct TransferAccounts<'info> {
+    pub asset: AccountInfo<'info>,
+    // The asset's collection, Core requires it when the asset has one
+    pub collection: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    // The owner, signing the transfer
+    pub authority: AccountInfo<'info>,
+    pub new_owner: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub core_program: AccountInfo<'info>,
+}
+
+// Reads borsh values out of a Core account
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let bytes = self
+            .data
+            .get(self.offset..self.offset + len)
+            .ok_or(AuctionError::InvalidCoreAsset)?;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+
+    fn pubkey(&mut self) -> Result<Pubkey> {
+        Ok(Pubkey::new_from_array(self.bytes(32)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self) -> Result<()> {
+        let len = self.u32()? as usize;
+        self.bytes(len)?;
+        Ok(())
+    }
+}
+
+// Parses an AssetV1 account
+pub fn parse_asset(asset: &AccountInfo) -> Result<CoreAccount> {
+    if *asset.owner != MPL_CORE_ID {
+        return Err(error!(AuctionError::InvalidCoreAsset));
+    }
+    let data = asset.try_borrow_data()?;
+    let mut reader = Reader {
+        data: &data,
+        offset: 0,
+    };
+    if reader.u8()? != KEY_ASSET_V1 {
+        return Err(error!(AuctionError::InvalidCoreAsset));
+    }
+    let owner = reader.pubkey()?;
+    let collection = match reader.u8()? {
+        UPDATE_AUTHORITY_NONE => None,
+        UPDATE_AUTHORITY_COLLECTION => Some(reader.pubkey()?),
+        _ => {
+            reader.pubkey()?;
+            None
+        }
+    };
+    // name, uri and the optional seq
+    reader.string()?;
+    reader.string()?;
+    if reader.u8()? == 1 {
+        reader.u64()?;
+    }
+
+    let (plugins, royalties) = parse_plugins(&mut reader)?;
+
+    Ok(CoreAccount {
+        owner,
+        collection,
+        plugins,
+        royalties,
+    })
+}
+
+// Parses a CollectionV1 account
+pub fn parse_collection(collection: &AccountInfo) -> Result<CoreAccount> {
+    if *collection.owner != MPL_CORE_ID {
+        return Err(error!(AuctionError::InvalidCoreAsset));
+    }
+    let data = collection.try_borrow_data()?;
+    let mut reader = Reader {
+        data: &data,
+        offset: 0,
+    };
+    if reader.u8()? != KEY_COLLECTION_V1 {
+        return Err(error!(AuctionError::InvalidCoreAsset));
+    }
+    let owner = reader.pubkey()?;
+    // name, uri, num_minted and current_size
+    reader.string()?;
+    reader.string()?;
+    reader.u32()?;
+    reader.u32()?;
+
+    let (plugins, royalties) = parse_plugins(&mut reader)?;
+
+    Ok(CoreAccount {
+        owner,
+        collection: None,
+        plugins,
+        royalties,
+    })
+}
+
+// Plugin types of the registry following the base account, with the royalties
+fn parse_plugins(reader: &mut Reader) -> Result<(Vec<u8>, Option<Royalties>)> {
+    let mut plugins = vec![];
+    let mut royalties = None;
+    if reader.offset == reader.data.len() {
+        return Ok((plugins, royalties));
+    }
+    if reader.u8()? != KEY_PLUGIN_HEADER_V1 {
+        return Err(error!(AuctionError::InvalidCoreAsset));
+    }
+    reader.offset = reader.u64()? as usize;
+    if reader.u8()? != KEY_PLUGIN_REGISTRY_V1 {
+        return Err(error!(AuctionError::InvalidCoreAsset));
+    }
+
+    let count = reader.u32()?;
+    for _ in 0..count {
+        let plugin_type = reader.u8()?;
+        if reader.u8()? == AUTHORITY_ADDRESS {
+            reader.pubkey()?;
+        }
+        let offset = reader.u64()? as usize;
+        plugins.push(plugin_type);
+
+        if plugin_type == PLUGIN_ROYALTIES {
+            let mut plugin = Reader {
+                data: reader.data,
+                offset,
+            };
+            // The Plugin enum's variant, then the royalties
+            plugin.u8()?;
+            let basis_points = u16::from_le_bytes(plugin.bytes(2)?.try_into().unwrap());
+            let mut creators = vec![];
+            for _ in 0..plugin.u32()? {
+                creators.push(Creator {
+                    address: plugin.pubkey()?,
+                    percentage: plugin.u8()?,
+                });
+            }
+            royalties = Some(Royalties {
+                basis_points,
+                creators,
+            });
+        }
+    }
+
+    Ok((plugins, royalties))
+}
+
+/**
+ * @dev Transfers the asset to new_owner. Core checks the authority owns the
+ * asset, the collection matches it and its plugins allow the transfer
+ */
+pub fn transfer<'info>(accounts: TransferAccounts<'info>, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+    // TransferV1Args without a compression proof
+    let data = vec![TRANSFER_V1, 0];
+
+    let metas = vec![
+        AccountMeta::new(accounts.asset.key(), false),
+        AccountMeta::new_readonly(accounts.collection.key(), false),
+        AccountMeta::new(accounts.payer.key(), true),
+        AccountMeta::new_readonly(accounts.authority.key(), true),
+        AccountMeta::new_readonly(accounts.new_owner.key(), false),
+        AccountMeta::new_readonly(accounts.system_program.key(), false),
+        // No log wrapper, Core takes its own id for a missing optional account
+        AccountMeta::new_readonly(MPL_CORE_ID, false),
+    ];
+
+    invoke_signed(
+        &Instruction {
+            program_id: MPL_CORE_ID,
+            accounts: metas,
+            data,
+        },
+        &[
+            accounts.asset,
+            accounts.collection,
+            accounts.payer,
+            accounts.authority,
+            accounts.new_owner,
+            accounts.system_program,
+            accounts.core_program,
+        ],
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
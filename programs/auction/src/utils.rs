@@ -1,22 +1,91 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{
-    program::invoke, program::invoke_signed, system_instruction::transfer,
+    program::invoke,
+    program::invoke_signed,
+    system_instruction::{create_account, transfer},
 };
+use anchor_spl::token_2022::spl_token_2022;
+use spl_token_2022::extension::ExtensionType;
 use spl_associated_token_account::instruction;
 
 pub const GLOBAL_AUTHORITY_SEED: &str = "global-authority";
+pub const AUCTION_SEED: &str = "auction";
+pub const AUCTION_VAULT_SEED: &str = "auction-vault";
+pub const SEALED_BID_SEED: &str = "sealed-bid";
+pub const SEALED_BID_VAULT_SEED: &str = "sealed-bid-vault";
+pub const QUOTE_MINT_SEED: &str = "quote-mint";
+pub const FORCE_CANCEL_SEED: &str = "force-cancel";
+pub const TREASURY_SEED: &str = "treasury";
+pub const COMPRESSED_ASSET_SEED: &str = "compressed-asset";
+pub const COLLECTION_CONFIG_SEED: &str = "collection-config";
+pub const BID_BOOK_SEED: &str = "bid-book";
 
-pub const DAY: u64 = 200; // 86400;
+
+pub const AUCTION_TYPE_ENGLISH: u8 = 0;
+pub const AUCTION_TYPE_DUTCH: u8 = 1;
+pub const AUCTION_TYPE_SEALED: u8 = 2;
+
+pub const SETTLE_FIRST_PRICE: u8 = 0;
+pub const SETTLE_SECOND_PRICE: u8 = 1;
+pub const SEALED_FORFEIT_PERCENT: u64 = 10;
+
+pub const ASSET_TYPE_NFT: u8 = 0;
+pub const ASSET_TYPE_COMPRESSED: u8 = 1;
+pub const ASSET_TYPE_CORE: u8 = 2;
+
+pub const DECAY_LINEAR: u8 = 0;
+pub const DECAY_STEPPED: u8 = 1;
+
+pub const PERMYRIAD: u64 = 10000;
+pub const MAX_FEE_RECIPIENTS: usize = 5;
+pub const MAX_COLLECTION_QUOTE_MINTS: usize = 5;
+pub const MAX_INCREMENT_TIERS: usize = 4;
+
+pub const ROYALTY_FALLBACK_SELLER: u8 = 0;
+pub const ROYALTY_FALLBACK_TREASURY: u8 = 1;
+
+pub const ROLE_FEE_MANAGER: u8 = 0;
+pub const ROLE_PAUSER: u8 = 1;
+pub const ROLE_CURATOR: u8 = 2;
+
+// Token-2022 extension types, as stored in the TLV data of a mint
+pub const EXTENSION_UNINITIALIZED: u16 = 0;
+pub const EXTENSION_TRANSFER_FEE_CONFIG: u16 = 1;
+pub const EXTENSION_NON_TRANSFERABLE: u16 = 9;
+pub const EXTENSION_PERMANENT_DELEGATE: u16 = 12;
+pub const EXTENSION_TRANSFER_HOOK: u16 = 14;
+pub const EXTENSION_METADATA_POINTER: u16 = 18;
+pub const EXTENSION_TOKEN_METADATA: u16 = 19;
+pub const EXTENSION_TOKEN_GROUP: u16 = 21;
+pub const EXTENSION_TOKEN_GROUP_MEMBER: u16 = 23;
+pub const TOKEN_ACCOUNT_SIZE: usize = 165;
+// Token-2022 pads mints to the token account size before the account type byte
+pub const EXTENSIONS_OFFSET: usize = TOKEN_ACCOUNT_SIZE;
+
+pub const CANCEL_REASON_STOLEN: u8 = 0;
+pub const CANCEL_REASON_COUNTERFEIT: u8 = 1;
+pub const CANCEL_REASON_SELLER_FRAUD: u8 = 2;
+pub const CANCEL_REASON_OTHER: u8 = 3;
+
+// Size of the global authority, and of its first release layout holding only the super admin
+pub const GLOBAL_SIZE: usize = 8 + 227;
+pub const LEGACY_GLOBAL_SIZE: usize = 8 + 32;
+
+// Defaults of the config in GlobalPool, changed with update_config
+pub const DAY: u64 = 86400;
 pub const MIN_DURATION_AFTER_BID_SECS: u64 = 600; // 10 min
+// Bounds of the soft-close window and extension sellers choose
+pub const MIN_SOFT_CLOSE_SECS: u64 = 60; // 1 min
+pub const MAX_SOFT_CLOSE_SECS: u64 = 3600; // 1 hour
 
 pub const FEE_PERCENT: u64 = 2;
-pub const PERMYRIAD: u64 = 10000;
+pub const MAX_FEE_PERCENT: u64 = 10;
 
 pub const MIN_INCREMENT_PERCENT: u64 = 5;
 pub const MIN_INCREMENT: u64 = 10_000_000_000;
 
+// Sole fee recipient of the treasury until set_fee_split
 pub const VAULT_WALLET: &str = "J1CHG5pAMT4GRprmLwuQ4JzTcjJxTuXD2nsZDqp7924x";
-pub const HTO_TOKEN_MINT: &str = "htoHLBJV1err8xP5oxyQdV2PLQhtVjxLXpKB7FsgJQD";
 
 pub fn create_ata<'info>(
     payer: AccountInfo<'info>,
@@ -26,7 +95,6 @@ pub fn create_ata<'info>(
     token_program: AccountInfo<'info>,
     ata_program: AccountInfo<'info>,
     system_program: AccountInfo<'info>,
-    rent_sysvar: AccountInfo<'info>,
 ) -> Result<()> {
     invoke_signed(
         &instruction::create_associated_token_account(
@@ -43,7 +111,6 @@ pub fn create_ata<'info>(
             token_program,
             ata_program,
             system_program,
-            rent_sysvar,
         ],
         &[],
     )?;
@@ -51,6 +118,37 @@ pub fn create_ata<'info>(
     Ok(())
 }
 
+pub fn create_ata_idempotent<'info>(
+    payer: AccountInfo<'info>,
+    wallet: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    ata: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    ata_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+) -> Result<()> {
+    invoke(
+        &instruction::create_associated_token_account_idempotent(
+            &payer.key(),
+            &wallet.key(),
+            &mint.key(),
+            &token_program.key(),
+        ),
+        &[
+            ata,
+            wallet,
+            mint,
+            payer,
+            token_program,
+            ata_program,
+            system_program,
+        ],
+    )?;
+
+    Ok(())
+}
+
+// Moves NFTs, which never carry a transfer fee, under Token or Token-2022
 pub fn transfer_spl<'info>(
     src: AccountInfo<'info>,
     src_ata: AccountInfo<'info>,
@@ -59,8 +157,9 @@ pub fn transfer_spl<'info>(
     token_program: AccountInfo<'info>,
     signer_seeds: &[&[&[u8]]],
 ) -> Result<()> {
+    #[allow(deprecated)]
     invoke_signed(
-        &spl_token::instruction::transfer(
+        &spl_token_2022::instruction::transfer(
             &token_program.key(),
             &src_ata.key(),
             &dst_ata.key(),
@@ -80,6 +179,176 @@ pub fn transfer_spl<'info>(
     Ok(())
 }
 
+pub fn close_spl_account<'info>(
+    authority: AccountInfo<'info>,
+    account: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_signed(
+        &spl_token_2022::instruction::close_account(
+            &token_program.key(),
+            &account.key(),
+            &destination.key(),
+            &authority.key(),
+            &[],
+        )?,
+        &[
+            token_program.to_account_info(),
+            account.to_account_info(),
+            destination.to_account_info(),
+            authority.to_account_info(),
+        ],
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
+
+// Extensions of a Token-2022 mint with their values, none for Token mints.
+// The TLV data is walked directly so extensions newer than the
+// spl-token-2022 crate, like transfer hooks, are found too
+pub fn mint_extensions(mint: &AccountInfo) -> Result<Vec<(u16, Vec<u8>)>> {
+    let mut extensions = vec![];
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(extensions);
+    }
+    let data = mint.try_borrow_data()?;
+
+    // Extensions follow the account type byte
+    let mut offset = EXTENSIONS_OFFSET + 1;
+    while offset + 4 <= data.len() {
+        let tlv_type = u16::from_le_bytes([data[offset], data[offset + 1]]);
+        let length = u16::from_le_bytes([data[offset + 2], data[offset + 3]]) as usize;
+        let start = offset + 4;
+        if tlv_type == EXTENSION_UNINITIALIZED {
+            break;
+        }
+        if start + length > data.len() {
+            return Err(ProgramError::InvalidAccountData.into());
+        }
+        extensions.push((tlv_type, data[start..start + length].to_vec()));
+        offset = start + length;
+    }
+
+    Ok(extensions)
+}
+
+// Value of a Token-2022 mint extension, None if the mint doesn't have it
+pub fn mint_extension(mint: &AccountInfo, extension_type: u16) -> Result<Option<Vec<u8>>> {
+    Ok(mint_extensions(mint)?
+        .into_iter()
+        .find(|(tlv_type, _)| *tlv_type == extension_type)
+        .map(|(_, value)| value))
+}
+
+// Size of a token account of the mint, including the account extensions
+// Token-2022 requires for the mint's extensions
+pub fn token_account_space(mint: &AccountInfo) -> Result<usize> {
+    let extensions = mint_extensions(mint)?;
+    let mint_extension_types: Vec<ExtensionType> = extensions
+        .iter()
+        .filter_map(|(tlv_type, _)| ExtensionType::try_from(*tlv_type).ok())
+        .collect();
+    let account_extension_types =
+        ExtensionType::get_required_init_account_extensions(&mint_extension_types);
+    let mut space =
+        ExtensionType::get_account_len::<spl_token_2022::state::Account>(&account_extension_types);
+
+    // Transfer hooks are newer than the crate, which doesn't know their
+    // TransferHookAccount extension
+    if extensions
+        .iter()
+        .any(|(tlv_type, _)| *tlv_type == EXTENSION_TRANSFER_HOOK)
+    {
+        if account_extension_types.is_empty() {
+            space = EXTENSIONS_OFFSET + 1;
+        }
+        space += 4 + 1;
+    }
+
+    Ok(space)
+}
+
+// Whether a Token-2022 mint describes itself with a metadata extension or pointer
+pub fn has_token_metadata(mint: &AccountInfo) -> Result<bool> {
+    Ok(mint_extensions(mint)?.iter().any(|(tlv_type, _)| {
+        *tlv_type == EXTENSION_TOKEN_METADATA || *tlv_type == EXTENSION_METADATA_POINTER
+    }))
+}
+
+// The group and member number of a Token-2022 mint's group member extension,
+// None if the mint isn't a group member
+pub fn token_group_member(mint: &AccountInfo) -> Result<Option<(Pubkey, u64)>> {
+    let member = match mint_extension(mint, EXTENSION_TOKEN_GROUP_MEMBER)? {
+        Some(member) => member,
+        None => return Ok(None),
+    };
+    // mint (32) || group (32) || member number (8)
+    if member.len() < 72 || member[..32] != mint.key.to_bytes() {
+        return Err(ProgramError::InvalidAccountData.into());
+    }
+    let group = Pubkey::new_from_array(member[32..64].try_into().unwrap());
+    let member_number = u64::from_le_bytes(member[64..72].try_into().unwrap());
+
+    Ok(Some((group, member_number)))
+}
+
+// The member count of a Token-2022 group mint keeping its own group data,
+// None if the account holds no group
+pub fn token_group_size(group: &AccountInfo) -> Result<Option<u64>> {
+    let data = match mint_extension(group, EXTENSION_TOKEN_GROUP)? {
+        Some(data) => data,
+        None => return Ok(None),
+    };
+    // update authority (32) || mint (32) || size (8) || max size (8)
+    if data.len() < 80 {
+        return Err(ProgramError::InvalidAccountData.into());
+    }
+    if data[32..64] != group.key.to_bytes() {
+        return Ok(None);
+    }
+
+    Ok(Some(u64::from_le_bytes(data[64..72].try_into().unwrap())))
+}
+
+// Creates and initializes a PDA token account owned by authority,
+// sized for the extensions of a Token-2022 mint
+pub fn create_token_vault<'info>(
+    payer: AccountInfo<'info>,
+    vault: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    vault_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let space = token_account_space(&mint)?;
+    invoke_signed(
+        &create_account(
+            &payer.key(),
+            &vault.key(),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            &token_program.key(),
+        ),
+        &[payer, vault.clone(), system_program],
+        vault_seeds,
+    )?;
+    invoke(
+        &spl_token_2022::instruction::initialize_account3(
+            &token_program.key(),
+            &vault.key(),
+            &mint.key(),
+            &authority.key(),
+        )?,
+        &[vault, mint, token_program],
+    )?;
+
+    Ok(())
+}
+
 // transfer from system-owned account
 pub fn transfer_sol<'info>(
     src: AccountInfo<'info>,
@@ -1,16 +1,15 @@
-import * as anchor from "@project-serum/anchor";
-import { Program } from "@project-serum/anchor";
-import { Auction } from "../target/types/auction";
+import * as anchor from '@project-serum/anchor';
+import { assert } from 'chai';
 
-describe("auction", () => {
-  // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+import { getGlobalState } from '../cli/script';
+import { admin, DAY, setup } from './helpers';
 
-  const program = anchor.workspace.Auction as Program<Auction>;
+describe('auction', () => {
+    before(setup);
 
-  it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
-    console.log("Your transaction signature", tx);
-  });
+    it('is initialized with the test config', async () => {
+        const config = await getGlobalState();
+        assert.ok(config.superAdmin.equals(admin.publicKey));
+        assert.ok(config.day.eq(new anchor.BN(DAY)));
+    });
 });
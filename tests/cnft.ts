@@ -0,0 +1,265 @@
+import * as anchor from '@project-serum/anchor';
+import { Token } from '@solana/spl-token';
+import { Keypair, PublicKey, SystemProgram, Transaction } from '@solana/web3.js';
+import {
+    computeCompressedNFTHash,
+    createCreateTreeInstruction,
+    createMintV1Instruction,
+    MetadataArgs,
+    TokenProgramVersion,
+    TokenStandard,
+} from '@metaplex-foundation/mpl-bubblegum';
+import {
+    ConcurrentMerkleTreeAccount,
+    createAllocTreeIx,
+    MerkleTree,
+} from '@solana/spl-account-compression';
+import { assert } from 'chai';
+
+import {
+    createAuctionCancelTx,
+    createAuctionClaimTx,
+    createCompressedAuctionTx,
+    createPlaceBidTx,
+    getAuctionState,
+} from '../cli/script';
+import {
+    getAssetId,
+    getTreeAuthority,
+    SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+    SPL_NOOP_PROGRAM_ID,
+} from '../cli/utils';
+import {
+    DAY,
+    expectFailure,
+    fundQuote,
+    newQuoteMint,
+    newUser,
+    provider,
+    send,
+    setup,
+    waitUntil,
+} from './helpers';
+
+const MAX_DEPTH = 5;
+const MAX_BUFFER_SIZE = 8;
+
+interface LocalAsset {
+    metadata: MetadataArgs,
+    index: number,
+    owner: PublicKey,
+}
+
+/**
+ * Stands in for a DAS RPC, which the local validator lacks. It mirrors the
+ * leaves of one tree, so the CLI reads cNFTs and their proofs from it
+ */
+class LocalDas {
+    leaves: Buffer[] = [];
+    assets = new Map<string, LocalAsset>();
+    realFetch: any;
+
+    constructor(public tree: PublicKey) {}
+
+    install() {
+        this.realFetch = (global as any).fetch;
+        (global as any).fetch = async (url: string, init: any) => {
+            const { method, params } = JSON.parse(init?.body ?? '{}');
+            if (method !== 'getAsset' && method !== 'getAssetProof') return this.realFetch(url, init);
+            const result = method === 'getAsset' ? this.getAsset(params.id) : this.getAssetProof(params.id);
+            return { json: async () => ({ result }) };
+        };
+    }
+
+    uninstall() {
+        (global as any).fetch = this.realFetch;
+    }
+
+    // Records the next leaf of the tree, minted to owner
+    add(assetId: PublicKey, metadata: MetadataArgs, owner: PublicKey) {
+        const index = this.leaves.length;
+        this.leaves.push(Buffer.alloc(32));
+        this.setOwner(assetId, owner, { metadata, index, owner });
+    }
+
+    // Mirrors a transfer, which also resets the delegate to the new owner
+    setOwner(assetId: PublicKey, owner: PublicKey, asset: LocalAsset = this.assets.get(assetId.toBase58())) {
+        asset.owner = owner;
+        this.assets.set(assetId.toBase58(), asset);
+        this.leaves[asset.index] = computeCompressedNFTHash(
+            assetId, owner, owner, new anchor.BN(asset.index), asset.metadata,
+        );
+    }
+
+    merkleTree() {
+        return MerkleTree.sparseMerkleTreeFromLeaves(this.leaves, MAX_DEPTH);
+    }
+
+    getAsset(id: string) {
+        const { metadata, index, owner } = this.assets.get(id);
+        return {
+            content: { metadata: { name: metadata.name, symbol: metadata.symbol }, json_uri: metadata.uri },
+            royalty: { basis_points: metadata.sellerFeeBasisPoints, primary_sale_happened: metadata.primarySaleHappened },
+            mutable: metadata.isMutable,
+            supply: { edition_nonce: metadata.editionNonce },
+            grouping: [],
+            uses: null,
+            creators: metadata.creators.map((creator) => ({
+                address: creator.address.toBase58(),
+                verified: creator.verified,
+                share: creator.share,
+            })),
+            compression: { tree: this.tree.toBase58(), leaf_id: index },
+            ownership: { owner: owner.toBase58(), delegate: null },
+        };
+    }
+
+    getAssetProof(id: string) {
+        const { index } = this.assets.get(id);
+        const { proof } = this.merkleTree().getProof(index);
+        return {
+            proof: proof.map((node) => new PublicKey(node).toBase58()),
+            node_index: index + 2 ** MAX_DEPTH,
+        };
+    }
+}
+
+describe('compressed NFT auction', () => {
+    let seller: Keypair;
+    let bidder: Keypair;
+    let quote: Token;
+    let tree: Keypair;
+    let das: LocalDas;
+
+    // A tree owned by the seller, who mints every cNFT of the tests
+    const createTree = async () => {
+        const tx = new Transaction();
+        tx.add(await createAllocTreeIx(
+            provider.connection,
+            tree.publicKey,
+            seller.publicKey,
+            { maxDepth: MAX_DEPTH, maxBufferSize: MAX_BUFFER_SIZE },
+            0,
+        ));
+        tx.add(createCreateTreeInstruction(
+            {
+                treeAuthority: await getTreeAuthority(tree.publicKey),
+                merkleTree: tree.publicKey,
+                payer: seller.publicKey,
+                treeCreator: seller.publicKey,
+                logWrapper: SPL_NOOP_PROGRAM_ID,
+                compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+            },
+            { maxDepth: MAX_DEPTH, maxBufferSize: MAX_BUFFER_SIZE, public: false },
+        ));
+        await send(tx, [seller, tree]);
+    }
+
+    // A cNFT of the seller, who is also its verified creator
+    const mintCnft = async () => {
+        const metadata: MetadataArgs = {
+            name: 'Test',
+            symbol: 'TEST',
+            uri: '',
+            sellerFeeBasisPoints: 500,
+            primarySaleHappened: false,
+            isMutable: true,
+            editionNonce: null,
+            tokenStandard: TokenStandard.NonFungible,
+            collection: null,
+            uses: null,
+            tokenProgramVersion: TokenProgramVersion.Original,
+            creators: [{ address: seller.publicKey, verified: true, share: 100 }],
+        };
+        const tx = new Transaction().add(createMintV1Instruction(
+            {
+                treeAuthority: await getTreeAuthority(tree.publicKey),
+                leafOwner: seller.publicKey,
+                leafDelegate: seller.publicKey,
+                merkleTree: tree.publicKey,
+                payer: seller.publicKey,
+                treeDelegate: seller.publicKey,
+                logWrapper: SPL_NOOP_PROGRAM_ID,
+                compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            },
+            { message: metadata },
+        ));
+        await send(tx, [seller]);
+
+        // Leaves are numbered in mint order
+        const assetId = await getAssetId(tree.publicKey, new anchor.BN(das.leaves.length));
+        das.add(assetId, metadata, seller.publicKey);
+        return assetId;
+    }
+
+    const listCnft = async (assetId: PublicKey) => {
+        const tx = await createCompressedAuctionTx(seller.publicKey, assetId, 1, DAY, undefined, undefined, undefined, quote.publicKey);
+        await send(tx, [seller]);
+        // The auction is the first account of every create instruction, and escrows the leaf
+        const auction = tx.instructions[tx.instructions.length - 1].keys[0].pubkey;
+        das.setOwner(assetId, auction);
+        return auction;
+    }
+
+    // The on-chain tree must hold exactly the leaves mirrored locally
+    const assertTreeMatches = async () => {
+        const account = await ConcurrentMerkleTreeAccount.fromAccountAddress(provider.connection, tree.publicKey, 'confirmed');
+        assert.deepEqual([...account.getCurrentRoot()], [...das.merkleTree().root]);
+    }
+
+    before(async () => {
+        await setup();
+        seller = await newUser();
+        bidder = await newUser();
+        quote = await newQuoteMint();
+        await fundQuote(quote, bidder.publicKey, 100);
+
+        tree = Keypair.generate();
+        das = new LocalDas(tree.publicKey);
+        das.install();
+        await createTree();
+    });
+
+    after(() => das.uninstall());
+
+    it('escrows a listed cNFT and hands it to the winner', async () => {
+        const assetId = await mintCnft();
+        const auction = await listCnft(assetId);
+        await assertTreeMatches();
+
+        await send(await createPlaceBidTx(bidder.publicKey, auction, 2), [bidder]);
+        await waitUntil((await getAuctionState(auction)).endTime);
+        await send(await createAuctionClaimTx(bidder.publicKey, auction), [bidder]);
+
+        das.setOwner(assetId, bidder.publicKey);
+        await assertTreeMatches();
+    });
+
+    it('returns a cancelled cNFT to the seller', async () => {
+        const assetId = await mintCnft();
+        const auction = await listCnft(assetId);
+
+        await send(await createAuctionCancelTx(seller.publicKey, auction), [seller]);
+
+        das.setOwner(assetId, seller.publicKey);
+        await assertTreeMatches();
+    });
+
+    it('rejects a proof the tree moved past', async () => {
+        const assetId = await mintCnft();
+        const auction = await listCnft(assetId);
+        await send(await createPlaceBidTx(bidder.publicKey, auction, 2), [bidder]);
+        await waitUntil((await getAuctionState(auction)).endTime);
+
+        // Minting a neighbour leaf changes the escrowed leaf's proof
+        const stale = await createAuctionClaimTx(bidder.publicKey, auction);
+        await mintCnft();
+        await expectFailure(send(stale, [bidder]));
+        await assertTreeMatches();
+
+        await send(await createAuctionClaimTx(bidder.publicKey, auction), [bidder]);
+        das.setOwner(assetId, bidder.publicKey);
+        await assertTreeMatches();
+    });
+});
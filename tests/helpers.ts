@@ -0,0 +1,189 @@
+import * as anchor from '@project-serum/anchor';
+import { Token, TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import {
+    Keypair,
+    LAMPORTS_PER_SOL,
+    PublicKey,
+    SYSVAR_CLOCK_PUBKEY,
+    Transaction,
+} from '@solana/web3.js';
+import {
+    createCreateMasterEditionV3Instruction,
+    createCreateMetadataAccountV3Instruction,
+} from '@metaplex-foundation/mpl-token-metadata';
+import NodeWallet from '@project-serum/anchor/dist/cjs/nodewallet';
+import { randomBytes } from 'crypto';
+
+import {
+    createAddQuoteMintTx,
+    createAuctionTx,
+    createInitializeTx,
+    createSetSoftCloseBoundsTx,
+    createUpdateConfigTx,
+    getGlobalState,
+    setClusterConfig,
+} from '../cli/script';
+import { getMasterEdition, getMetadata } from '../cli/utils';
+import { BuyNowConfig, GLOBAL_AUTHORITY_SEED, SealedConfig, SoftCloseConfig } from '../cli/types';
+
+export const PROGRAM_ID = new PublicKey('6VwSgSesAeqqSw3uXsU8BGMxMAqSzFVQxPPUDUVX8Qw4');
+
+// A short day keeps every auction phase to a few seconds on the local validator
+export const DAY = 5;
+
+export let provider: anchor.AnchorProvider = null;
+export let admin: Keypair = null;
+
+let ready: Promise<void> = null;
+
+// Points the CLI at the local validator and initializes the program once for all test files
+export const setup = () => {
+    if (!ready) ready = init();
+    return ready;
+}
+
+const init = async () => {
+    const env = anchor.AnchorProvider.env();
+    admin = (env.wallet as NodeWallet).payer;
+    await setClusterConfig('devnet', env.wallet as NodeWallet, env.connection.rpcEndpoint);
+    provider = anchor.getProvider() as anchor.AnchorProvider;
+
+    if (!await getGlobalState()) {
+        await send(await createInitializeTx(admin.publicKey));
+    }
+    const config = await getGlobalState();
+    await send(await createUpdateConfigTx(admin.publicKey, {
+        ...config,
+        minDurationAfterBidSecs: new anchor.BN(1),
+        day: new anchor.BN(DAY),
+    }));
+    await send(await createSetSoftCloseBoundsTx(admin.publicKey, 1, DAY));
+}
+
+export const getGlobalAuthority = async () => {
+    return (await PublicKey.findProgramAddress([Buffer.from(GLOBAL_AUTHORITY_SEED)], PROGRAM_ID))[0];
+}
+
+export const send = async (tx: Transaction, signers: Keypair[] = []) => {
+    return provider.sendAndConfirm(tx, signers, { commitment: 'confirmed' });
+}
+
+// Resolves when the promise rejects, i.e. the transaction failed
+export const expectFailure = async (promise: Promise<unknown>) => {
+    let failed = false;
+    try {
+        await promise;
+    } catch (e) {
+        failed = true;
+    }
+    if (!failed) throw new Error('The transaction was expected to fail');
+}
+
+export const newUser = async (sol: number = 10) => {
+    const user = Keypair.generate();
+    const txId = await provider.connection.requestAirdrop(user.publicKey, sol * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(txId, 'confirmed');
+    return user;
+}
+
+// A whitelisted quote token with 6 decimals
+export const newQuoteMint = async () => {
+    const quote = await Token.createMint(provider.connection, admin, admin.publicKey, null, 6, TOKEN_PROGRAM_ID);
+    await send(await createAddQuoteMintTx(admin.publicKey, quote.publicKey));
+    return quote;
+}
+
+// Mints UI amount of the quote token to a fresh ATA of owner
+export const fundQuote = async (quote: Token, owner: PublicKey, amount: number) => {
+    const account = await quote.createAssociatedTokenAccount(owner);
+    await quote.mintTo(account, admin, [], amount * 10 ** 6);
+    return account;
+}
+
+export const tokenBalance = async (account: PublicKey) => {
+    const balance = await provider.connection.getTokenAccountBalance(account, 'confirmed');
+    return Number(balance.value.amount);
+}
+
+// A master edition NFT held by owner, who is also its verified creator
+export const mintNft = async (owner: Keypair) => {
+    const nft = await Token.createMint(provider.connection, owner, owner.publicKey, owner.publicKey, 0, TOKEN_PROGRAM_ID);
+    const account = await nft.createAssociatedTokenAccount(owner.publicKey);
+    await nft.mintTo(account, owner, [], 1);
+
+    const metadata = await getMetadata(nft.publicKey);
+    const accounts = {
+        metadata,
+        mint: nft.publicKey,
+        mintAuthority: owner.publicKey,
+        payer: owner.publicKey,
+        updateAuthority: owner.publicKey,
+    };
+    const tx = new Transaction();
+    tx.add(createCreateMetadataAccountV3Instruction(accounts, {
+        createMetadataAccountArgsV3: {
+            data: {
+                name: 'Test',
+                symbol: 'TEST',
+                uri: '',
+                sellerFeeBasisPoints: 500,
+                creators: [{ address: owner.publicKey, verified: true, share: 100 }],
+                collection: null,
+                uses: null,
+            },
+            isMutable: true,
+            collectionDetails: null,
+        },
+    }));
+    tx.add(createCreateMasterEditionV3Instruction(
+        { ...accounts, edition: await getMasterEdition(nft.publicKey) },
+        { createMasterEditionArgs: { maxSupply: 0 } },
+    ));
+    await send(tx, [owner]);
+
+    return nft.publicKey;
+}
+
+export interface Listing {
+    startPrice?: number,
+    duration?: number,
+    sealed?: SealedConfig,
+    buyNow?: BuyNowConfig,
+    reservePrice?: number,
+    softClose?: SoftCloseConfig,
+}
+
+// Lists the NFT of seller for a day, returning the auction PDA and the reserve salt
+export const list = async (seller: Keypair, mint: PublicKey, quoteMint: PublicKey, listing: Listing = {}) => {
+    const reserveSalt = randomBytes(32);
+    const tx = await createAuctionTx(
+        seller.publicKey,
+        mint,
+        listing.startPrice ?? 1,
+        listing.duration ?? DAY,
+        undefined,
+        listing.sealed,
+        listing.buyNow,
+        listing.reservePrice,
+        reserveSalt,
+        quoteMint,
+        listing.softClose,
+    );
+    await send(tx, [seller]);
+
+    // The auction is the first account of every create instruction
+    const auction = tx.instructions[tx.instructions.length - 1].keys[0].pubkey;
+    return { auction, reserveSalt };
+}
+
+export const now = async () => {
+    const clock = await provider.connection.getAccountInfo(SYSVAR_CLOCK_PUBKEY, 'confirmed');
+    return clock.data.readUIntLE(32, 6);
+}
+
+// Waits until the cluster clock passes timestamp
+export const waitUntil = async (timestamp: number | anchor.BN) => {
+    while (await now() <= Number(timestamp)) {
+        await new Promise((resolve) => setTimeout(resolve, 500));
+    }
+}
@@ -0,0 +1,99 @@
+import { Token } from '@solana/spl-token';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { assert } from 'chai';
+
+import {
+    createAuctionClaimTx,
+    createPlaceBidTx,
+    getAuctionState,
+    getBidBookState,
+} from '../cli/script';
+import { getAssociatedTokenAccount } from '../cli/utils';
+import {
+    fundQuote,
+    list,
+    mintNft,
+    newQuoteMint,
+    newUser,
+    send,
+    setup,
+    tokenBalance,
+    waitUntil,
+} from './helpers';
+
+const UNIT = 10 ** 6;
+
+describe('proxy bidding', () => {
+    let seller: Keypair;
+    let alice: Keypair;
+    let bob: Keypair;
+    let carol: Keypair;
+    let quote: Token;
+    let auction: PublicKey;
+
+    const balance = async (user: Keypair) => {
+        return tokenBalance(await getAssociatedTokenAccount(user.publicKey, quote.publicKey));
+    }
+
+    before(async () => {
+        await setup();
+        seller = await newUser();
+        alice = await newUser();
+        bob = await newUser();
+        carol = await newUser();
+        quote = await newQuoteMint();
+        for (const bidder of [alice, bob, carol]) {
+            await fundQuote(quote, bidder.publicKey, 100);
+        }
+
+        const mint = await mintNft(seller);
+        // Long enough for every bid to land before the end
+        ({ auction } = await list(seller, mint, quote.publicKey, { duration: 30 }));
+    });
+
+    it('answers a bid within the ceiling without taking the bidder funds', async () => {
+        await send(await createPlaceBidTx(alice.publicKey, auction, 2, 10), [alice]);
+        assert.equal(await balance(alice), 90 * UNIT);
+
+        await send(await createPlaceBidTx(bob.publicKey, auction, 5), [bob]);
+        const state = await getAuctionState(auction);
+        assert.ok(state.bidder.equals(alice.publicKey));
+        assert.isAbove(state.currentBid.toNumber(), 5 * UNIT);
+        assert.isAtMost(state.currentBid.toNumber(), 10 * UNIT);
+        assert.equal(await balance(bob), 100 * UNIT);
+        assert.equal((await getBidBookState(auction)).counterBidCount.toNumber(), 1);
+    });
+
+    it('raises the ceiling of the leader without raising the price', async () => {
+        const before = await getAuctionState(auction);
+        await send(await createPlaceBidTx(alice.publicKey, auction, 20, 20), [alice]);
+
+        const state = await getAuctionState(auction);
+        assert.equal(state.currentBid.toNumber(), before.currentBid.toNumber());
+        assert.equal(state.escrowedBid.toNumber(), 20 * UNIT);
+        // Only the difference to the last ceiling is escrowed
+        assert.equal(await balance(alice), 80 * UNIT);
+    });
+
+    it('takes a plain bid of the leader as the new price', async () => {
+        await send(await createPlaceBidTx(alice.publicKey, auction, 25), [alice]);
+
+        const state = await getAuctionState(auction);
+        assert.equal(state.currentBid.toNumber(), 25 * UNIT);
+        assert.equal(state.escrowedBid.toNumber(), 25 * UNIT);
+        assert.equal(await balance(alice), 75 * UNIT);
+    });
+
+    it('refunds the unused ceiling when outbid and at settlement', async () => {
+        await send(await createPlaceBidTx(carol.publicKey, auction, 21, 40), [carol]);
+        const state = await getAuctionState(auction);
+        assert.ok(state.bidder.equals(carol.publicKey));
+        assert.isBelow(state.currentBid.toNumber(), 40 * UNIT);
+        assert.equal(await balance(alice), 100 * UNIT);
+        assert.equal(await balance(carol), 60 * UNIT);
+
+        await waitUntil(state.endTime);
+        await send(await createAuctionClaimTx(carol.publicKey, auction), [carol]);
+        assert.equal(await balance(carol), 100 * UNIT - state.currentBid.toNumber());
+    });
+});
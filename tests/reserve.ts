@@ -0,0 +1,57 @@
+import { Token } from '@solana/spl-token';
+import { Keypair } from '@solana/web3.js';
+import { assert } from 'chai';
+
+import {
+    createAuctionClaimTx,
+    createPlaceBidTx,
+    getAuctionState,
+} from '../cli/script';
+import { getAssociatedTokenAccount } from '../cli/utils';
+import {
+    DAY,
+    expectFailure,
+    fundQuote,
+    list,
+    mintNft,
+    newQuoteMint,
+    newUser,
+    send,
+    setup,
+    tokenBalance,
+    waitUntil,
+} from './helpers';
+
+describe('hidden reserve', () => {
+    let seller: Keypair;
+    let bidder: Keypair;
+    let quote: Token;
+
+    before(async () => {
+        await setup();
+        seller = await newUser();
+        bidder = await newUser();
+        quote = await newQuoteMint();
+        await fundQuote(quote, bidder.publicKey, 100);
+    });
+
+    it('calls the sale off when the seller never reveals the reserve', async () => {
+        const mint = await mintNft(seller);
+        const { auction } = await list(seller, mint, quote.publicKey, { reservePrice: 5 });
+        await send(await createPlaceBidTx(bidder.publicKey, auction, 2), [bidder]);
+        const bidderAccount = await getAssociatedTokenAccount(bidder.publicKey, quote.publicKey);
+        assert.equal(await tokenBalance(bidderAccount), 98 * 10 ** 6);
+
+        // Within a day of the end only the seller can settle
+        const { endTime } = await getAuctionState(auction);
+        await waitUntil(endTime);
+        await expectFailure(
+            createAuctionClaimTx(bidder.publicKey, auction).then((tx) => send(tx, [bidder]))
+        );
+
+        await waitUntil(endTime.toNumber() + DAY);
+        await send(await createAuctionClaimTx(bidder.publicKey, auction), [bidder]);
+        assert.equal(await tokenBalance(bidderAccount), 100 * 10 ** 6);
+        assert.equal(await tokenBalance(await getAssociatedTokenAccount(seller.publicKey, mint)), 1);
+    });
+});
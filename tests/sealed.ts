@@ -0,0 +1,89 @@
+import { Token, TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { assert } from 'chai';
+import { randomBytes } from 'crypto';
+
+import {
+    createAuctionClaimTx,
+    createCommitBidTx,
+    createRevealBidTx,
+    getAuctionState,
+} from '../cli/script';
+import { SETTLE_FIRST_PRICE } from '../cli/types';
+import { getAssociatedTokenAccount } from '../cli/utils';
+import {
+    admin,
+    DAY,
+    expectFailure,
+    fundQuote,
+    getGlobalAuthority,
+    list,
+    mintNft,
+    newQuoteMint,
+    newUser,
+    provider,
+    send,
+    setup,
+    tokenBalance,
+    waitUntil,
+} from './helpers';
+
+describe('sealed auction', () => {
+    let seller: Keypair;
+    let bidder: Keypair;
+    let quote: Token;
+    let globalAuthority: PublicKey;
+
+    before(async () => {
+        await setup();
+        seller = await newUser();
+        bidder = await newUser();
+        quote = await newQuoteMint();
+        await fundQuote(quote, bidder.publicKey, 100);
+        globalAuthority = await getGlobalAuthority();
+    });
+
+    it('does not let the winner claim another escrowed NFT', async () => {
+        const mintA = await mintNft(seller);
+        const mintB = await mintNft(seller);
+        // mintB sits in escrow under the same global authority
+        await list(seller, mintB, quote.publicKey);
+        const { auction } = await list(seller, mintA, quote.publicKey, {
+            sealed: { revealDuration: DAY, settlementType: SETTLE_FIRST_PRICE },
+        });
+
+        const salt = randomBytes(32);
+        await send(await createCommitBidTx(bidder.publicKey, auction, 2, 2, salt), [bidder]);
+        const state = await getAuctionState(auction);
+        await waitUntil(state.endTime);
+        await send(await createRevealBidTx(bidder.publicKey, auction, 2, salt), [bidder]);
+        await waitUntil(state.revealEndTime);
+
+        // With the winner's NFT ATAs in place, the claim instruction is the last of the tx
+        const winnerAtaA = await new Token(provider.connection, mintA, TOKEN_PROGRAM_ID, admin)
+            .createAssociatedTokenAccount(bidder.publicKey);
+        const winnerAtaB = await new Token(provider.connection, mintB, TOKEN_PROGRAM_ID, admin)
+            .createAssociatedTokenAccount(bidder.publicKey);
+        const auctionAtaA = await getAssociatedTokenAccount(globalAuthority, mintA);
+        const auctionAtaB = await getAssociatedTokenAccount(globalAuthority, mintB);
+
+        // Point the claim at the other escrowed NFT
+        const swap = new Map([
+            [mintA.toBase58(), mintB],
+            [auctionAtaA.toBase58(), auctionAtaB],
+            [winnerAtaA.toBase58(), winnerAtaB],
+        ]);
+        const tx = await createAuctionClaimTx(bidder.publicKey, auction);
+        const claim = tx.instructions[tx.instructions.length - 1];
+        claim.keys = claim.keys.map((key) =>
+            swap.has(key.pubkey.toBase58()) ? { ...key, pubkey: swap.get(key.pubkey.toBase58()) } : key
+        );
+        await expectFailure(send(tx, [bidder]));
+        assert.equal(await tokenBalance(auctionAtaB), 1);
+
+        await send(await createAuctionClaimTx(bidder.publicKey, auction), [bidder]);
+        assert.equal(await tokenBalance(winnerAtaA), 1);
+        assert.equal(await tokenBalance(winnerAtaB), 0);
+        assert.equal(await tokenBalance(auctionAtaB), 1);
+    });
+});